@@ -47,6 +47,14 @@ pub struct CmdArgs {
     /// Verbose output
     #[cfg_attr(feature = "cli", arg(long))]
     pub verbose: bool,
+
+    /// Organize the requires of the checked files and write them back before checking
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub fix: bool,
+
+    /// Report the cycles of modules requiring each other while they load
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub require_cycles: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -0,0 +1,37 @@
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, organize_requires};
+
+/// Organize the requires of the files and write the changed ones back, the analysis is
+/// updated with their new text. Returns the number of changed files
+pub fn fix_files(analysis: &mut EmmyLuaAnalysis, file_ids: &[FileId]) -> usize {
+    let mut fixed_files = Vec::new();
+    for file_id in file_ids {
+        let Some(semantic_model) = analysis.compilation.get_semantic_model(*file_id) else {
+            continue;
+        };
+        let Some(edits) = organize_requires(&semantic_model) else {
+            continue;
+        };
+
+        let mut text = semantic_model.get_document().get_text().to_string();
+        // the edits are sorted and don't overlap
+        for (range, new_text) in edits.into_iter().rev() {
+            text.replace_range(
+                usize::from(range.start())..usize::from(range.end()),
+                &new_text,
+            );
+        }
+
+        let Some(path) = analysis.compilation.get_db().get_vfs().get_file_path(file_id) else {
+            continue;
+        };
+        if let Err(err) = std::fs::write(path, &text) {
+            log::error!("Failed to write {:?}: {}", path, err);
+            continue;
+        }
+        fixed_files.push((path.clone(), Some(text)));
+    }
+
+    let fixed_count = fixed_files.len();
+    analysis.update_files_by_path(fixed_files);
+    fixed_count
+}
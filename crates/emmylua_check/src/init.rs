@@ -1,5 +1,6 @@
 use emmylua_code_analysis::{
-    EmmyLuaAnalysis, Emmyrc, LuaFileInfo, load_configs, load_workspace_files, update_code_style,
+    EmmyLuaAnalysis, Emmyrc, LuaFileInfo, LuaIndexCache, load_configs, load_workspace_files,
+    update_code_style,
 };
 use fern::Dispatch;
 use log::LevelFilter;
@@ -63,7 +64,7 @@ pub fn setup_logger(verbose: bool) {
 
 pub fn load_workspace(
     main_path: PathBuf,
-    mut workspace_folders: Vec<PathBuf>,
+    workspace_folders: Vec<PathBuf>,
     config_paths: Option<Vec<PathBuf>>,
     ignore: Option<Vec<String>>,
 ) -> Option<EmmyLuaAnalysis> {
@@ -95,6 +96,8 @@ pub fn load_workspace(
 
     let mut analysis = EmmyLuaAnalysis::new();
     analysis.update_config(emmyrc.clone().into());
+    analysis
+        .set_index_cache(LuaIndexCache::default_dir(&workspace_folders).map(LuaIndexCache::new));
     analysis.init_std_lib(None);
 
     for path in &workspace_folders {
@@ -106,12 +109,25 @@ pub fn load_workspace(
     }
 
     for lib in &emmyrc.workspace.library {
-        analysis.add_library_workspace(PathBuf::from_str(lib).unwrap());
-        workspace_folders.push(PathBuf::from_str(lib).unwrap());
+        let lib_root = PathBuf::from_str(lib).unwrap();
+        let files = load_files(&vec![lib_root.clone()], &analysis.emmyrc, ignore.clone());
+        analysis.add_library_workspace(lib_root, files);
     }
 
-    let file_infos = collect_files(&workspace_folders, &analysis.emmyrc, ignore);
-    let files = file_infos
+    let files = load_files(&workspace_folders, &analysis.emmyrc, ignore);
+    analysis.update_files_by_path(files);
+
+    Some(analysis)
+}
+
+/// Collect the lua files of the workspaces, the `.editorconfig` files update the code style
+fn load_files(
+    workspaces: &Vec<PathBuf>,
+    emmyrc: &Emmyrc,
+    ignore: Option<Vec<String>>,
+) -> Vec<(PathBuf, Option<String>)> {
+    let file_infos = collect_files(workspaces, emmyrc, ignore);
+    file_infos
         .into_iter()
         .filter_map(|file| {
             if file.path.ends_with(".editorconfig") {
@@ -130,10 +146,7 @@ pub fn load_workspace(
                 Some(file.into_tuple())
             }
         })
-        .collect();
-    analysis.update_files_by_path(files);
-
-    Some(analysis)
+        .collect()
 }
 
 pub fn collect_files(
@@ -1,6 +1,8 @@
 pub mod cmd_args;
+mod fix;
 mod init;
 mod output;
+mod require_cycles;
 mod terminal_display;
 
 pub use cmd_args::*;
@@ -30,7 +32,7 @@ pub async fn run_check(cmd_args: CmdArgs) -> Result<(), Box<dyn Error + Sync + S
         .ok_or("Failed to load workspace")?
         .clone();
 
-    let analysis = match init::load_workspace(
+    let mut analysis = match init::load_workspace(
         main_path.clone(),
         workspaces.clone(),
         cmd_args.config,
@@ -44,6 +46,16 @@ pub async fn run_check(cmd_args: CmdArgs) -> Result<(), Box<dyn Error + Sync + S
 
     let db = analysis.compilation.get_db();
     let need_check_files = db.get_module_index().get_main_workspace_file_ids();
+    if cmd_args.fix {
+        let fixed_count = fix::fix_files(&mut analysis, &need_check_files);
+        eprintln!("Fixed {} files", fixed_count);
+    }
+
+    if cmd_args.require_cycles {
+        let db = analysis.compilation.get_db();
+        let cycle_count = require_cycles::report_require_cycles(db, &need_check_files);
+        eprintln!("Found {} require cycles", cycle_count);
+    }
 
     let (sender, receiver) = tokio::sync::mpsc::channel(100);
     let analysis = Arc::new(analysis);
@@ -0,0 +1,31 @@
+use std::collections::HashSet;
+
+use emmylua_code_analysis::{DbIndex, FileId};
+
+/// Print the require cycles that pass through the checked files. Returns the number of cycles
+pub fn report_require_cycles(db: &DbIndex, file_ids: &[FileId]) -> usize {
+    let checked_files: HashSet<_> = file_ids.iter().copied().collect();
+    let module_index = db.get_module_index();
+    let cycles = db
+        .get_file_dependencies_index()
+        .get_require_cycles()
+        .into_iter()
+        .filter(|cycle| cycle.iter().any(|file_id| checked_files.contains(file_id)))
+        .collect::<Vec<_>>();
+
+    for cycle in &cycles {
+        // the cycle ends where it starts
+        let names = cycle
+            .iter()
+            .chain(cycle.first())
+            .map(|file_id| {
+                module_index
+                    .get_module(*file_id)
+                    .map(|module_info| module_info.full_module_name.clone())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        eprintln!("Require cycle: {}", names.join(" -> "));
+    }
+    cycles.len()
+}
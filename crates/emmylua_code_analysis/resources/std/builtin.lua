@@ -123,6 +123,10 @@
 --- built-in type for Rawget
 ---@alias std.RawGet<T, K> unknown
 
+---
+--- built-in type for the captures of a Lua pattern
+---@alias std.Captures<Pattern> unknown
+
 ---
 --- built-in type for generic template, for match integer const and true/false
 ---@alias std.ConstTpl<T> unknown
@@ -173,3 +177,23 @@
 --- - getter: Getter method name. Takes precedence over `convention`.
 --- - setter: Setter method name. Takes precedence over `convention`.
 ---@attribute field_accessor(convention: "camelCase"|"PascalCase"|"snake_case"|nil, getter: string?, setter: string?)
+
+--- Marks a parameter of the function as a `string.format` style format string, the specifiers of a literal
+--- format string are checked against the arguments that follow it.
+---
+--- Receives the index of the format string parameter, starting from 1 and not counting `self`.
+---@attribute format_string(index: integer)
+
+--- Marks a parameter of the function as a Lua pattern, a literal pattern is checked for syntax errors.
+---
+--- Parameters:
+--- - `index` - The index of the pattern parameter, starting from 1 and not counting `self`.
+--- - `plain` - The index of the parameter that turns off the pattern matching when it is `true`.
+---@attribute lua_pattern(index: integer, plain: integer?)
+
+--- Marks an `if` chain dispatching on an enum or a union of literals, the values no branch compares with are reported.
+--- A chain ending in an `else` that calls `error` is checked without it.
+---@attribute exhaustive()
+
+--- Marks a method as overriding a method of a super type, it is an error when no super type has it.
+---@attribute override()
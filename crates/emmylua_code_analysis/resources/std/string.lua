@@ -81,6 +81,7 @@ function string.dump(func, strip) end
 ---@return integer? end
 ---@return string?... captured
 ---@nodiscard
+---@[lua_pattern(2, 4)]
 function string.find(s, pattern, init, plain) end
 
 ---
@@ -116,6 +117,7 @@ function string.find(s, pattern, init, plain) end
 ---@param ... any
 ---@return string
 ---@nodiscard
+---@[format_string(1)]
 function string.format(fmt, ...) end
 
 ---
@@ -142,9 +144,11 @@ function string.format(fmt, ...) end
 ---
 --- For this function, a caret '`^`' at the start of a pattern does not work as
 --- an anchor, as this would prevent the iteration.
+---@generic P: string
 ---@param s string
----@param pattern string
----@return fun():string?...
+---@param pattern std.ConstTpl<P>
+---@return fun():std.Captures<P>
+---@[lua_pattern(2)]
 function string.gmatch(s, pattern) end
 
 ---
@@ -196,6 +200,7 @@ function string.gmatch(s, pattern) end
 ---@param n? integer
 ---@return string
 ---@return integer count
+---@[lua_pattern(2)]
 function string.gsub(s, pattern, repl, n) end
 
 ---
@@ -219,10 +224,12 @@ function string.lower(s) end
 --- it returns **nil**. If `pattern` specifies no captures, then the whole match
 --- is returned. A third, optional numerical argument `init` specifies where
 --- to start the search; its default value is 1 and can be negative.
+---@generic P: string
 ---@param s string
----@param pattern string
+---@param pattern std.ConstTpl<P>
 ---@param init? integer
----@return string?...
+---@return std.Captures<P>
+---@[lua_pattern(2)]
 function string.match(s, pattern, init) end
 
 ---@version >5.3
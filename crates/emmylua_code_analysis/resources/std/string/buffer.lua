@@ -152,6 +152,7 @@ function buf:put(data, ...) end
 ---@param format string
 ---@param ...    string.buffer.data
 ---@return string.buffer
+---@[format_string(1)]
 function buf:putf(format, ...) end
 
 
@@ -333,10 +333,12 @@ pub fn analyze_call_expr(analyzer: &mut DeclAnalyzer, expr: LuaCallExpr) -> Opti
             let file_id = analyzer.get_file_id();
             let module_info = analyzer.db.get_module_index().find_module(&module_path)?;
             let module_file_id = module_info.file_id;
-            analyzer
-                .db
-                .get_file_dependencies_index_mut()
-                .add_required_file(file_id, module_file_id);
+            let dependency_index = analyzer.db.get_file_dependencies_index_mut();
+            dependency_index.add_required_file(file_id, module_file_id);
+            // 函数体中的 require 在调用时才执行
+            if expr.ancestors::<LuaClosureExpr>().next().is_none() {
+                dependency_index.add_load_required_file(file_id, module_file_id);
+            }
         }
     }
 
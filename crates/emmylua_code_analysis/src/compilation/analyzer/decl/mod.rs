@@ -15,7 +15,7 @@ use rowan::{TextRange, TextSize, WalkEvent};
 
 use crate::{
     FileId,
-    db_index::{LuaDecl, LuaDeclId, LuaDeclarationTree, LuaScopeId},
+    db_index::{LuaDecl, LuaDeclId, LuaDeclarationTree, LuaLabel, LuaScopeId},
 };
 
 pub struct DeclAnalysisPipeline;
@@ -79,6 +79,9 @@ fn walk_node_enter(analyzer: &mut DeclAnalyzer, node: LuaAst) {
         LuaAst::LuaRepeatStat(stat) => {
             analyzer.create_scope(stat.get_range(), LuaScopeKind::Repeat);
         }
+        LuaAst::LuaLabelStat(stat) => {
+            stats::analyze_label_stat(analyzer, stat);
+        }
         LuaAst::LuaNameExpr(expr) => {
             exprs::analyze_name_expr(analyzer, expr);
         }
@@ -86,7 +89,7 @@ fn walk_node_enter(analyzer: &mut DeclAnalyzer, node: LuaAst) {
             exprs::analyze_index_expr(analyzer, expr);
         }
         LuaAst::LuaClosureExpr(expr) => {
-            analyzer.create_scope(expr.get_range(), LuaScopeKind::Normal);
+            analyzer.create_scope(expr.get_range(), LuaScopeKind::Closure);
             exprs::analyze_closure_expr(analyzer, expr);
         }
         LuaAst::LuaTableExpr(expr) => {
@@ -228,6 +231,13 @@ impl<'a> DeclAnalyzer<'a> {
         id
     }
 
+    pub fn add_label(&mut self, name: &str, range: TextRange) {
+        if let Some(scope_id) = self.scopes.last() {
+            let label = LuaLabel::new(name, range, *scope_id);
+            self.decl.add_label(label);
+        }
+    }
+
     pub fn find_decl(&self, name: &str, position: TextSize) -> Option<&LuaDecl> {
         self.decl.find_local_decl(name, position)
     }
@@ -1,7 +1,7 @@
 use emmylua_parser::{
     LuaAssignStat, LuaAstNode, LuaAstToken, LuaExpr, LuaForRangeStat, LuaForStat, LuaFuncStat,
-    LuaIndexExpr, LuaIndexKey, LuaLocalFuncStat, LuaLocalStat, LuaSyntaxId, LuaSyntaxKind,
-    LuaVarExpr,
+    LuaIndexExpr, LuaIndexKey, LuaLabelStat, LuaLocalFuncStat, LuaLocalStat, LuaSyntaxId,
+    LuaSyntaxKind, LuaVarExpr,
 };
 
 use crate::{
@@ -327,3 +327,9 @@ pub fn analyze_local_func_stat(analyzer: &mut DeclAnalyzer, stat: LuaLocalFuncSt
 
     Some(())
 }
+
+pub fn analyze_label_stat(analyzer: &mut DeclAnalyzer, stat: LuaLabelStat) -> Option<()> {
+    let name_token = stat.get_label_name_token()?;
+    analyzer.add_label(name_token.get_name_text(), name_token.get_range());
+    Some(())
+}
@@ -0,0 +1,144 @@
+use std::collections::{BTreeMap, HashSet};
+
+use smol_str::SmolStr;
+
+use crate::{
+    FileId, LuaMemberOwner, LuaTypeOwner, RenderLevel, db_index::DbIndex, humanize_type,
+    profile::Profile,
+};
+
+/// Record the files that declare the globals and types used by the analyzed files, so the
+/// analyzed files are analyzed again when one of those files changes. The used names are
+/// recorded too, a file declaring one of them later has them analyzed again.
+pub fn analyze_dependencies(db: &mut DbIndex, file_ids: &HashSet<FileId>) {
+    let _p = Profile::cond_new("dependency analyze", file_ids.len() > 1);
+    let mut used_names = Vec::new();
+    let mut dependencies = Vec::new();
+    for file_id in file_ids {
+        if let Some(names) = db.get_reference_index().get_file_global_names(file_id) {
+            for name in names {
+                used_names.push((*file_id, name.clone()));
+                let Some(decl_ids) = db.get_global_index().get_global_decl_ids(name) else {
+                    continue;
+                };
+                for decl_id in decl_ids {
+                    dependencies.push((*file_id, decl_id.file_id));
+                }
+            }
+        }
+
+        for type_decl_id in db.get_reference_index().get_file_type_references(file_id) {
+            used_names.push((*file_id, SmolStr::new(type_decl_id.get_name())));
+            let Some(type_decl) = db.get_type_index().get_type_decl(&type_decl_id) else {
+                continue;
+            };
+
+            for location in type_decl.get_locations() {
+                dependencies.push((*file_id, location.file_id));
+            }
+        }
+    }
+
+    let dependency_index = db.get_file_dependencies_index_mut();
+    for (file_id, name) in used_names {
+        dependency_index.add_used_name(file_id, name);
+    }
+    for (file_id, declaration_file_id) in dependencies {
+        dependency_index.add_declaration_file(file_id, declaration_file_id);
+    }
+}
+
+/// What the files using the file see of it: the globals and types it declares with their types,
+/// the members it adds by the name of their owner, and the type of its module under the empty
+/// name. The files using it are analyzed again when this changes.
+pub fn get_file_exports(db: &DbIndex, file_id: FileId) -> BTreeMap<SmolStr, String> {
+    let mut exports: BTreeMap<SmolStr, String> = BTreeMap::new();
+    let type_index = db.get_type_index();
+    if let Some(decl_tree) = db.get_decl_index().get_decl_tree(&file_id) {
+        let mut decls = decl_tree
+            .get_decls()
+            .values()
+            .filter(|decl| decl.is_global())
+            .collect::<Vec<_>>();
+        decls.sort_by_key(|decl| decl.get_position());
+        for decl in decls {
+            let typ = type_index
+                .get_type_cache(&LuaTypeOwner::Decl(decl.get_id()))
+                .map(|cache| humanize_type(db, cache.as_type(), RenderLevel::Detailed))
+                .unwrap_or_default();
+            let export = exports.entry(SmolStr::new(decl.get_name())).or_default();
+            export.push_str(&typ);
+            export.push(';');
+        }
+    }
+
+    for type_decl_id in type_index.get_file_types(&file_id).into_iter().flatten() {
+        let Some(type_decl) = type_index.get_type_decl(type_decl_id) else {
+            continue;
+        };
+        let mut parts = vec![format!(
+            "{}{}{}{}",
+            type_decl.is_class(),
+            type_decl.is_enum(),
+            type_decl.is_alias(),
+            type_decl.is_attribute()
+        )];
+        if let Some(alias) = type_decl.get_alias_ref() {
+            parts.push(humanize_type(db, alias, RenderLevel::Detailed));
+        }
+        for super_type in type_index
+            .get_super_types_iter(type_decl_id)
+            .into_iter()
+            .flatten()
+        {
+            parts.push(humanize_type(db, super_type, RenderLevel::Detailed));
+        }
+        exports
+            .entry(SmolStr::new(type_decl_id.get_name()))
+            .or_default()
+            .push_str(&parts.join(";"));
+    }
+
+    // members of tables and classes declared in other files are seen through their owners
+    let member_index = db.get_member_index();
+    let mut members = member_index
+        .get_file_members(file_id)
+        .into_iter()
+        .filter_map(|member| {
+            let owner_name = match member_index.get_current_owner(&member.get_id())? {
+                LuaMemberOwner::Type(type_decl_id) => type_decl_id.get_name(),
+                LuaMemberOwner::GlobalPath(global_id) => {
+                    global_id.get_name().split('.').next().unwrap_or_default()
+                }
+                LuaMemberOwner::Element(_) | LuaMemberOwner::LocalUnresolve => "",
+            };
+            let typ = type_index
+                .get_type_cache(&LuaTypeOwner::Member(member.get_id()))
+                .map(|cache| humanize_type(db, cache.as_type(), RenderLevel::Detailed))
+                .unwrap_or_default();
+            Some((
+                SmolStr::new(owner_name),
+                format!("{}: {}", member.get_key().to_path(), typ),
+            ))
+        })
+        .collect::<Vec<_>>();
+    members.sort();
+    for (owner_name, member) in members {
+        let export = exports.entry(owner_name).or_default();
+        export.push(';');
+        export.push_str(&member);
+    }
+
+    if let Some(export_type) = db
+        .get_module_index()
+        .get_module(file_id)
+        .and_then(|module| module.export_type.as_ref())
+    {
+        exports
+            .entry(SmolStr::default())
+            .or_default()
+            .push_str(&humanize_type(db, export_type, RenderLevel::Detailed));
+    }
+
+    exports
+}
@@ -18,6 +18,10 @@ pub fn analyze_tag_attribute_use(
     tag_use: LuaDocTagAttributeUse,
 ) -> Option<()> {
     let owner = attribute_use_get_owner(analyzer, &tag_use);
+    // 语句上的特性由检查直接读取
+    if let Some(LuaAst::LuaIfStat(_)) = owner {
+        return Some(());
+    }
     let owner_id = match get_owner_id(analyzer, owner.clone(), true) {
         Some(id) => id,
         None => {
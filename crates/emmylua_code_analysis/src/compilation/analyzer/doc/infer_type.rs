@@ -127,6 +127,7 @@ fn infer_buildin_or_ref_type(
         "unknown" => LuaType::Unknown,
         "nil" | "void" => LuaType::Nil,
         "any" => LuaType::Any,
+        "never" => LuaType::Never,
         "userdata" => LuaType::Userdata,
         "thread" => LuaType::Thread,
         "boolean" | "bool" => LuaType::Boolean,
@@ -309,6 +310,16 @@ fn infer_special_generic_type(
                 LuaAliasCallType::new(LuaAliasCallKind::RawGet, params).into(),
             ));
         }
+        "std.Captures" => {
+            let mut params = Vec::new();
+            for param in generic_type.get_generic_types()?.get_types() {
+                let param_type = infer_type(analyzer, param);
+                params.push(param_type);
+            }
+            return Some(LuaType::Call(
+                LuaAliasCallType::new(LuaAliasCallKind::Captures, params).into(),
+            ));
+        }
         "TypeGuard" => {
             let first_doc_param_type = generic_type.get_generic_types()?.get_types().next()?;
             let first_param = infer_type(analyzer, first_doc_param_type);
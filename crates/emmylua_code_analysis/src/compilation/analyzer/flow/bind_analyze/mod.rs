@@ -3,7 +3,7 @@ mod comment;
 mod exprs;
 mod stats;
 
-use emmylua_parser::{LuaAst, LuaAstNode, LuaBlock, LuaChunk, LuaExpr};
+use emmylua_parser::{LuaAst, LuaAstNode, LuaBlock, LuaChunk, LuaExpr, LuaStat};
 
 use crate::{
     FlowAntecedent, FlowId, FlowNodeKind,
@@ -35,8 +35,18 @@ fn bind_block(binder: &mut FlowBinder, block: LuaBlock, current: FlowId) -> Flow
     let mut return_flow_id = current;
     let mut can_change_flow = true;
     for node in block.children::<LuaAst>() {
-        let node_flow_id = bind_node(binder, node, return_flow_id);
-        if can_change_flow {
+        let is_label = matches!(node, LuaAst::LuaLabelStat(_));
+        if LuaStat::can_cast(node.syntax().kind().into()) && !is_label {
+            binder.bind_syntax_node(node.get_syntax_id(), return_flow_id);
+        }
+
+        let node_flow_id = bind_node(binder, node.clone(), return_flow_id);
+        if is_label {
+            // a label can be reached by goto, so the flow continues from it
+            binder.bind_syntax_node(node.get_syntax_id(), node_flow_id);
+            return_flow_id = node_flow_id;
+            can_change_flow = true;
+        } else if can_change_flow {
             return_flow_id = node_flow_id;
         }
 
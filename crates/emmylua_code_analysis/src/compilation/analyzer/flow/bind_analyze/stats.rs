@@ -189,7 +189,7 @@ pub fn bind_goto_stat(binder: &mut FlowBinder, goto_stat: LuaGotoStat, current:
 
     let label_name = label_token.get_name_text();
     let return_flow_id = binder.create_return();
-    binder.cache_goto_flow(closure_id, label_token.clone(), label_name, return_flow_id);
+    binder.cache_goto_flow(closure_id, label_name, return_flow_id);
     binder.add_antecedent(return_flow_id, current);
     return_flow_id
 }
@@ -317,6 +317,8 @@ pub fn bind_if_stat(binder: &mut FlowBinder, if_stat: LuaIfStat, current: FlowId
     if let Some(condition_expr) = if_stat.get_condition_expr() {
         bind_condition_expr(binder, condition_expr, current, then_label, else_label);
     }
+    // 每个 elseif 的条件都接在上一个条件为假的流之后
+    else_label = finish_flow_label(binder, else_label, current);
 
     if let Some(then_block) = if_stat.get_block() {
         let then_label = finish_flow_label(binder, then_label, current);
@@ -329,7 +331,7 @@ pub fn bind_if_stat(binder: &mut FlowBinder, if_stat: LuaIfStat, current: FlowId
     }
 
     for elseif_clause in if_stat.get_else_if_clause_list() {
-        let pre_elseif_label = finish_flow_label(binder, else_label, current);
+        let pre_elseif_label = else_label;
         let post_elseif_label = binder.create_branch_label();
         let elseif_then_label = binder.create_branch_label();
         if let Some(condition_expr) = elseif_clause.get_condition_expr() {
@@ -362,6 +364,14 @@ pub fn bind_if_stat(binder: &mut FlowBinder, if_stat: LuaIfStat, current: FlowId
         binder.add_antecedent(post_if_label, else_label);
     }
 
+    // every branch has returned, so nothing after the if statement is reachable
+    if binder
+        .get_flow(post_if_label)
+        .is_some_and(|flow_node| flow_node.antecedent.is_none())
+    {
+        return binder.unreachable;
+    }
+
     finish_flow_label(binder, post_if_label, else_label)
 }
 
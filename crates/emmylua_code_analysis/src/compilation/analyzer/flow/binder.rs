@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use emmylua_parser::{LuaAstPtr, LuaExpr, LuaNameToken, LuaSyntaxId};
+use emmylua_parser::{LuaAstPtr, LuaExpr, LuaSyntaxId};
 use internment::ArcIntern;
 use rowan::TextSize;
 use smol_str::SmolStr;
@@ -25,6 +25,7 @@ pub struct FlowBinder<'a> {
     multiple_antecedents: Vec<Vec<FlowId>>,
     labels: HashMap<LuaClosureId, HashMap<SmolStr, FlowId>>,
     goto_stats: Vec<GotoCache>,
+    label_jumps: HashMap<FlowId, Vec<FlowId>>,
     bindings: HashMap<LuaSyntaxId, FlowId>,
 }
 
@@ -42,6 +43,7 @@ impl<'a> FlowBinder<'a> {
             break_target_label: FlowId::default(),
             bindings: HashMap::new(),
             goto_stats: Vec::new(),
+            label_jumps: HashMap::new(),
             loop_label: FlowId::default(),
             true_target: FlowId::default(),
             false_target: FlowId::default(),
@@ -157,16 +159,9 @@ impl<'a> FlowBinder<'a> {
         self.bindings.get(&syntax_id).copied()
     }
 
-    pub fn cache_goto_flow(
-        &mut self,
-        closure_id: LuaClosureId,
-        label_token: LuaNameToken,
-        label: &str,
-        flow_id: FlowId,
-    ) {
+    pub fn cache_goto_flow(&mut self, closure_id: LuaClosureId, label: &str, flow_id: FlowId) {
         self.goto_stats.push(GotoCache {
             closure_id,
-            label_token,
             label: SmolStr::new(label),
             flow_id,
         });
@@ -176,6 +171,13 @@ impl<'a> FlowBinder<'a> {
         self.goto_stats.drain(..).collect()
     }
 
+    pub fn add_label_jump(&mut self, label_id: FlowId, goto_flow_id: FlowId) {
+        self.label_jumps
+            .entry(label_id)
+            .or_default()
+            .push(goto_flow_id);
+    }
+
     pub fn get_flow(&self, flow_id: FlowId) -> Option<&FlowNode> {
         self.flow_nodes.get(flow_id.0 as usize)
     }
@@ -193,6 +195,7 @@ impl<'a> FlowBinder<'a> {
             self.multiple_antecedents,
             // self.labels,
             self.bindings,
+            self.label_jumps,
         )
     }
 }
@@ -200,7 +203,6 @@ impl<'a> FlowBinder<'a> {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GotoCache {
     pub closure_id: LuaClosureId,
-    pub label_token: LuaNameToken,
     pub label: SmolStr,
     pub flow_id: FlowId,
 }
@@ -1,14 +1,19 @@
 mod common;
 mod decl;
+mod dependency;
 mod doc;
 mod flow;
 mod infer_cache_manager;
 mod lua;
 mod unresolve;
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use crate::{Emmyrc, InFiled, InferFailReason, WorkspaceId, db_index::DbIndex, profile::Profile};
+pub use dependency::get_file_exports;
 use emmylua_parser::LuaChunk;
 use infer_cache_manager::InferCacheManager;
 use unresolve::UnResolve;
@@ -18,6 +23,7 @@ pub fn analyze(db: &mut DbIndex, need_analyzed_files: Vec<InFiled<LuaChunk>>, co
         return;
     }
 
+    let file_ids: HashSet<_> = need_analyzed_files.iter().map(|x| x.file_id).collect();
     let contexts = module_analyze(db, need_analyzed_files, config);
 
     for (workspace_id, mut context) in contexts {
@@ -29,6 +35,8 @@ pub fn analyze(db: &mut DbIndex, need_analyzed_files: Vec<InFiled<LuaChunk>>, co
         run_analysis::<lua::LuaAnalysisPipeline>(db, &mut context);
         run_analysis::<unresolve::UnResolveAnalysisPipeline>(db, &mut context);
     }
+
+    dependency::analyze_dependencies(db, &file_ids);
 }
 
 trait AnalysisPipeline {
@@ -3,9 +3,9 @@ use std::sync::Arc;
 use emmylua_parser::{LuaAstNode, LuaIndexMemberExpr, LuaTableExpr, LuaVarExpr};
 
 use crate::{
-    DbIndex, InferFailReason, InferGuard, InferGuardRef, LuaDocParamInfo, LuaDocReturnInfo,
-    LuaFunctionType, LuaInferCache, LuaSignature, LuaType, SignatureReturnStatus, TypeOps,
-    get_real_type, infer_call_expr_func, infer_expr, infer_table_should_be,
+    AsyncState, DbIndex, InferFailReason, InferGuard, InferGuardRef, LuaDocParamInfo,
+    LuaDocReturnInfo, LuaFunctionType, LuaInferCache, LuaSignature, LuaType, SignatureReturnStatus,
+    TypeOps, get_real_type, infer_call_expr_func, infer_expr, infer_table_should_be,
 };
 
 use super::{
@@ -436,7 +436,10 @@ fn resolve_doc_function(
         .get_mut(&closure_params.signature_id)
         .ok_or(InferFailReason::None)?;
 
-    signature.async_state = doc_func.get_async_state();
+    // 闭包自身的 `---@async` 优先
+    if signature.async_state == AsyncState::None {
+        signature.async_state = doc_func.get_async_state();
+    }
 
     let mut doc_params = doc_func.get_params().to_vec();
     // doc_func 是往上追溯的有效签名, signature 是未解析的签名
@@ -1,7 +1,12 @@
 mod analyzer;
 mod test;
 
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
+
+use smol_str::SmolStr;
 
 use crate::{
     Emmyrc, FileId, InFiled, LuaIndex, LuaInferCache, db_index::DbIndex, semantic::SemanticModel,
@@ -11,6 +16,14 @@ use crate::{
 pub struct LuaCompilation {
     db: DbIndex,
     emmyrc: Arc<Emmyrc>,
+    // the exports and the dependents of the files removed since the last `update_index`
+    removed_files: HashMap<FileId, RemovedFile>,
+}
+
+#[derive(Debug)]
+struct RemovedFile {
+    exports: BTreeMap<SmolStr, String>,
+    dependents: Vec<FileId>,
 }
 
 impl LuaCompilation {
@@ -18,6 +31,7 @@ impl LuaCompilation {
         let mut compilation = Self {
             db: DbIndex::new(),
             emmyrc: emmyrc.clone(),
+            removed_files: HashMap::new(),
         };
 
         compilation.db.update_config(emmyrc.clone());
@@ -36,7 +50,75 @@ impl LuaCompilation {
         ))
     }
 
+    /// Analyze the files, then analyze again the files using one removed by `remove_index` or
+    /// given here when what it exports changed, like the type of a global it declares.
     pub fn update_index(&mut self, file_ids: Vec<FileId>) {
+        self.analyze_files(file_ids.clone());
+
+        let mut changed_files: HashSet<FileId> = file_ids.iter().copied().collect();
+        let removed_files = std::mem::take(&mut self.removed_files);
+        let mut dependents = HashSet::new();
+        for (file_id, removed_file) in &removed_files {
+            if self.collect_changed_dependents(*file_id, &removed_file.exports, &mut dependents) {
+                dependents.extend(removed_file.dependents.iter().copied());
+            }
+            changed_files.insert(*file_id);
+        }
+        // files analyzed before the globals or types they use were declared
+        for file_id in &file_ids {
+            if !removed_files.contains_key(file_id) {
+                self.collect_changed_dependents(*file_id, &BTreeMap::new(), &mut dependents);
+            }
+        }
+
+        let dependents = self.filter_dependents(dependents, &changed_files);
+        if !dependents.is_empty() {
+            self.db.remove_index(dependents.clone());
+            self.analyze_files(dependents);
+        }
+    }
+
+    /// Add the files using the names whose export differs from `old_exports`, and tell whether
+    /// any export differs
+    fn collect_changed_dependents(
+        &self,
+        file_id: FileId,
+        old_exports: &BTreeMap<SmolStr, String>,
+        dependents: &mut HashSet<FileId>,
+    ) -> bool {
+        let exports = analyzer::get_file_exports(&self.db, file_id);
+        if exports == *old_exports {
+            return false;
+        }
+
+        let dependency_index = self.db.get_file_dependencies_index();
+        for name in exports.keys().chain(old_exports.keys()) {
+            if exports.get(name) != old_exports.get(name)
+                && let Some(name_dependents) = dependency_index.get_name_dependents(name)
+            {
+                dependents.extend(name_dependents.iter().copied());
+            }
+        }
+        true
+    }
+
+    fn filter_dependents(
+        &self,
+        dependents: HashSet<FileId>,
+        analyzed_files: &HashSet<FileId>,
+    ) -> Vec<FileId> {
+        let mut dependents: Vec<FileId> = dependents
+            .into_iter()
+            .filter(|file_id| {
+                !analyzed_files.contains(file_id)
+                    && self.db.get_vfs().get_syntax_tree(file_id).is_some()
+            })
+            .collect();
+        dependents.sort();
+        dependents
+    }
+
+    fn analyze_files(&mut self, file_ids: Vec<FileId>) {
         let mut need_analyzed_files = vec![];
         for file_id in file_ids {
             let tree = match self.db.get_vfs().get_syntax_tree(&file_id) {
@@ -56,10 +138,25 @@ impl LuaCompilation {
     }
 
     pub fn remove_index(&mut self, file_ids: Vec<FileId>) {
+        for file_id in &file_ids {
+            if self.removed_files.contains_key(file_id) {
+                continue;
+            }
+            let removed_file = RemovedFile {
+                exports: analyzer::get_file_exports(&self.db, *file_id),
+                dependents: self
+                    .db
+                    .get_file_dependencies_index()
+                    .get_file_dependencies()
+                    .collect_file_dependents(vec![*file_id]),
+            };
+            self.removed_files.insert(*file_id, removed_file);
+        }
         self.db.remove_index(file_ids);
     }
 
     pub fn clear_index(&mut self) {
+        self.removed_files.clear();
         self.db.clear();
     }
 
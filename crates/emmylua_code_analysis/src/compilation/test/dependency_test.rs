@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod test {
+    use crate::{LuaType, VirtualWorkspace};
+
+    #[test]
+    fn test_update_required_file() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def_file(
+            "a.lua",
+            r#"
+            ---@return integer
+            local function f() end
+            return f
+            "#,
+        );
+        ws.def_file(
+            "b.lua",
+            r#"
+            local f = require("a")
+            GlobalValue = f()
+            "#,
+        );
+        assert_eq!(ws.expr_ty("GlobalValue"), ws.ty("integer"));
+
+        ws.def_file(
+            "a.lua",
+            r#"
+            ---@return string
+            local function f() end
+            return f
+            "#,
+        );
+        assert_eq!(ws.expr_ty("GlobalValue"), ws.ty("string"));
+    }
+
+    #[test]
+    fn test_update_global_decl_file() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def_file(
+            "a.lua",
+            r#"
+            ---@return integer
+            function GetValue() end
+            "#,
+        );
+        ws.def_file(
+            "b.lua",
+            r#"
+            ---@class Holder
+            Holder = {}
+            Holder.value = GetValue()
+            "#,
+        );
+        assert_eq!(ws.expr_ty("Holder.value"), ws.ty("integer"));
+
+        ws.def_file(
+            "a.lua",
+            r#"
+            ---@return string
+            function GetValue() end
+            "#,
+        );
+        assert_eq!(ws.expr_ty("Holder.value"), ws.ty("string"));
+    }
+
+    #[test]
+    fn test_declare_global_after_use() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def_file(
+            "b.lua",
+            r#"
+            ---@class Holder
+            Holder = {}
+            Holder.value = GetValue()
+            "#,
+        );
+        ws.def_file(
+            "a.lua",
+            r#"
+            ---@return string
+            function GetValue() end
+            "#,
+        );
+        assert_eq!(ws.expr_ty("Holder.value"), ws.ty("string"));
+    }
+
+    #[test]
+    fn test_keep_dependents_when_exports_unchanged() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def_file(
+            "a.lua",
+            r#"
+            ---@return integer
+            function GetValue() return 1 end
+            "#,
+        );
+        ws.def_file(
+            "b.lua",
+            r#"
+            Value = GetValue()
+            "#,
+        );
+        // change the text of b.lua without analyzing it, it shows whether b.lua is analyzed again
+        let uri = ws.virtual_url_generator.new_uri("b.lua");
+        ws.get_db_mut().get_vfs_mut().set_file_content(
+            &uri,
+            Some(
+                r#"
+                Value = GetValue()
+                Reanalyzed = 1
+                "#
+                .to_string(),
+            ),
+        );
+
+        ws.def_file(
+            "a.lua",
+            r#"
+            ---@return integer
+            function GetValue() return 2 end
+            "#,
+        );
+        assert_eq!(ws.expr_ty("Reanalyzed"), LuaType::Unknown);
+
+        ws.def_file(
+            "a.lua",
+            r#"
+            ---@return string
+            function GetValue() return "" end
+            "#,
+        );
+        assert_eq!(ws.expr_ty("Reanalyzed"), LuaType::IntegerConst(1));
+    }
+
+    #[test]
+    fn test_update_member_of_other_file_class() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def_file(
+            "a.lua",
+            r#"
+            ---@class Foo
+            Foo = {}
+            "#,
+        );
+        ws.def_file(
+            "b.lua",
+            r#"
+            ---@return integer
+            function Foo:bar() end
+            "#,
+        );
+        ws.def_file(
+            "c.lua",
+            r#"
+            Value = Foo:bar()
+            "#,
+        );
+        assert_eq!(ws.expr_ty("Value"), ws.ty("integer"));
+
+        ws.def_file(
+            "b.lua",
+            r#"
+            ---@return string
+            function Foo:bar() end
+            "#,
+        );
+        assert_eq!(ws.expr_ty("Value"), ws.ty("string"));
+    }
+}
@@ -1459,4 +1459,25 @@ _2 = a[1]
         let e_expected = ws.ty("string");
         assert_eq!(e, e_expected);
     }
+
+    #[test]
+    fn test_elseif_chain_narrow() {
+        let mut ws = VirtualWorkspace::new();
+
+        ws.def(
+            r#"
+            local a ---@type "a"|"b"|"c"|"d"
+
+            if a == "a" then
+            elseif a == "b" then
+            elseif "c" == a then
+            else
+                d = a
+            end
+            "#,
+        );
+
+        let d = ws.expr_ty("d");
+        assert_eq!(ws.humanize_type(d), "\"d\"");
+    }
 }
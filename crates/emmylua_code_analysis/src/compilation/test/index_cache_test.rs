@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod test {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::SystemTime,
+    };
+
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{EmmyLuaAnalysis, Emmyrc, LuaIndexCache};
+
+    const LIB_CODE: &str = r#"
+        ---@class LibClass
+        ---@field name string
+        LibValue = {}
+
+        ---@return LibClass
+        function LibValue.create() end
+    "#;
+
+    const USE_CODE: &str = r#"
+        ---@type LibClass
+        LibUse = LibValue.create()
+    "#;
+
+    const MAIN_CODE: &str = r#"
+        local value = LibValue.create()
+        local len = string.len(value.name)
+        print(value.unknown, len, UnknownGlobal)
+    "#;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "emmylua_index_cache_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn cache_files(dir: &Path) -> Vec<(PathBuf, SystemTime)> {
+        let mut files = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                let modified = path.metadata().unwrap().modified().unwrap();
+                (path, modified)
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    fn load_analysis(dir: &Path, index_cache: bool, lib_code: &str) -> EmmyLuaAnalysis {
+        let mut analysis = EmmyLuaAnalysis::new();
+        let mut emmyrc = Emmyrc::default();
+        emmyrc.workspace.enable_index_cache = index_cache;
+        analysis.update_config(Arc::new(emmyrc));
+        analysis.set_index_cache(Some(LuaIndexCache::new(dir.join("cache"))));
+        analysis.init_std_lib(None);
+        let lib_root = dir.join("lib");
+        analysis.add_library_workspace(
+            lib_root.clone(),
+            vec![
+                (lib_root.join("lib.lua"), Some(lib_code.to_string())),
+                (lib_root.join("use.lua"), Some(USE_CODE.to_string())),
+            ],
+        );
+        analysis.add_main_workspace(dir.join("main"));
+        analysis
+    }
+
+    fn diagnose_main(analysis: &mut EmmyLuaAnalysis, dir: &Path) -> Vec<String> {
+        diagnose_code(analysis, dir, MAIN_CODE)
+    }
+
+    fn diagnose_code(analysis: &mut EmmyLuaAnalysis, dir: &Path, code: &str) -> Vec<String> {
+        let file_id = analysis
+            .update_file_by_path(&dir.join("main").join("main.lua"), Some(code.into()))
+            .unwrap();
+        let mut diagnostics = analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap()
+            .into_iter()
+            .map(|diagnostic| format!("{:?} {}", diagnostic.range, diagnostic.message))
+            .collect::<Vec<_>>();
+        diagnostics.sort();
+        diagnostics
+    }
+
+    fn changed_files(
+        saved_files: &[(PathBuf, SystemTime)],
+        files: &[(PathBuf, SystemTime)],
+    ) -> usize {
+        files
+            .iter()
+            .filter(|file| !saved_files.contains(file))
+            .count()
+    }
+
+    #[test]
+    fn test_restore_index_cache() {
+        let dir = test_dir("restore");
+        let mut analysis = load_analysis(&dir, false, LIB_CODE);
+        let expected = diagnose_main(&mut analysis, &dir);
+        assert_eq!(expected.len(), 2);
+        // the cache is off by default
+        assert!(!dir.join("cache").exists());
+
+        let mut analysis = load_analysis(&dir, true, LIB_CODE);
+        assert_eq!(diagnose_main(&mut analysis, &dir), expected);
+        // one entry for each std and library file
+        let saved_files = cache_files(&dir.join("cache"));
+        let std_file_count = analysis
+            .compilation
+            .get_db()
+            .get_vfs()
+            .get_all_file_ids()
+            .len()
+            - 3;
+        assert_eq!(saved_files.len(), std_file_count + 2);
+
+        let mut analysis = load_analysis(&dir, true, LIB_CODE);
+        assert_eq!(diagnose_main(&mut analysis, &dir), expected);
+        // restored entries are not written again
+        assert_eq!(cache_files(&dir.join("cache")), saved_files);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_index_cache_changed_file() {
+        let dir = test_dir("changed");
+        load_analysis(&dir, true, LIB_CODE);
+        let saved_files = cache_files(&dir.join("cache"));
+
+        // the changed file and the file that uses it are analyzed again
+        let changed_code = LIB_CODE.replace("name string", "name integer");
+        let mut analysis = load_analysis(&dir, true, &changed_code);
+        let files = cache_files(&dir.join("cache"));
+        assert_eq!(files.len(), saved_files.len());
+        assert_eq!(changed_files(&saved_files, &files), 2);
+
+        // `string.len` now gets an integer
+        let diagnostics = diagnose_main(&mut analysis, &dir);
+        assert_eq!(diagnostics.len(), 3);
+
+        let mut analysis = load_analysis(&dir, true, &changed_code);
+        assert_eq!(diagnose_main(&mut analysis, &dir), diagnostics);
+        assert_eq!(cache_files(&dir.join("cache")), files);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_restore_std_index_cache() {
+        let code = r#"
+            local parts = {}
+            for word in string.gmatch("a b", "%S+") do
+                table.insert(parts, word:upper())
+            end
+            local t = setmetatable({}, { __index = string })
+            local n = math.max(1, "2")
+            io.write(#parts, t, n, os.time({}))
+            coroutine.wrap(print)(select(2, parts))
+        "#;
+        let dir = test_dir("std");
+        let mut analysis = load_analysis(&dir, false, LIB_CODE);
+        let expected = diagnose_code(&mut analysis, &dir, code);
+        assert!(!expected.is_empty());
+
+        let mut analysis = load_analysis(&dir, true, LIB_CODE);
+        assert_eq!(diagnose_code(&mut analysis, &dir, code), expected);
+        let mut analysis = load_analysis(&dir, true, LIB_CODE);
+        assert_eq!(diagnose_code(&mut analysis, &dir, code), expected);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
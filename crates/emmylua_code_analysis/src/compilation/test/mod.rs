@@ -6,11 +6,13 @@ mod closure_generic;
 mod closure_param_infer_test;
 mod closure_return_test;
 mod decl_test;
+mod dependency_test;
 mod diagnostic_disable_test;
 mod export_test;
 mod flow;
 mod for_range_var_infer_test;
 mod generic_test;
+mod index_cache_test;
 mod infer_str_tpl_test;
 mod inherit_type;
 mod mathlib_test;
@@ -18,6 +20,7 @@ mod member_infer_test;
 mod metatable_test;
 mod module_annotation;
 mod multi_return;
+mod optional_chaining_test;
 mod out_of_order;
 mod overload_field;
 mod overload_test;
@@ -28,4 +31,3 @@ mod syntax_error_test;
 mod tuple_test;
 mod type_check_test;
 mod unpack_test;
-mod optional_chaining_test;
@@ -8,12 +8,22 @@ pub struct EmmyrcCodeAction {
     #[serde(default = "default_false")]
     #[schemars(extend("x-vscode-setting" = true))]
     pub insert_space: bool,
+    /// The order of the groups that organizing requires sorts the top-level requires into.
+    /// Groups are separated by an empty line, modules of an unlisted group go last.
+    #[serde(default = "default_require_groups")]
+    pub require_groups: Vec<EmmyrcRequireGroup>,
+    /// Offer the `Change signature` code action. Only for a client that asks the user for the
+    /// new parameters and adds them to the `emmy.change.signature` command before running it.
+    #[serde(default = "default_false")]
+    pub change_signature: bool,
 }
 
 impl Default for EmmyrcCodeAction {
     fn default() -> Self {
         Self {
             insert_space: default_false(),
+            require_groups: default_require_groups(),
+            change_signature: default_false(),
         }
     }
 }
@@ -21,3 +31,22 @@ impl Default for EmmyrcCodeAction {
 fn default_false() -> bool {
     false
 }
+
+fn default_require_groups() -> Vec<EmmyrcRequireGroup> {
+    vec![
+        EmmyrcRequireGroup::Std,
+        EmmyrcRequireGroup::Library,
+        EmmyrcRequireGroup::Workspace,
+    ]
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EmmyrcRequireGroup {
+    /// Modules of the standard library.
+    Std,
+    /// Modules of the library workspaces.
+    Library,
+    /// Modules of the main workspace, and the modules that can not be found.
+    Workspace,
+}
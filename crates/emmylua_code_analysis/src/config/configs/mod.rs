@@ -16,7 +16,7 @@ mod signature;
 mod strict;
 mod workspace;
 
-pub use code_action::EmmyrcCodeAction;
+pub use code_action::{EmmyrcCodeAction, EmmyrcRequireGroup};
 pub use codelen::EmmyrcCodeLens;
 pub use completion::{EmmyrcCompletion, EmmyrcFilenameConvention};
 pub use diagnostics::{DiagnosticSeveritySetting, EmmyrcDiagnostic};
@@ -29,14 +29,10 @@ pub struct EmmyrcWorkspace {
     /// }
     #[serde(default)]
     pub module_map: Vec<EmmyrcWorkspaceModuleMap>,
-    /// Delay between changing a file and full project reindex, in milliseconds.
-    #[serde(default = "reindex_duration_default")]
-    #[schemars(extend("x-vscode-setting" = true))]
-    pub reindex_duration: u64,
-    /// Enable full project reindex after changing a file.
-    #[serde(default = "enable_reindex_default")]
-    #[schemars(extend("x-vscode-setting" = true))]
-    pub enable_reindex: bool,
+    /// Cache the analyzed std and library files on disk for each workspace, the unchanged
+    /// files are restored on the next startup instead of analyzed again.
+    #[serde(default = "enable_index_cache_default")]
+    pub enable_index_cache: bool,
 }
 
 impl Default for EmmyrcWorkspace {
@@ -49,8 +45,7 @@ impl Default for EmmyrcWorkspace {
             preload_file_size: 0,
             encoding: encoding_default(),
             module_map: Vec::new(),
-            reindex_duration: 5000,
-            enable_reindex: false,
+            enable_index_cache: enable_index_cache_default(),
         }
     }
 }
@@ -65,10 +60,6 @@ fn encoding_default() -> String {
     "utf-8".to_string()
 }
 
-fn reindex_duration_default() -> u64 {
-    5000
-}
-
-fn enable_reindex_default() -> bool {
+fn enable_index_cache_default() -> bool {
     false
 }
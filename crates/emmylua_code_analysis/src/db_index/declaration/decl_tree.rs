@@ -1,16 +1,18 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::{LuaDeclId, decl, scope};
+use super::{LuaDeclId, LuaLabel, decl, scope};
 use crate::{FileId, db_index::LuaMemberId};
 use decl::LuaDecl;
 use rowan::{TextRange, TextSize};
 use scope::{LuaScope, LuaScopeId, LuaScopeKind, ScopeOrDeclId};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaDeclarationTree {
     file_id: FileId,
     decls: HashMap<LuaDeclId, LuaDecl>,
     scopes: Vec<LuaScope>,
+    labels: Vec<LuaLabel>,
 }
 
 impl LuaDeclarationTree {
@@ -19,6 +21,7 @@ impl LuaDeclarationTree {
             file_id,
             decls: HashMap::new(),
             scopes: Vec::new(),
+            labels: Vec::new(),
         }
     }
 
@@ -267,6 +270,35 @@ impl LuaDeclarationTree {
     pub fn get_decls(&self) -> &HashMap<LuaDeclId, LuaDecl> {
         &self.decls
     }
+
+    pub fn add_label(&mut self, label: LuaLabel) {
+        self.labels.push(label);
+    }
+
+    pub fn get_labels(&self) -> &[LuaLabel] {
+        &self.labels
+    }
+
+    /// Find the label visible at `position`, labels are visible in the entire block
+    /// where they are defined and its nested blocks, except inside nested functions.
+    pub fn find_label(&self, name: &str, position: TextSize) -> Option<&LuaLabel> {
+        let mut scope = self.find_scope(position)?;
+        loop {
+            if let Some(label) = self
+                .labels
+                .iter()
+                .find(|label| label.get_scope_id() == scope.get_id() && label.get_name() == name)
+            {
+                return Some(label);
+            }
+
+            if scope.get_kind() == LuaScopeKind::Closure {
+                return None;
+            }
+
+            scope = self.get_scope(&scope.get_parent()?)?;
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
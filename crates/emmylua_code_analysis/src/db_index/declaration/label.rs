@@ -0,0 +1,40 @@
+use rowan::{TextRange, TextSize};
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+use super::LuaScopeId;
+
+/// A `::name::` label statement, recorded in the block scope that defines it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LuaLabel {
+    name: SmolStr,
+    range: TextRange,
+    scope_id: LuaScopeId,
+}
+
+impl LuaLabel {
+    pub fn new(name: &str, range: TextRange, scope_id: LuaScopeId) -> Self {
+        Self {
+            name: SmolStr::new(name),
+            range,
+            scope_id,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Range of the label name token
+    pub fn get_range(&self) -> TextRange {
+        self.range
+    }
+
+    pub fn get_position(&self) -> TextSize {
+        self.range.start()
+    }
+
+    pub fn get_scope_id(&self) -> LuaScopeId {
+        self.scope_id
+    }
+}
@@ -1,20 +1,23 @@
 mod decl;
 mod decl_id;
 mod decl_tree;
+mod label;
 mod scope;
 
 pub use decl::LuaDeclExtra;
 pub use decl::{LocalAttribute, LuaDecl};
 pub use decl_id::LuaDeclId;
 pub use decl_tree::{LuaDeclOrMemberId, LuaDeclarationTree};
+pub use label::LuaLabel;
 pub use scope::{LuaScope, LuaScopeId, LuaScopeKind, ScopeOrDeclId};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::FileId;
 
-use super::traits::LuaIndex;
+use super::traits::{LuaFileIndex, LuaIndex};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaDeclIndex {
     decl_trees: HashMap<FileId, LuaDeclarationTree>,
 }
@@ -60,3 +63,17 @@ impl LuaIndex for LuaDeclIndex {
         self.decl_trees.clear();
     }
 }
+
+impl LuaFileIndex for LuaDeclIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        if let Some(tree) = self.decl_trees.remove(&file_id) {
+            index.decl_trees.insert(file_id, tree);
+        }
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        self.decl_trees.extend(other.decl_trees);
+    }
+}
@@ -1,16 +1,23 @@
 mod file_dependency_relation;
 
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use file_dependency_relation::FileDependencyRelation;
 
 use crate::FileId;
 
-use super::LuaIndex;
+use super::{LuaFileIndex, LuaIndex};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaDependencyIndex {
     dependencies: HashMap<FileId, HashSet<FileId>>,
+    /// The modules a file requires while it loads, requires inside function bodies are deferred
+    load_requires: HashMap<FileId, HashSet<FileId>>,
+    /// The files using each global or type name, declared or not
+    name_dependents: HashMap<SmolStr, HashSet<FileId>>,
+    file_used_names: HashMap<FileId, HashSet<SmolStr>>,
 }
 
 impl Default for LuaDependencyIndex {
@@ -23,6 +30,9 @@ impl LuaDependencyIndex {
     pub fn new() -> Self {
         Self {
             dependencies: HashMap::new(),
+            load_requires: HashMap::new(),
+            name_dependents: HashMap::new(),
+            file_used_names: HashMap::new(),
         }
     }
 
@@ -33,10 +43,110 @@ impl LuaDependencyIndex {
             .insert(dependency_id);
     }
 
+    pub fn add_load_required_file(&mut self, file_id: FileId, dependency_id: FileId) {
+        self.load_requires
+            .entry(file_id)
+            .or_default()
+            .insert(dependency_id);
+    }
+
+    /// Record a dependency on a global or a type declared in another file
+    pub fn add_declaration_file(&mut self, file_id: FileId, declaration_file_id: FileId) {
+        if file_id != declaration_file_id {
+            self.dependencies
+                .entry(file_id)
+                .or_default()
+                .insert(declaration_file_id);
+        }
+    }
+
+    /// Record that the file uses a global or type name, it may be declared later
+    pub fn add_used_name(&mut self, file_id: FileId, name: SmolStr) {
+        self.name_dependents
+            .entry(name.clone())
+            .or_default()
+            .insert(file_id);
+        self.file_used_names
+            .entry(file_id)
+            .or_default()
+            .insert(name);
+    }
+
+    /// The files using the global or type name
+    pub fn get_name_dependents(&self, name: &str) -> Option<&HashSet<FileId>> {
+        self.name_dependents.get(name)
+    }
+
     pub fn get_required_files(&self, file_id: &FileId) -> Option<&HashSet<FileId>> {
         self.dependencies.get(file_id)
     }
 
+    /// The shortest chain of load time requires from `from` to `to`, both included
+    pub fn find_require_path(&self, from: FileId, to: FileId) -> Option<Vec<FileId>> {
+        let mut parents: HashMap<FileId, FileId> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(file_id) = queue.pop_front() {
+            if file_id == to {
+                let mut path = vec![to];
+                let mut current = to;
+                while current != from {
+                    current = parents[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let Some(required_files) = self.load_requires.get(&file_id) else {
+                continue;
+            };
+            let mut required_files = required_files.iter().copied().collect::<Vec<_>>();
+            required_files.sort();
+            for required_file in required_files {
+                if required_file != from && !parents.contains_key(&required_file) {
+                    parents.insert(required_file, file_id);
+                    queue.push_back(required_file);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Every require cycle of files loading each other, the shortest one through each require,
+    /// starting at its smallest file and without repeating it at the end
+    pub fn get_require_cycles(&self) -> Vec<Vec<FileId>> {
+        let mut file_ids = self.load_requires.keys().copied().collect::<Vec<_>>();
+        file_ids.sort();
+        let mut cycles = Vec::new();
+        let mut seen = HashSet::new();
+        for file_id in file_ids {
+            let mut required_files = self.load_requires[&file_id]
+                .iter()
+                .copied()
+                .collect::<Vec<_>>();
+            required_files.sort();
+            for required_file in required_files {
+                let Some(mut cycle) = self.find_require_path(required_file, file_id) else {
+                    continue;
+                };
+                cycle.pop();
+                cycle.insert(0, file_id);
+                let start = cycle
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, id)| **id)
+                    .map_or(0, |(i, _)| i);
+                cycle.rotate_left(start);
+                if seen.insert(cycle.clone()) {
+                    cycles.push(cycle);
+                }
+            }
+        }
+
+        cycles
+    }
+
     pub fn get_file_dependencies<'a>(&'a self) -> FileDependencyRelation<'a> {
         FileDependencyRelation::new(&self.dependencies)
     }
@@ -45,9 +155,50 @@ impl LuaDependencyIndex {
 impl LuaIndex for LuaDependencyIndex {
     fn remove(&mut self, file_id: FileId) {
         self.dependencies.remove(&file_id);
+        self.load_requires.remove(&file_id);
+        for name in self.file_used_names.remove(&file_id).unwrap_or_default() {
+            if let Some(dependents) = self.name_dependents.get_mut(&name) {
+                dependents.remove(&file_id);
+                if dependents.is_empty() {
+                    self.name_dependents.remove(&name);
+                }
+            }
+        }
     }
 
     fn clear(&mut self) {
         self.dependencies.clear();
+        self.load_requires.clear();
+        self.name_dependents.clear();
+        self.file_used_names.clear();
+    }
+}
+
+impl LuaFileIndex for LuaDependencyIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        if let Some(dependencies) = self.dependencies.remove(&file_id) {
+            index.dependencies.insert(file_id, dependencies);
+        }
+        if let Some(load_requires) = self.load_requires.remove(&file_id) {
+            index.load_requires.insert(file_id, load_requires);
+        }
+        if let Some(names) = self.file_used_names.get(&file_id).cloned() {
+            self.remove(file_id);
+            for name in names {
+                index.add_used_name(file_id, name);
+            }
+        }
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        self.dependencies.extend(other.dependencies);
+        self.load_requires.extend(other.load_requires);
+        for (file_id, names) in other.file_used_names {
+            for name in names {
+                self.add_used_name(file_id, name);
+            }
+        }
     }
 }
@@ -1,8 +1,9 @@
 use rowan::TextRange;
+use serde::{Deserialize, Serialize};
 
 use crate::DiagnosticCode;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DiagnosticAction {
     range: TextRange,
     kind: DiagnosticActionKind,
@@ -50,7 +51,7 @@ impl DiagnosticAction {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum DiagnosticActionKind {
     Disable(DiagnosticCode),
     Enable(DiagnosticCode), // donot use this
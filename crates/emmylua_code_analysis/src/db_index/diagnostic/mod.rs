@@ -1,6 +1,7 @@
 mod analyze_error;
 mod diagnostic_action;
 
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 pub use analyze_error::AnalyzeError;
@@ -9,9 +10,9 @@ use rowan::TextRange;
 
 use crate::{DiagnosticCode, FileId};
 
-use super::traits::LuaIndex;
+use super::traits::{LuaFileIndex, LuaIndex};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DiagnosticIndex {
     diagnostic_actions: HashMap<FileId, Vec<DiagnosticAction>>,
     diagnostics: HashMap<FileId, Vec<AnalyzeError>>,
@@ -119,3 +120,31 @@ impl LuaIndex for DiagnosticIndex {
         self.file_diagnostic_enabled.clear();
     }
 }
+
+impl LuaFileIndex for DiagnosticIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        if let Some(actions) = self.diagnostic_actions.remove(&file_id) {
+            index.diagnostic_actions.insert(file_id, actions);
+        }
+        if let Some(diagnostics) = self.diagnostics.remove(&file_id) {
+            index.diagnostics.insert(file_id, diagnostics);
+        }
+        if let Some(disabled) = self.file_diagnostic_disabled.remove(&file_id) {
+            index.file_diagnostic_disabled.insert(file_id, disabled);
+        }
+        if let Some(enabled) = self.file_diagnostic_enabled.remove(&file_id) {
+            index.file_diagnostic_enabled.insert(file_id, enabled);
+        }
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        self.diagnostic_actions.extend(other.diagnostic_actions);
+        self.diagnostics.extend(other.diagnostics);
+        self.file_diagnostic_disabled
+            .extend(other.file_diagnostic_disabled);
+        self.file_diagnostic_enabled
+            .extend(other.file_diagnostic_enabled);
+    }
+}
@@ -4,14 +4,15 @@ use emmylua_parser::{
 };
 use internment::ArcIntern;
 use rowan::{TextRange, TextSize};
+use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
 /// Unique identifier for flow nodes
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub struct FlowId(pub u32);
 
 /// Represents how flow nodes are connected
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FlowAntecedent {
     /// Single predecessor node
     Single(FlowId),
@@ -20,7 +21,7 @@ pub enum FlowAntecedent {
 }
 
 /// Main flow node structure containing all flow analysis information
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FlowNode {
     pub id: FlowId,
     pub kind: FlowNodeKind,
@@ -28,7 +29,7 @@ pub struct FlowNode {
 }
 
 /// Different types of flow nodes in the control flow graph
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FlowNodeKind {
     /// Entry point of the flow
     Start,
@@ -94,7 +95,7 @@ impl FlowNodeKind {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct LuaClosureId(TextRange);
 
 impl LuaClosureId {
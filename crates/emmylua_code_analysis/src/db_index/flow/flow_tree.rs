@@ -1,16 +1,19 @@
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 use emmylua_parser::{LuaAstPtr, LuaExpr, LuaSyntaxId};
 
-use crate::{FlowId, FlowNode, LuaDeclId};
+use crate::{FlowAntecedent, FlowId, FlowNode, FlowNodeKind, LuaDeclId};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct FlowTree {
     decl_bind_expr_ref: HashMap<LuaDeclId, LuaAstPtr<LuaExpr>>,
     flow_nodes: Vec<FlowNode>,
     multiple_antecedents: Vec<Vec<FlowId>>,
     // labels: HashMap<LuaClosureId, HashMap<SmolStr, FlowId>>,
     bindings: HashMap<LuaSyntaxId, FlowId>,
+    // goto jumps into named labels, only used for reachability
+    label_jumps: HashMap<FlowId, Vec<FlowId>>,
 }
 
 impl FlowTree {
@@ -20,12 +23,14 @@ impl FlowTree {
         multiple_antecedents: Vec<Vec<FlowId>>,
         // labels: HashMap<LuaClosureId, HashMap<SmolStr, FlowId>>,
         bindings: HashMap<LuaSyntaxId, FlowId>,
+        label_jumps: HashMap<FlowId, Vec<FlowId>>,
     ) -> Self {
         Self {
             decl_bind_expr_ref,
             flow_nodes,
             multiple_antecedents,
             bindings,
+            label_jumps,
         }
     }
 
@@ -46,4 +51,42 @@ impl FlowTree {
     pub fn get_decl_ref_expr(&self, decl_id: &LuaDeclId) -> Option<LuaAstPtr<LuaExpr>> {
         self.decl_bind_expr_ref.get(decl_id).cloned()
     }
+
+    /// Check whether the flow node can be reached from the start of the file,
+    /// following antecedents and `goto` jumps into named labels.
+    pub fn is_reachable(&self, flow_id: FlowId) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec![flow_id];
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+
+            let Some(flow_node) = self.get_flow_node(id) else {
+                continue;
+            };
+
+            match &flow_node.kind {
+                FlowNodeKind::Start => return true,
+                FlowNodeKind::Unreachable => continue,
+                _ => {}
+            }
+
+            match &flow_node.antecedent {
+                Some(FlowAntecedent::Single(antecedent)) => stack.push(*antecedent),
+                Some(FlowAntecedent::Multiple(index)) => {
+                    if let Some(antecedents) = self.get_multi_antecedents(*index) {
+                        stack.extend_from_slice(antecedents);
+                    }
+                }
+                None => {}
+            }
+
+            if let Some(jumps) = self.label_jumps.get(&id) {
+                stack.extend_from_slice(jumps);
+            }
+        }
+
+        false
+    }
 }
@@ -2,6 +2,7 @@ mod flow_node;
 mod flow_tree;
 mod signature_cast;
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::{FileId, LuaSignatureId};
@@ -10,9 +11,9 @@ pub use flow_node::*;
 pub use flow_tree::FlowTree;
 pub use signature_cast::LuaSignatureCast;
 
-use super::traits::LuaIndex;
+use super::traits::{LuaFileIndex, LuaIndex};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaFlowIndex {
     file_flow_tree: HashMap<FileId, FlowTree>,
     signature_cast_cache: HashMap<FileId, HashMap<LuaSignatureId, LuaSignatureCast>>,
@@ -79,3 +80,21 @@ impl LuaIndex for LuaFlowIndex {
         self.signature_cast_cache.clear();
     }
 }
+
+impl LuaFileIndex for LuaFlowIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        if let Some(flow_tree) = self.file_flow_tree.remove(&file_id) {
+            index.file_flow_tree.insert(file_id, flow_tree);
+        }
+        if let Some(casts) = self.signature_cast_cache.remove(&file_id) {
+            index.signature_cast_cache.insert(file_id, casts);
+        }
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        self.file_flow_tree.extend(other.file_flow_tree);
+        self.signature_cast_cache.extend(other.signature_cast_cache);
+    }
+}
@@ -1,14 +1,15 @@
 mod global_id;
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 pub use global_id::GlobalId;
 
 use crate::FileId;
 
-use super::{LuaDeclId, LuaIndex};
+use super::{LuaDeclId, LuaFileIndex, LuaIndex};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaGlobalIndex {
     global_decl: HashMap<GlobalId, Vec<LuaDeclId>>,
 }
@@ -49,6 +50,10 @@ impl LuaGlobalIndex {
         let id = GlobalId::new(name);
         self.global_decl.contains_key(&id)
     }
+
+    pub fn get_all_global_names(&self) -> impl Iterator<Item = &str> {
+        self.global_decl.keys().map(|id| id.get_name())
+    }
 }
 
 impl LuaIndex for LuaGlobalIndex {
@@ -63,3 +68,33 @@ impl LuaIndex for LuaGlobalIndex {
         self.global_decl.clear();
     }
 }
+
+impl LuaFileIndex for LuaGlobalIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        self.global_decl.retain(|id, decl_ids| {
+            let file_decl_ids = decl_ids
+                .iter()
+                .filter(|decl_id| decl_id.file_id == file_id)
+                .copied()
+                .collect::<Vec<_>>();
+            if !file_decl_ids.is_empty() {
+                decl_ids.retain(|decl_id| decl_id.file_id != file_id);
+                index.global_decl.insert(id.clone(), file_decl_ids);
+            }
+            !decl_ids.is_empty()
+        });
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        for (id, decl_ids) in other.global_decl {
+            let current = self.global_decl.entry(id).or_default();
+            for decl_id in decl_ids {
+                if !current.contains(&decl_id) {
+                    current.push(decl_id);
+                }
+            }
+        }
+    }
+}
@@ -8,7 +8,7 @@ use smol_str::SmolStr;
 use super::lua_member_feature::LuaMemberFeature;
 use crate::{DbIndex, FileId, GlobalId, InferFailReason, LuaInferCache, LuaType, infer_expr};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaMember {
     member_id: LuaMemberId,
     key: LuaMemberKey,
@@ -90,7 +90,7 @@ impl LuaMemberId {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LuaMemberKey {
     None,
     Integer(i64),
@@ -1,8 +1,9 @@
 use crate::{DbIndex, InferFailReason, LuaSemanticDeclId, LuaType, TypeOps};
+use serde::{Deserialize, Serialize};
 
 use super::LuaMemberId;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LuaMemberIndexItem {
     One(LuaMemberId),
     Many(Vec<LuaMemberId>),
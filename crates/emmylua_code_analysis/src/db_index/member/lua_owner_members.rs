@@ -1,9 +1,10 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::{LuaMemberIndexItem, LuaMemberKey};
 
 #[allow(unused)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LuaOwnerMembers {
     members: HashMap<LuaMemberKey, LuaMemberIndexItem>,
     resolve_state: OwnerMemberStatus,
@@ -46,6 +47,10 @@ impl LuaOwnerMembers {
         self.members.iter_mut()
     }
 
+    pub fn into_members(self) -> impl Iterator<Item = (LuaMemberKey, LuaMemberIndexItem)> {
+        self.members.into_iter()
+    }
+
     pub fn remove_member(&mut self, key: &LuaMemberKey) -> Option<LuaMemberIndexItem> {
         self.members.remove(key)
     }
@@ -67,7 +72,7 @@ impl LuaOwnerMembers {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OwnerMemberStatus {
     UnResolved,
     Resolved,
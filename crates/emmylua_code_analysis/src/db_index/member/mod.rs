@@ -4,16 +4,17 @@ mod lua_member_item;
 mod lua_member_owner;
 mod lua_owner_members;
 
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
-use super::traits::LuaIndex;
+use super::traits::{LuaFileIndex, LuaIndex};
 use crate::{FileId, db_index::member::lua_owner_members::LuaOwnerMembers};
 pub use lua_member::{LuaMember, LuaMemberId, LuaMemberKey};
 pub use lua_member_feature::LuaMemberFeature;
 pub use lua_member_item::LuaMemberIndexItem;
 pub use lua_member_owner::LuaMemberOwner;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaMemberIndex {
     members: HashMap<LuaMemberId, LuaMember>,
     in_filed: HashMap<FileId, HashSet<MemberOrOwner>>,
@@ -21,7 +22,7 @@ pub struct LuaMemberIndex {
     member_current_owner: HashMap<LuaMemberId, LuaMemberOwner>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 enum MemberOrOwner {
     Member(LuaMemberId),
     Owner(LuaMemberOwner),
@@ -228,6 +229,18 @@ impl LuaMemberIndex {
     pub fn get_current_owner(&self, id: &LuaMemberId) -> Option<&LuaMemberOwner> {
         self.member_current_owner.get(id)
     }
+
+    pub fn get_file_members(&self, file_id: FileId) -> Vec<&LuaMember> {
+        self.in_filed
+            .get(&file_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|member_or_owner| match member_or_owner {
+                MemberOrOwner::Member(member_id) => self.members.get(member_id),
+                MemberOrOwner::Owner(_) => None,
+            })
+            .collect()
+    }
 }
 
 impl LuaIndex for LuaMemberIndex {
@@ -288,3 +301,85 @@ impl LuaIndex for LuaMemberIndex {
         self.owner_members.clear();
     }
 }
+
+impl LuaFileIndex for LuaMemberIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        let Some(member_ids) = self.in_filed.get(&file_id).cloned() else {
+            return index;
+        };
+
+        for member_id_or_owner in &member_ids {
+            match member_id_or_owner {
+                MemberOrOwner::Member(member_id) => {
+                    if let Some(member) = self.members.remove(member_id) {
+                        index.members.insert(*member_id, member);
+                    }
+                    if let Some(owner) = self.member_current_owner.remove(member_id) {
+                        index.member_current_owner.insert(*member_id, owner);
+                    }
+                }
+                MemberOrOwner::Owner(owner) => {
+                    let Some(member_items) = self.owner_members.get_mut(owner) else {
+                        continue;
+                    };
+                    let mut file_members = LuaOwnerMembers::new();
+                    for (key, item) in member_items.iter_mut() {
+                        let file_item = match item {
+                            LuaMemberIndexItem::One(id) if id.file_id == file_id => {
+                                LuaMemberIndexItem::One(*id)
+                            }
+                            LuaMemberIndexItem::Many(ids) => {
+                                let ids = ids
+                                    .iter()
+                                    .filter(|id| id.file_id == file_id)
+                                    .copied()
+                                    .collect::<Vec<_>>();
+                                if ids.is_empty() {
+                                    continue;
+                                }
+                                LuaMemberIndexItem::Many(ids)
+                            }
+                            _ => continue,
+                        };
+                        file_members.add_member(key.clone(), file_item);
+                    }
+                    index.owner_members.insert(owner.clone(), file_members);
+                }
+            }
+        }
+        index.in_filed.insert(file_id, member_ids);
+
+        self.remove(file_id);
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        self.members.extend(other.members);
+        self.member_current_owner.extend(other.member_current_owner);
+        for (file_id, member_ids) in other.in_filed {
+            self.in_filed.entry(file_id).or_default().extend(member_ids);
+        }
+        for (owner, file_members) in other.owner_members {
+            let member_items = self
+                .owner_members
+                .entry(owner)
+                .or_insert_with(LuaOwnerMembers::new);
+            for (key, file_item) in file_members.into_members() {
+                let Some(item) = member_items.get_member_mut(&key) else {
+                    member_items.add_member(key, file_item);
+                    continue;
+                };
+                let mut ids = item.get_member_ids();
+                for id in file_item.get_member_ids() {
+                    if !ids.contains(&id) {
+                        ids.push(id);
+                    }
+                }
+                if ids.len() > 1 {
+                    *item = LuaMemberIndexItem::Many(ids);
+                }
+            }
+        }
+    }
+}
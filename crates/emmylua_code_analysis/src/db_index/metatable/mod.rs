@@ -1,12 +1,13 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use rowan::TextRange;
 
 use crate::{FileId, InFiled};
 
-use super::LuaIndex;
+use super::{LuaFileIndex, LuaIndex};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaMetatableIndex {
     pub metatables: HashMap<InFiled<TextRange>, InFiled<TextRange>>,
 }
@@ -42,3 +43,25 @@ impl LuaIndex for LuaMetatableIndex {
         self.metatables.clear();
     }
 }
+
+impl LuaFileIndex for LuaMetatableIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        let tables = self
+            .metatables
+            .keys()
+            .filter(|table| table.file_id == file_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        for table in tables {
+            if let Some(metatable) = self.metatables.remove(&table) {
+                index.metatables.insert(table, metatable);
+            }
+        }
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        self.metatables.extend(other.metatables);
+    }
+}
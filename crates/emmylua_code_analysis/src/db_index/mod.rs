@@ -16,6 +16,8 @@ mod r#type;
 
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{Emmyrc, FileId, Vfs};
 pub use declaration::*;
 pub use dependency::LuaDependencyIndex;
@@ -30,10 +32,10 @@ pub use property::*;
 pub use reference::*;
 pub use semantic_decl::*;
 pub use signature::*;
-pub use traits::LuaIndex;
+pub use traits::{LuaFileIndex, LuaIndex};
 pub use r#type::*;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DbIndex {
     decl_index: LuaDeclIndex,
     references_index: LuaReferenceIndex,
@@ -45,10 +47,12 @@ pub struct DbIndex {
     diagnostic_index: DiagnosticIndex,
     operator_index: LuaOperatorIndex,
     flow_index: LuaFlowIndex,
+    #[serde(skip)]
     vfs: Vfs,
     file_dependencies_index: LuaDependencyIndex,
     metatable_index: LuaMetatableIndex,
     global_index: LuaGlobalIndex,
+    #[serde(skip)]
     emmyrc: Arc<Emmyrc>,
 }
 
@@ -242,3 +246,41 @@ impl LuaIndex for DbIndex {
         self.global_index.clear();
     }
 }
+
+impl LuaFileIndex for DbIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        index.decl_index = self.decl_index.take_file(file_id);
+        index.references_index = self.references_index.take_file(file_id);
+        index.types_index = self.types_index.take_file(file_id);
+        index.modules_index = self.modules_index.take_file(file_id);
+        index.members_index = self.members_index.take_file(file_id);
+        index.property_index = self.property_index.take_file(file_id);
+        index.signature_index = self.signature_index.take_file(file_id);
+        index.diagnostic_index = self.diagnostic_index.take_file(file_id);
+        index.operator_index = self.operator_index.take_file(file_id);
+        index.flow_index = self.flow_index.take_file(file_id);
+        index.file_dependencies_index = self.file_dependencies_index.take_file(file_id);
+        index.metatable_index = self.metatable_index.take_file(file_id);
+        index.global_index = self.global_index.take_file(file_id);
+        index
+    }
+
+    /// The vfs, the config and the module workspaces of the other index are not merged
+    fn merge_file(&mut self, other: Self) {
+        self.decl_index.merge_file(other.decl_index);
+        self.references_index.merge_file(other.references_index);
+        self.types_index.merge_file(other.types_index);
+        self.modules_index.merge_file(other.modules_index);
+        self.members_index.merge_file(other.members_index);
+        self.property_index.merge_file(other.property_index);
+        self.signature_index.merge_file(other.signature_index);
+        self.diagnostic_index.merge_file(other.diagnostic_index);
+        self.operator_index.merge_file(other.operator_index);
+        self.flow_index.merge_file(other.flow_index);
+        self.file_dependencies_index
+            .merge_file(other.file_dependencies_index);
+        self.metatable_index.merge_file(other.metatable_index);
+        self.global_index.merge_file(other.global_index);
+    }
+}
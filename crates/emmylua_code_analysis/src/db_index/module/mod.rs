@@ -8,9 +8,10 @@ use log::{error, info};
 pub use module_info::ModuleInfo;
 pub use module_node::{ModuleNode, ModuleNodeId};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 pub use workspace::{Workspace, WorkspaceId};
 
-use super::traits::LuaIndex;
+use super::traits::{LuaFileIndex, LuaIndex};
 use crate::{Emmyrc, FileId};
 use std::{
     collections::HashMap,
@@ -18,16 +19,20 @@ use std::{
     sync::Arc,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaModuleIndex {
+    #[serde(skip)]
     module_patterns: Vec<Regex>,
     module_root_id: ModuleNodeId,
     module_nodes: HashMap<ModuleNodeId, ModuleNode>,
     file_module_map: HashMap<FileId, ModuleInfo>,
     module_name_to_file_ids: HashMap<String, Vec<FileId>>,
+    #[serde(skip)]
     workspaces: Vec<Workspace>,
     id_counter: u32,
+    #[serde(skip)]
     fuzzy_search: bool,
+    #[serde(skip)]
     module_replace_vec: Vec<(Regex, String)>,
 }
 
@@ -365,6 +370,10 @@ impl LuaModuleIndex {
         }
     }
 
+    pub fn get_workspaces(&self) -> &[Workspace] {
+        &self.workspaces
+    }
+
     #[allow(unused)]
     pub fn remove_workspace_root(&mut self, root: &Path) {
         self.workspaces.retain(|r| r.root != root);
@@ -487,6 +496,7 @@ impl LuaModuleIndex {
         false
     }
 
+    /// Take the modules of a restored index, the patterns and workspaces come from the config
     pub fn get_workspace_id(&self, file_id: FileId) -> Option<WorkspaceId> {
         if let Some(module_info) = self.file_module_map.get(&file_id) {
             return Some(module_info.workspace_id);
@@ -576,3 +586,31 @@ impl LuaIndex for LuaModuleIndex {
         self.module_nodes.insert(self.module_root_id, root_node);
     }
 }
+
+impl LuaFileIndex for LuaModuleIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        if let Some(module_info) = self.file_module_map.get(&file_id) {
+            index.file_module_map.insert(file_id, module_info.clone());
+            self.remove(file_id);
+        }
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        for (file_id, module_info) in other.file_module_map {
+            self.add_module_by_module_path(
+                file_id,
+                module_info.full_module_name.clone(),
+                module_info.workspace_id,
+            );
+            if let Some(current) = self.file_module_map.get_mut(&file_id) {
+                current.visible = module_info.visible;
+                current.export_type = module_info.export_type;
+                current.version_conds = module_info.version_conds;
+                current.semantic_id = module_info.semantic_id;
+                current.is_meta = module_info.is_meta;
+            }
+        }
+    }
+}
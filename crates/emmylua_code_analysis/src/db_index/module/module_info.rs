@@ -1,10 +1,11 @@
 use emmylua_parser::{LuaVersionCondition, LuaVersionNumber};
+use serde::{Deserialize, Serialize};
 
 use crate::{DbIndex, FileId, LuaExport, LuaSemanticDeclId, db_index::LuaType};
 
 use super::{module_node::ModuleNodeId, workspace::WorkspaceId};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleInfo {
     pub file_id: FileId,
     pub full_module_name: String,
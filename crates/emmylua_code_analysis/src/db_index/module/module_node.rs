@@ -1,15 +1,16 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::FileId;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ModuleNode {
     pub parent: Option<ModuleNodeId>,
     pub children: HashMap<String, ModuleNodeId>,
     pub file_ids: Vec<FileId>,
 }
 
-#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub struct ModuleNodeId {
     pub id: u32,
 }
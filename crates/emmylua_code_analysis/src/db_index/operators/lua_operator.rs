@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use rowan::{TextRange, TextSize};
@@ -10,7 +11,7 @@ use crate::{
 
 use super::lua_operator_meta_method::LuaOperatorMetaMethod;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LuaOperator {
     owner: LuaOperatorOwner,
     op: LuaOperatorMetaMethod,
@@ -19,7 +20,7 @@ pub struct LuaOperator {
     func: OperatorFunction,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OperatorFunction {
     Func(Arc<LuaFunctionType>),
     Signature(LuaSignatureId),
@@ -170,7 +171,7 @@ impl LuaOperator {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct LuaOperatorId {
     pub file_id: FileId,
     pub position: TextSize,
@@ -182,7 +183,7 @@ impl LuaOperatorId {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LuaOperatorOwner {
     Table(InFiled<TextRange>),
     Type(LuaTypeDeclId),
@@ -1,15 +1,16 @@
 mod lua_operator;
 mod lua_operator_meta_method;
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::FileId;
 
-use super::traits::LuaIndex;
+use super::traits::{LuaFileIndex, LuaIndex};
 pub use lua_operator::{LuaOperator, LuaOperatorId, LuaOperatorOwner, OperatorFunction};
 pub use lua_operator_meta_method::LuaOperatorMetaMethod;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaOperatorIndex {
     operators: HashMap<LuaOperatorId, LuaOperator>,
     type_operators_map:
@@ -98,3 +99,30 @@ impl LuaIndex for LuaOperatorIndex {
         self.in_filed_operator_map.clear();
     }
 }
+
+impl LuaFileIndex for LuaOperatorIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        if let Some(operator_ids) = self.in_filed_operator_map.get(&file_id) {
+            for id in operator_ids {
+                if let Some(operator) = self.operators.get(id) {
+                    index.add_operator(operator.clone());
+                }
+            }
+        }
+        self.remove(file_id);
+        index
+    }
+
+    fn merge_file(&mut self, mut other: Self) {
+        for (_, operator_ids) in other.in_filed_operator_map {
+            for id in operator_ids {
+                if let Some(operator) = other.operators.remove(&id)
+                    && !self.operators.contains_key(&id)
+                {
+                    self.add_operator(operator);
+                }
+            }
+        }
+    }
+}
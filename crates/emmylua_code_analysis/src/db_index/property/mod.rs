@@ -2,6 +2,7 @@ mod decl_feature;
 #[allow(clippy::module_inception)]
 mod property;
 
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 pub use decl_feature::{DeclFeatureFlag, PropertyDeclFeature};
@@ -12,9 +13,12 @@ pub use property::{LuaDeprecated, LuaExport, LuaExportScope, LuaPropertyId};
 pub use crate::db_index::property::property::LuaAttributeUse;
 use crate::{DbIndex, FileId, LuaMember, LuaSignatureId};
 
-use super::{LuaSemanticDeclId, traits::LuaIndex};
+use super::{
+    LuaSemanticDeclId,
+    traits::{LuaFileIndex, LuaIndex},
+};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaPropertyIndex {
     properties: HashMap<LuaPropertyId, LuaCommonProperty>,
     property_owners_map: HashMap<LuaSemanticDeclId, LuaPropertyId>,
@@ -277,6 +281,67 @@ impl LuaIndex for LuaPropertyIndex {
     }
 }
 
+impl LuaFileIndex for LuaPropertyIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        let Some(owner_ids) = self.in_filed_owner.remove(&file_id) else {
+            return index;
+        };
+
+        let mut property_ids = HashSet::new();
+        for owner_id in &owner_ids {
+            if let Some(property_id) = self.property_owners_map.remove(owner_id)
+                && let Some(property) = self.properties.remove(&property_id)
+            {
+                index
+                    .property_owners_map
+                    .insert(owner_id.clone(), property_id);
+                index.properties.insert(property_id, property);
+                property_ids.insert(property_id);
+            }
+        }
+        // the owners sharing a property with one of the file, they are kept like on remove
+        for (owner_id, property_id) in &self.property_owners_map {
+            if property_ids.contains(property_id) {
+                index
+                    .property_owners_map
+                    .insert(owner_id.clone(), *property_id);
+            }
+        }
+        index.in_filed_owner.insert(file_id, owner_ids);
+        index
+    }
+
+    fn merge_file(&mut self, mut other: Self) {
+        // property ids are given by each index, the merged ones get new ids
+        let mut new_ids = HashMap::new();
+        for (file_id, owner_ids) in other.in_filed_owner {
+            for owner_id in &owner_ids {
+                let Some(property_id) = other.property_owners_map.get(owner_id) else {
+                    continue;
+                };
+                let Some(property) = other.properties.remove(property_id) else {
+                    continue;
+                };
+                let Some((current, new_id)) = self.get_or_create_property(owner_id.clone()) else {
+                    continue;
+                };
+                *current = property;
+                new_ids.insert(*property_id, new_id);
+            }
+            self.in_filed_owner
+                .entry(file_id)
+                .or_default()
+                .extend(owner_ids);
+        }
+        for (owner_id, property_id) in other.property_owners_map {
+            if let Some(new_id) = new_ids.get(&property_id) {
+                self.property_owners_map.insert(owner_id, *new_id);
+            }
+        }
+    }
+}
+
 /// 尝试从 @field 定义中提取函数类型的位置信息
 pub fn try_extract_signature_id_from_field(
     db: &DbIndex,
@@ -1,6 +1,7 @@
 mod file_reference;
 mod string_reference;
 
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 use emmylua_parser::LuaSyntaxId;
@@ -9,14 +10,19 @@ use rowan::TextRange;
 use smol_str::SmolStr;
 use string_reference::StringReference;
 
-use super::{LuaDeclId, LuaMemberKey, LuaTypeDeclId, traits::LuaIndex};
+use super::{
+    LuaDeclId, LuaMemberKey, LuaTypeDeclId,
+    traits::{LuaFileIndex, LuaIndex},
+};
 use crate::{FileId, InFiled};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaReferenceIndex {
     file_references: HashMap<FileId, FileReference>,
     index_reference: HashMap<LuaMemberKey, HashMap<FileId, HashSet<LuaSyntaxId>>>,
     global_references: HashMap<SmolStr, HashMap<FileId, HashSet<LuaSyntaxId>>>,
+    /// The global names each file references
+    file_global_names: HashMap<FileId, HashSet<SmolStr>>,
     string_references: HashMap<FileId, StringReference>,
     type_references: HashMap<FileId, HashMap<LuaTypeDeclId, HashSet<TextRange>>>,
 }
@@ -33,6 +39,7 @@ impl LuaReferenceIndex {
             file_references: HashMap::new(),
             index_reference: HashMap::new(),
             global_references: HashMap::new(),
+            file_global_names: HashMap::new(),
             string_references: HashMap::new(),
             type_references: HashMap::new(),
         }
@@ -53,6 +60,10 @@ impl LuaReferenceIndex {
 
     pub fn add_global_reference(&mut self, name: &str, file_id: FileId, syntax_id: LuaSyntaxId) {
         let key = SmolStr::new(name);
+        self.file_global_names
+            .entry(file_id)
+            .or_default()
+            .insert(key.clone());
         self.global_references
             .entry(key)
             .or_default()
@@ -165,6 +176,11 @@ impl LuaReferenceIndex {
         Some(results)
     }
 
+    /// The global names the file references
+    pub fn get_file_global_names(&self, file_id: &FileId) -> Option<&HashSet<SmolStr>> {
+        self.file_global_names.get(file_id)
+    }
+
     pub fn get_index_references(&self, key: &LuaMemberKey) -> Option<Vec<InFiled<LuaSyntaxId>>> {
         let results = self
             .index_reference
@@ -192,6 +208,13 @@ impl LuaReferenceIndex {
             .collect()
     }
 
+    pub fn get_file_type_references(&self, file_id: &FileId) -> Vec<LuaTypeDeclId> {
+        self.type_references
+            .get(file_id)
+            .map(|type_references| type_references.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
     pub fn get_type_references(
         &self,
         type_decl_id: &LuaTypeDeclId,
@@ -229,17 +252,14 @@ impl LuaIndex for LuaReferenceIndex {
             self.index_reference.remove(&key);
         }
 
-        let mut to_be_remove = Vec::new();
-        for (key, references) in self.global_references.iter_mut() {
-            references.remove(&file_id);
-            if references.is_empty() {
-                to_be_remove.push(key.clone());
+        for name in self.file_global_names.remove(&file_id).unwrap_or_default() {
+            if let Some(references) = self.global_references.get_mut(&name) {
+                references.remove(&file_id);
+                if references.is_empty() {
+                    self.global_references.remove(&name);
+                }
             }
         }
-
-        for key in to_be_remove {
-            self.global_references.remove(&key);
-        }
     }
 
     fn clear(&mut self) {
@@ -247,5 +267,72 @@ impl LuaIndex for LuaReferenceIndex {
         self.string_references.clear();
         self.index_reference.clear();
         self.global_references.clear();
+        self.file_global_names.clear();
+    }
+}
+
+impl LuaFileIndex for LuaReferenceIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        if let Some(file_reference) = self.file_references.remove(&file_id) {
+            index.file_references.insert(file_id, file_reference);
+        }
+        if let Some(string_reference) = self.string_references.remove(&file_id) {
+            index.string_references.insert(file_id, string_reference);
+        }
+        if let Some(type_references) = self.type_references.remove(&file_id) {
+            index.type_references.insert(file_id, type_references);
+        }
+        self.index_reference.retain(|key, references| {
+            if let Some(syntax_ids) = references.remove(&file_id) {
+                index
+                    .index_reference
+                    .entry(key.clone())
+                    .or_default()
+                    .insert(file_id, syntax_ids);
+            }
+            !references.is_empty()
+        });
+        let names = self.file_global_names.remove(&file_id).unwrap_or_default();
+        for name in &names {
+            if let Some(references) = self.global_references.get_mut(name) {
+                if let Some(syntax_ids) = references.remove(&file_id) {
+                    index
+                        .global_references
+                        .entry(name.clone())
+                        .or_default()
+                        .insert(file_id, syntax_ids);
+                }
+                if references.is_empty() {
+                    self.global_references.remove(name);
+                }
+            }
+        }
+        index.file_global_names.insert(file_id, names);
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        self.file_references.extend(other.file_references);
+        self.string_references.extend(other.string_references);
+        self.type_references.extend(other.type_references);
+        for (key, references) in other.index_reference {
+            self.index_reference
+                .entry(key)
+                .or_default()
+                .extend(references);
+        }
+        for (name, references) in other.global_references {
+            self.global_references
+                .entry(name)
+                .or_default()
+                .extend(references);
+        }
+        for (file_id, names) in other.file_global_names {
+            self.file_global_names
+                .entry(file_id)
+                .or_default()
+                .extend(names);
+        }
     }
 }
@@ -1,8 +1,9 @@
 use rowan::TextRange;
+use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 use std::collections::HashMap;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StringReference {
     string_references: HashMap<SmolStr, Vec<TextRange>>,
 }
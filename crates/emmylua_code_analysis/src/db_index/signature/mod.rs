@@ -2,6 +2,7 @@ mod async_state;
 #[allow(clippy::module_inception)]
 mod signature;
 
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 pub use async_state::AsyncState;
@@ -12,9 +13,9 @@ pub use signature::{
 
 use crate::FileId;
 
-use super::traits::LuaIndex;
+use super::traits::{LuaFileIndex, LuaIndex};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaSignatureIndex {
     signatures: HashMap<LuaSignatureId, LuaSignature>,
     in_file_signatures: HashMap<FileId, HashSet<LuaSignatureId>>,
@@ -65,3 +66,28 @@ impl LuaIndex for LuaSignatureIndex {
         self.in_file_signatures.clear();
     }
 }
+
+impl LuaFileIndex for LuaSignatureIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        if let Some(signature_ids) = self.in_file_signatures.remove(&file_id) {
+            for signature_id in &signature_ids {
+                if let Some(signature) = self.signatures.remove(signature_id) {
+                    index.signatures.insert(*signature_id, signature);
+                }
+            }
+            index.in_file_signatures.insert(file_id, signature_ids);
+        }
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        self.signatures.extend(other.signatures);
+        for (file_id, signature_ids) in other.in_file_signatures {
+            self.in_file_signatures
+                .entry(file_id)
+                .or_default()
+                .extend(signature_ids);
+        }
+    }
+}
@@ -5,3 +5,12 @@ pub trait LuaIndex {
 
     fn clear(&mut self);
 }
+
+/// Moving the data of a single file out of an index and back, the index cache stores it per file
+pub trait LuaFileIndex: Sized {
+    /// Remove the data of the file and return it as a new index
+    fn take_file(&mut self, file_id: FileId) -> Self;
+
+    /// Add the data taken from a file, data shared with other files is added only once
+    fn merge_file(&mut self, other: Self);
+}
@@ -67,6 +67,7 @@ pub fn humanize_type(db: &DbIndex, ty: &LuaType, level: RenderLevel) -> String {
         LuaType::Union(union) => humanize_union_type(db, union, level),
         LuaType::Tuple(tuple) => humanize_tuple_type(db, tuple, level),
         LuaType::Unknown => "unknown".to_string(),
+        LuaType::Never => "never".to_string(),
         LuaType::Integer => "integer".to_string(),
         LuaType::Io => "io".to_string(),
         LuaType::SelfInfer => "self".to_string(),
@@ -354,6 +355,7 @@ fn humanize_call_type(db: &DbIndex, inner: &LuaAliasCallType, level: RenderLevel
         LuaAliasCallKind::Unpack => "unpack",
         LuaAliasCallKind::Index => "index",
         LuaAliasCallKind::RawGet => "rawget",
+        LuaAliasCallKind::Captures => "captures",
     };
     let operands = inner
         .get_operands()
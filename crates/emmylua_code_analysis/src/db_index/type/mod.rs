@@ -7,10 +7,11 @@ mod type_owner;
 mod type_visit_trait;
 mod types;
 
-use super::traits::LuaIndex;
+use super::traits::{LuaFileIndex, LuaIndex};
 use crate::{DbIndex, FileId, InFiled};
 pub use generic_param::GenericParam;
 pub use humanize_type::{RenderLevel, format_union_type, humanize_type};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 pub use type_decl::{LuaDeclLocation, LuaDeclTypeKind, LuaTypeDecl, LuaTypeDeclId, LuaTypeFlag};
 pub use type_ops::TypeOps;
@@ -18,7 +19,7 @@ pub use type_owner::{LuaTypeCache, LuaTypeOwner};
 pub use type_visit_trait::TypeVisitTrait;
 pub use types::*;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LuaTypeIndex {
     file_namespace: HashMap<FileId, String>,
     file_using_namespace: HashMap<FileId, Vec<String>>,
@@ -80,6 +81,10 @@ impl LuaTypeIndex {
         }
     }
 
+    pub fn get_file_types(&self, file_id: &FileId) -> Option<&Vec<LuaTypeDeclId>> {
+        self.file_types.get(file_id)
+    }
+
     pub fn find_type_decl(&self, file_id: FileId, name: &str) -> Option<&LuaTypeDecl> {
         if let Some(ns) = self.get_file_namespace(&file_id) {
             let full_name = LuaTypeDeclId::new(&format!("{}.{}", ns, name));
@@ -188,6 +193,24 @@ impl LuaTypeIndex {
             .map(|supers| supers.iter().map(|s| &s.value))
     }
 
+    /// The types that list the given type as a direct super type, generic supers included
+    pub fn get_sub_types(&self, decl_id: &LuaTypeDeclId) -> Vec<LuaTypeDeclId> {
+        let mut sub_types = self
+            .supers
+            .iter()
+            .filter(|(_, supers)| {
+                supers.iter().any(|super_type| match &super_type.value {
+                    LuaType::Ref(id) => id == decl_id,
+                    LuaType::Generic(generic) => generic.get_base_type_id_ref() == decl_id,
+                    _ => false,
+                })
+            })
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        sub_types.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+        sub_types
+    }
+
     pub fn get_type_decl(&self, decl_id: &LuaTypeDeclId) -> Option<&LuaTypeDecl> {
         self.full_name_type_map.get(decl_id)
     }
@@ -273,6 +296,72 @@ impl LuaIndex for LuaTypeIndex {
     }
 }
 
+impl LuaFileIndex for LuaTypeIndex {
+    fn take_file(&mut self, file_id: FileId) -> Self {
+        let mut index = Self::new();
+        if let Some(namespace) = self.file_namespace.get(&file_id) {
+            index.file_namespace.insert(file_id, namespace.clone());
+        }
+        if let Some(usings) = self.file_using_namespace.get(&file_id) {
+            index.file_using_namespace.insert(file_id, usings.clone());
+        }
+        // a type may be declared in several files, the file keeps its own locations
+        let type_ids = self
+            .file_types
+            .get(&file_id)
+            .into_iter()
+            .flatten()
+            .collect::<HashSet<_>>();
+        for id in type_ids {
+            if let Some(decl) = self.full_name_type_map.get(id) {
+                let mut decl = decl.clone();
+                decl.get_mut_locations()
+                    .retain(|location| location.file_id == file_id);
+                index.add_type_decl(file_id, decl);
+            }
+            if let Some(params) = self.generic_params.get(id) {
+                index.generic_params.insert(id.clone(), params.clone());
+            }
+            for super_type in self.supers.get(id).into_iter().flatten() {
+                if super_type.file_id == file_id {
+                    index.add_super_type(id.clone(), file_id, super_type.value.clone());
+                }
+            }
+        }
+        for owner in self.in_filed_type_owner.get(&file_id).into_iter().flatten() {
+            if let Some(cache) = self.types.get(owner) {
+                index.bind_type(owner.clone(), cache.clone());
+            }
+        }
+
+        self.remove(file_id);
+        index
+    }
+
+    fn merge_file(&mut self, other: Self) {
+        self.file_namespace.extend(other.file_namespace);
+        self.file_using_namespace.extend(other.file_using_namespace);
+        for (file_id, type_ids) in other.file_types {
+            for id in type_ids {
+                if let Some(decl) = other.full_name_type_map.get(&id) {
+                    self.add_type_decl(file_id, decl.clone());
+                }
+            }
+        }
+        for (id, params) in other.generic_params {
+            self.generic_params.entry(id).or_insert(params);
+        }
+        for (id, supers) in other.supers {
+            for super_type in supers {
+                self.add_super_type(id.clone(), super_type.file_id, super_type.value);
+            }
+        }
+        for (owner, cache) in other.types {
+            self.bind_type(owner, cache);
+        }
+    }
+}
+
 pub fn get_real_type<'a>(db: &'a DbIndex, typ: &'a LuaType) -> Option<&'a LuaType> {
     get_real_type_with_depth(db, typ, 0)
 }
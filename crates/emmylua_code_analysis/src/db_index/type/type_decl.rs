@@ -10,7 +10,7 @@ use crate::{
 
 use super::{LuaType, LuaUnionType};
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum LuaDeclTypeKind {
     Class,
     Enum,
@@ -19,6 +19,7 @@ pub enum LuaDeclTypeKind {
 }
 
 flags! {
+    #[derive(Serialize, Deserialize)]
     pub enum LuaTypeFlag: u8 {
         None,
         Key,
@@ -29,7 +30,7 @@ flags! {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub struct LuaTypeDecl {
     simple_name: String,
     locations: Vec<LuaDeclLocation>,
@@ -317,14 +318,14 @@ impl<'de> Deserialize<'de> for LuaTypeDeclId {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaDeclLocation {
     pub file_id: FileId,
     pub range: TextRange,
     pub flag: FlagSet<LuaTypeFlag>,
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LuaTypeExtra {
     Enum { base: Option<LuaType> },
     Class,
@@ -11,6 +11,27 @@ pub fn remove_type(db: &DbIndex, source: LuaType, removed_type: LuaType) -> Opti
 
     let real_type = get_real_type(db, &source).unwrap_or(&source);
 
+    // 与枚举的一个值比较后, 剩下的是枚举的其他值
+    if let LuaType::Ref(type_decl_id) | LuaType::Def(type_decl_id) = real_type
+        && matches!(
+            removed_type,
+            LuaType::StringConst(_)
+                | LuaType::DocStringConst(_)
+                | LuaType::IntegerConst(_)
+                | LuaType::DocIntegerConst(_)
+        )
+        && let Some(field_type) = db
+            .get_type_index()
+            .get_type_decl(type_decl_id)
+            .and_then(|type_decl| type_decl.get_enum_field_type(db))
+    {
+        let remaining = remove_type(db, field_type.clone(), removed_type);
+        if remaining.as_ref() != Some(&field_type) {
+            return remaining;
+        }
+        return Some(source.clone());
+    }
+
     match &removed_type {
         LuaType::Nil => {
             if real_type.is_nil() {
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
@@ -17,7 +18,7 @@ use crate::{
 
 use super::{TypeOps, type_decl::LuaTypeDeclId};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LuaType {
     Unknown,
     Any,
@@ -505,7 +506,7 @@ impl TypeVisitTrait for LuaType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaTupleType {
     types: Vec<LuaType>,
     pub status: LuaTupleStatus,
@@ -522,7 +523,7 @@ impl TypeVisitTrait for LuaTupleType {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LuaTupleStatus {
     DocResolve,
     InferResolve,
@@ -600,7 +601,7 @@ impl From<LuaTupleType> for LuaType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaFunctionType {
     async_state: AsyncState,
     is_colon_define: bool,
@@ -722,7 +723,7 @@ pub enum LuaIndexAccessKey {
     Type(LuaType),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaObjectType {
     fields: HashMap<LuaMemberKey, LuaType>,
     index_access: Vec<(LuaType, LuaType)>,
@@ -845,7 +846,7 @@ impl From<LuaObjectType> for LuaType {
         LuaType::Object(t.into())
     }
 }
-#[derive(Debug, Clone, Eq)]
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub enum LuaUnionType {
     Nullable(LuaType),
     Multi(Vec<LuaType>),
@@ -977,7 +978,7 @@ impl PartialEq for LuaUnionType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaIntersectionType {
     types: Vec<LuaType>,
 }
@@ -1018,7 +1019,7 @@ impl From<LuaIntersectionType> for LuaType {
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LuaAliasCallKind {
     KeyOf,
     Index,
@@ -1028,9 +1029,10 @@ pub enum LuaAliasCallKind {
     Select,
     Unpack,
     RawGet,
+    Captures,
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaAliasCallType {
     call_kind: LuaAliasCallKind,
     operand: Vec<LuaType>,
@@ -1065,7 +1067,7 @@ impl LuaAliasCallType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaGenericType {
     base: LuaTypeDeclId,
     params: Vec<LuaType>,
@@ -1114,7 +1116,7 @@ impl From<LuaGenericType> for LuaType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VariadicType {
     Multi(Vec<LuaType>),
     Base(LuaType),
@@ -1259,7 +1261,7 @@ impl From<SmolStr> for LuaType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaInstanceType {
     base: LuaType,
     range: InFiled<TextRange>,
@@ -1288,7 +1290,7 @@ impl LuaInstanceType {
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GenericTplId {
     Type(u32),
     Func(u32),
@@ -1318,7 +1320,7 @@ impl GenericTplId {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GenericTpl {
     tpl_id: GenericTplId,
     name: ArcIntern<SmolStr>,
@@ -1347,7 +1349,7 @@ impl GenericTpl {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaStringTplType {
     prefix: ArcIntern<String>,
     tpl_id: GenericTplId,
@@ -1382,7 +1384,7 @@ impl LuaStringTplType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaMultiLineUnion {
     unions: Vec<(LuaType, Option<String>)>,
 }
@@ -1421,7 +1423,7 @@ impl LuaMultiLineUnion {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaArrayType {
     base: LuaType,
     len: LuaArrayLen,
@@ -1436,7 +1438,7 @@ impl TypeVisitTrait for LuaArrayType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LuaArrayLen {
     None,
     Max(i64),
@@ -1467,7 +1469,7 @@ impl LuaArrayType {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LuaAttributeType {
     params: Vec<(String, Option<LuaType>)>,
 }
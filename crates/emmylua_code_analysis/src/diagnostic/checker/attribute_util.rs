@@ -0,0 +1,109 @@
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaCallExpr, LuaExpr, LuaLiteralToken, LuaStringToken,
+};
+use rowan::{TextRange, TextSize};
+
+use crate::{
+    LuaAttributeUse, LuaSemanticDeclId, LuaType, LuaTypeDeclId, SemanticDeclLevel,
+    semantic::SemanticModel,
+};
+
+/// The attribute of the called function, with the call arguments aligned to the declared
+/// parameters: the receiver of a colon call is the first one, and `self` of a colon definition
+/// is not counted
+pub fn get_call_attribute(
+    semantic_model: &SemanticModel,
+    call_expr: &LuaCallExpr,
+    name: &str,
+) -> Option<(LuaAttributeUse, Vec<LuaExpr>)> {
+    let prefix_expr = call_expr.get_prefix_expr()?;
+    let mut semantic_ids = Vec::new();
+    let mut colon_define = false;
+    if let Ok(LuaType::Signature(signature_id)) = semantic_model.infer_expr(prefix_expr.clone()) {
+        if let Some(signature) = semantic_model
+            .get_db()
+            .get_signature_index()
+            .get(&signature_id)
+        {
+            colon_define = signature.is_colon_define;
+        }
+        semantic_ids.push(LuaSemanticDeclId::Signature(signature_id));
+    }
+    // `local f = function(fmt, ...) end` 的特性记录在变量上
+    if let Some(semantic_id) = semantic_model.find_decl(
+        prefix_expr.syntax().clone().into(),
+        SemanticDeclLevel::default(),
+    ) {
+        semantic_ids.push(semantic_id);
+    }
+    let property_index = semantic_model.get_db().get_property_index();
+    let attribute_use = semantic_ids.iter().find_map(|semantic_id| {
+        property_index
+            .get_property(semantic_id)?
+            .find_attribute_use(LuaTypeDeclId::new(name))
+    })?;
+
+    let mut args = call_expr.get_args_list()?.get_args().collect::<Vec<_>>();
+    // 根据冒号定义与冒号调用的情况将调用参数与声明的参数对齐
+    match (call_expr.is_colon_call(), colon_define) {
+        (true, false) => {
+            let LuaExpr::IndexExpr(index_expr) = prefix_expr else {
+                return None;
+            };
+            args.insert(0, index_expr.get_prefix_expr()?);
+        }
+        (false, true) => {
+            if args.is_empty() {
+                return None;
+            }
+            args.remove(0);
+        }
+        _ => {}
+    }
+
+    Some((attribute_use.clone(), args))
+}
+
+/// The 1-based parameter index given as the `i`th argument of the attribute
+pub fn get_attribute_param_index(attribute_use: &LuaAttributeUse, i: usize) -> Option<usize> {
+    match attribute_use
+        .args
+        .get(i)
+        .and_then(|(_, typ)| typ.as_ref())?
+    {
+        LuaType::DocIntegerConst(index) | LuaType::IntegerConst(index) if *index >= 1 => {
+            Some(*index as usize - 1)
+        }
+        _ => None,
+    }
+}
+
+pub fn get_string_literal(expr: &LuaExpr) -> Option<LuaStringToken> {
+    match expr {
+        LuaExpr::LiteralExpr(literal_expr) => match literal_expr.get_literal()? {
+            LuaLiteralToken::String(string_token) => Some(string_token),
+            _ => None,
+        },
+        LuaExpr::ParenExpr(paren_expr) => get_string_literal(&paren_expr.get_expr()?),
+        _ => None,
+    }
+}
+
+/// The source range of `start..end` in the string value, the whole literal when it has escapes
+/// and the offsets of the value and the source differ
+pub fn get_string_value_range(
+    string_token: &LuaStringToken,
+    start: usize,
+    end: usize,
+) -> TextRange {
+    let text = string_token.get_text();
+    let range = string_token.get_range();
+    if !(text.starts_with('"') || text.starts_with('\'')) || text.len() < 2 || text.contains('\\') {
+        return range;
+    }
+    let value_start = range.start() + TextSize::from(1);
+    TextRange::new(
+        value_start + TextSize::from(start as u32),
+        value_start + TextSize::from(end as u32),
+    )
+}
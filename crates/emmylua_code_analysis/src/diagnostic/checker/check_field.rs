@@ -5,13 +5,17 @@ use emmylua_parser::{
     LuaIndexExpr, LuaIndexKey, LuaRepeatStat, LuaSyntaxKind, LuaTokenKind, LuaVarExpr,
     LuaWhileStat,
 };
+use serde_json::Value;
 
 use crate::{
     DiagnosticCode, InferFailReason, LuaMemberKey, LuaSemanticDeclId, LuaType, ModuleInfo,
     SemanticModel, enum_variable_is_param, parse_require_module_info,
 };
 
-use super::{Checker, DiagnosticContext, humanize_lint_type};
+use super::{
+    Checker, DiagnosticContext, humanize_lint_type,
+    similar_names::{format_names, get_similar_names},
+};
 
 pub struct CheckFieldChecker;
 
@@ -95,34 +99,57 @@ fn check_index_expr(
     }
 
     let index_name = index_key.get_path_part();
-    match code {
-        DiagnosticCode::InjectField => {
-            context.add_diagnostic(
-                DiagnosticCode::InjectField,
-                index_key.get_range()?,
-                t!(
-                    "Fields cannot be injected into the reference of `%{class}` for `%{field}`. ",
-                    class = humanize_lint_type(db, &prefix_typ),
-                    field = index_name,
-                )
-                .to_string(),
-                None,
-            );
+    let similar_names = match &index_key {
+        LuaIndexKey::Name(_) | LuaIndexKey::String(_) => {
+            get_similar_member_names(semantic_model, &prefix_typ, &index_name)
         }
+        _ => Vec::new(),
+    };
+    let message = match code {
+        DiagnosticCode::InjectField => t!(
+            "Fields cannot be injected into the reference of `%{class}` for `%{field}`. ",
+            class = humanize_lint_type(db, &prefix_typ),
+            field = index_name,
+        ),
         DiagnosticCode::UndefinedField => {
-            context.add_diagnostic(
-                DiagnosticCode::UndefinedField,
-                index_key.get_range()?,
-                t!("Undefined field `%{field}`. ", field = index_name,).to_string(),
-                None,
-            );
+            t!("Undefined field `%{field}`. ", field = index_name,)
         }
-        _ => {}
+        _ => return Some(()),
+    };
+    let mut message = message.to_string();
+    if !similar_names.is_empty() {
+        message.push_str(&t!(
+            "Did you mean %{names}?",
+            names = format_names(&similar_names)
+        ));
     }
+    context.add_diagnostic(
+        code,
+        index_key.get_range()?,
+        message,
+        Some(Value::Array(
+            similar_names.into_iter().map(Value::String).collect(),
+        )),
+    );
 
     Some(())
 }
 
+fn get_similar_member_names(
+    semantic_model: &SemanticModel,
+    prefix_typ: &LuaType,
+    name: &str,
+) -> Vec<String> {
+    let Some(member_map) = semantic_model.get_member_info_map(prefix_typ) else {
+        return Vec::new();
+    };
+    let candidates = member_map.keys().filter_map(|key| match key {
+        LuaMemberKey::Name(name) => Some(name.as_str()),
+        _ => None,
+    });
+    get_similar_names(name, candidates)
+}
+
 fn is_invalid_prefix_type(typ: &LuaType) -> bool {
     let mut current_typ = typ;
     loop {
@@ -0,0 +1,54 @@
+use emmylua_parser::{LuaAstNode, LuaCallExpr, LuaClosureExpr};
+
+use crate::{DiagnosticCode, FileId, SemanticModel};
+
+use super::{Checker, DiagnosticContext, attribute_util::get_string_literal};
+
+pub struct CircleRequireChecker;
+
+impl Checker for CircleRequireChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::CircleRequire];
+
+    /// 检查加载时互相 require 的模块
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let root = semantic_model.get_root().clone();
+        for call_expr in root.descendants::<LuaCallExpr>() {
+            if call_expr.is_require() && call_expr.ancestors::<LuaClosureExpr>().next().is_none() {
+                check_require_call(context, &call_expr);
+            }
+        }
+    }
+}
+
+fn check_require_call(context: &mut DiagnosticContext, call_expr: &LuaCallExpr) -> Option<()> {
+    let arg = call_expr.get_args_list()?.get_args().next()?;
+    let module_path = get_string_literal(&arg)?.get_value();
+    let db = context.db;
+    let module_file_id = db.get_module_index().find_module(&module_path)?.file_id;
+    let path = db
+        .get_file_dependencies_index()
+        .find_require_path(module_file_id, context.file_id)?;
+
+    let cycle = std::iter::once(context.file_id)
+        .chain(path)
+        .map(|file_id| get_module_name(context, file_id))
+        .collect::<Vec<_>>();
+    context.add_diagnostic(
+        DiagnosticCode::CircleRequire,
+        call_expr.get_range(),
+        t!("Circular require: %{cycle}", cycle = cycle.join(" -> ")).to_string(),
+        None,
+    );
+
+    Some(())
+}
+
+/// Every file of the cycle is required by another one, so it is a module
+fn get_module_name(context: &DiagnosticContext, file_id: FileId) -> String {
+    context
+        .db
+        .get_module_index()
+        .get_module(file_id)
+        .map(|module_info| module_info.full_module_name.clone())
+        .unwrap_or_default()
+}
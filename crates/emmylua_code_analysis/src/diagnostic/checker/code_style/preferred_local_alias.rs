@@ -5,6 +5,7 @@ use emmylua_parser::{
     LuaSyntaxKind,
 };
 use rowan::{NodeOrToken, TextRange};
+use serde_json::json;
 
 use crate::{
     DiagnosticCode, LuaDeclId, LuaSemanticDeclId, SemanticDeclLevel, SemanticModel,
@@ -98,7 +99,7 @@ fn collect_local_alias(
                 let name_token = local_name.get_name_token()?;
                 let preferred_name = name_token.get_name_text();
 
-                local_alias_set.insert(name, preferred_name.to_string(), semantic_id);
+                local_alias_set.insert(name, preferred_name.to_string(), semantic_id, decl_id);
                 local_alias_set.add_disable_check(value_expr.get_range());
             }
         }
@@ -138,6 +139,7 @@ struct LocalAliasSet {
 struct LocalAliasInfo {
     pub decl_id: LuaSemanticDeclId,
     pub preferred_name: String,
+    pub local_decl_id: LuaDeclId,
     pub invalid: bool,
 }
 
@@ -157,13 +159,20 @@ impl LocalAliasSet {
         self.local_alias_stack.pop();
     }
 
-    fn insert(&mut self, name: String, preferred_name: String, decl_id: LuaSemanticDeclId) {
+    fn insert(
+        &mut self,
+        name: String,
+        preferred_name: String,
+        decl_id: LuaSemanticDeclId,
+        local_decl_id: LuaDeclId,
+    ) {
         if let Some(map) = self.local_alias_stack.last_mut() {
             map.insert(
                 name,
                 LocalAliasInfo {
                     decl_id,
                     preferred_name,
+                    local_decl_id,
                     invalid: false,
                 },
             );
@@ -247,7 +256,10 @@ fn check_index_expr_preference(
                 name = alias_info.preferred_name
             )
             .to_string(),
-            None,
+            Some(json!({
+                "name": alias_info.preferred_name,
+                "position": u32::from(alias_info.local_decl_id.position),
+            })),
         );
     }
 
@@ -1,8 +1,8 @@
 use emmylua_parser::{BinaryOperator, LuaAst, LuaAstNode, LuaBinaryExpr, LuaExpr};
 
 use crate::{
-    DiagnosticCode, LuaMemberKey, LuaType, LuaTypeDeclId, SemanticModel,
-    diagnostic::checker::humanize_lint_type,
+    DiagnosticCode, LuaMemberKey, LuaSemanticDeclId, LuaType, LuaTypeDeclId, SemanticDeclLevel,
+    SemanticModel, diagnostic::checker::humanize_lint_type,
 };
 
 use super::{Checker, DiagnosticContext};
@@ -52,8 +52,8 @@ fn check_binary_expr(
     }
 
     let (left_expr, right_expr) = binary_expr.get_exprs()?;
-    let left_type = semantic_model.infer_expr(left_expr.clone()).ok()?;
-    let right_type = semantic_model.infer_expr(right_expr.clone()).ok()?;
+    let left_type = infer_operand_type(semantic_model, &left_expr)?;
+    let right_type = infer_operand_type(semantic_model, &right_expr)?;
 
     if check_enum_value_pair(context, &right_expr, &left_type, &right_type).is_some() {
         return Some(());
@@ -65,6 +65,29 @@ fn check_binary_expr(
     Some(())
 }
 
+/// 前面的条件会把枚举变量收窄为剩余的值, 此时使用变量声明的枚举类型
+fn infer_operand_type(semantic_model: &SemanticModel, expr: &LuaExpr) -> Option<LuaType> {
+    let typ = semantic_model.infer_expr(expr.clone()).ok()?;
+    if matches!(typ, LuaType::Ref(_) | LuaType::Def(_))
+        || !matches!(expr, LuaExpr::NameExpr(_) | LuaExpr::IndexExpr(_))
+    {
+        return Some(typ);
+    }
+
+    let decl_type = match semantic_model.find_decl(
+        expr.syntax().clone().into(),
+        SemanticDeclLevel::default(),
+    )? {
+        LuaSemanticDeclId::LuaDecl(decl_id) => semantic_model.get_type(decl_id.into()),
+        LuaSemanticDeclId::Member(member_id) => semantic_model.get_type(member_id.into()),
+        _ => return Some(typ),
+    };
+    match decl_type {
+        LuaType::Ref(_) | LuaType::Def(_) => Some(decl_type),
+        _ => Some(typ),
+    }
+}
+
 fn check_enum_value_pair(
     context: &mut DiagnosticContext,
     value_expr: &LuaExpr,
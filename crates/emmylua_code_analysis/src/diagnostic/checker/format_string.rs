@@ -0,0 +1,306 @@
+use emmylua_parser::{LuaAstNode, LuaCallExpr, LuaExpr, LuaLiteralToken};
+
+use crate::{DiagnosticCode, LuaMemberKey, LuaType, SemanticModel};
+
+use super::{
+    Checker, DiagnosticContext,
+    attribute_util::{
+        get_attribute_param_index, get_call_attribute, get_string_literal, get_string_value_range,
+    },
+    humanize_lint_type,
+};
+
+pub struct FormatStringChecker;
+
+impl Checker for FormatStringChecker {
+    const CODES: &[DiagnosticCode] = &[
+        DiagnosticCode::InvalidFormatString,
+        DiagnosticCode::FormatArgumentMismatch,
+    ];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let root = semantic_model.get_root().clone();
+        for call_expr in root.descendants::<LuaCallExpr>() {
+            check_call_expr(context, semantic_model, call_expr);
+        }
+    }
+}
+
+fn check_call_expr(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    call_expr: LuaCallExpr,
+) -> Option<()> {
+    let (attribute_use, args) = get_call_attribute(semantic_model, &call_expr, "format_string")?;
+    let param_index = get_attribute_param_index(&attribute_use, 0)?;
+    let string_token = get_string_literal(args.get(param_index)?)?;
+    let specs = parse_format_string(&string_token.get_value());
+    let spec_range =
+        |spec: &FormatSpec| get_string_value_range(&string_token, spec.start, spec.end);
+
+    let mut conversions = Vec::new();
+    for spec in &specs {
+        match spec.conversion {
+            Some(conversion) => conversions.push((spec, conversion)),
+            None => {
+                context.add_diagnostic(
+                    DiagnosticCode::InvalidFormatString,
+                    spec_range(spec),
+                    t!(
+                        "Invalid conversion `%{spec}` in format string",
+                        spec = spec.text
+                    )
+                    .to_string(),
+                    None,
+                );
+            }
+        }
+    }
+    // 无效的转换会让格式化在运行时出错, 此时不再检查参数
+    if conversions.len() != specs.len() {
+        return Some(());
+    }
+
+    let format_args = &args[param_index + 1..];
+    // `...` 或函数调用作为最后一个参数时, 其数量未知
+    let multi_value = format_args.last().is_some_and(is_multi_value_expr);
+    let known_len = if multi_value {
+        format_args.len() - 1
+    } else {
+        format_args.len()
+    };
+
+    for (i, (spec, conversion)) in conversions.iter().enumerate() {
+        let Some(arg) = format_args.get(i) else {
+            if !multi_value {
+                context.add_diagnostic(
+                    DiagnosticCode::FormatArgumentMismatch,
+                    spec_range(spec),
+                    t!(
+                        "Missing argument for `%{spec}` in format string",
+                        spec = spec.text
+                    )
+                    .to_string(),
+                    None,
+                );
+            }
+            continue;
+        };
+        if i >= known_len {
+            continue;
+        }
+        let Ok(typ) = semantic_model.infer_expr(arg.clone()) else {
+            continue;
+        };
+        if is_acceptable(semantic_model, *conversion, &typ) {
+            continue;
+        }
+        let typ = humanize_lint_type(semantic_model.get_db(), &typ);
+        let message = match conversion {
+            FormatConversion::Integer => t!(
+                "`%{spec}` expects an integer, but got `%{typ}`",
+                spec = spec.text,
+                typ = typ
+            ),
+            FormatConversion::Float => t!(
+                "`%{spec}` expects a number, but got `%{typ}`",
+                spec = spec.text,
+                typ = typ
+            ),
+            FormatConversion::String => t!(
+                "`%{spec}` can not format `%{typ}`, it has no `__tostring` metamethod",
+                spec = spec.text,
+                typ = typ
+            ),
+            FormatConversion::Quoted => t!(
+                "`%{spec}` can not format `%{typ}`, it has no literal form",
+                spec = spec.text,
+                typ = typ
+            ),
+            FormatConversion::Pointer => continue,
+        };
+        context.add_diagnostic(
+            DiagnosticCode::FormatArgumentMismatch,
+            arg.get_range(),
+            message.to_string(),
+            None,
+        );
+    }
+
+    if known_len > conversions.len() {
+        for arg in &format_args[conversions.len()..known_len] {
+            context.add_diagnostic(
+                DiagnosticCode::FormatArgumentMismatch,
+                arg.get_range(),
+                t!("The argument is not used by the format string").to_string(),
+                None,
+            );
+        }
+    }
+
+    Some(())
+}
+
+fn is_multi_value_expr(expr: &LuaExpr) -> bool {
+    match expr {
+        LuaExpr::CallExpr(_) => true,
+        LuaExpr::LiteralExpr(literal_expr) => {
+            matches!(literal_expr.get_literal(), Some(LuaLiteralToken::Dots(_)))
+        }
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormatConversion {
+    /// `c d i o u x X`
+    Integer,
+    /// `a A e E f F g G`
+    Float,
+    /// `s`
+    String,
+    /// `q`
+    Quoted,
+    /// `p`
+    Pointer,
+}
+
+/// A conversion of the format string, `conversion` is `None` when it is invalid
+#[derive(Debug)]
+struct FormatSpec {
+    start: usize,
+    end: usize,
+    text: String,
+    conversion: Option<FormatConversion>,
+}
+
+/// Lua 允许的修饰符: 标志 `-+ #0`, 至多两位的宽度与精度
+fn parse_format_string(format: &str) -> Vec<FormatSpec> {
+    let bytes = format.as_bytes();
+    let mut specs = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        if bytes.get(i) == Some(&b'%') {
+            i += 1;
+            continue;
+        }
+        while i < bytes.len() && b"-+ #0".contains(&bytes[i]) {
+            i += 1;
+        }
+        let mut valid = skip_digits(bytes, &mut i) <= 2;
+        if bytes.get(i) == Some(&b'.') {
+            i += 1;
+            valid &= skip_digits(bytes, &mut i) <= 2;
+        }
+
+        let conversion = match bytes.get(i) {
+            Some(b'c' | b'd' | b'i' | b'o' | b'u' | b'x' | b'X') => Some(FormatConversion::Integer),
+            Some(b'a' | b'A' | b'e' | b'E' | b'f' | b'F' | b'g' | b'G') => {
+                Some(FormatConversion::Float)
+            }
+            Some(b's') => Some(FormatConversion::String),
+            Some(b'q') => Some(FormatConversion::Quoted),
+            Some(b'p') => Some(FormatConversion::Pointer),
+            _ => None,
+        };
+        if i < bytes.len() {
+            i += 1;
+            while !format.is_char_boundary(i) {
+                i += 1;
+            }
+        }
+        specs.push(FormatSpec {
+            start,
+            end: i,
+            text: format[start..i].to_string(),
+            conversion: conversion.filter(|_| valid),
+        });
+    }
+
+    specs
+}
+
+fn skip_digits(bytes: &[u8], i: &mut usize) -> usize {
+    let start = *i;
+    while *i < bytes.len() && bytes[*i].is_ascii_digit() {
+        *i += 1;
+    }
+    *i - start
+}
+
+/// Whether a value of the type may be formatted by the conversion, an unknown type always may
+fn is_acceptable(
+    semantic_model: &SemanticModel,
+    conversion: FormatConversion,
+    typ: &LuaType,
+) -> bool {
+    if let LuaType::Union(union) = typ {
+        return union
+            .into_vec()
+            .iter()
+            .any(|typ| is_acceptable(semantic_model, conversion, typ));
+    }
+
+    match conversion {
+        FormatConversion::Integer | FormatConversion::Float => match typ {
+            LuaType::FloatConst(value) => {
+                conversion == FormatConversion::Float || value.fract() == 0.0
+            }
+            LuaType::Nil
+            | LuaType::Boolean
+            | LuaType::BooleanConst(_)
+            | LuaType::DocBooleanConst(_)
+            | LuaType::String
+            | LuaType::StringConst(_)
+            | LuaType::DocStringConst(_)
+            | LuaType::Function
+            | LuaType::Signature(_)
+            | LuaType::DocFunction(_) => false,
+            _ => !is_table_value(semantic_model, typ),
+        },
+        FormatConversion::String => {
+            !is_table_value(semantic_model, typ)
+                || semantic_model
+                    .get_member_info_with_key(typ, LuaMemberKey::Name("__tostring".into()), false)
+                    .is_some_and(|members| !members.is_empty())
+        }
+        FormatConversion::Quoted => {
+            !matches!(
+                typ,
+                LuaType::Function | LuaType::Signature(_) | LuaType::DocFunction(_)
+            ) && !is_table_value(semantic_model, typ)
+        }
+        FormatConversion::Pointer => true,
+    }
+}
+
+/// A table, or an instance of a class declared in code, classes of meta files may describe
+/// userdata
+fn is_table_value(semantic_model: &SemanticModel, typ: &LuaType) -> bool {
+    match typ {
+        LuaType::Table
+        | LuaType::TableConst(_)
+        | LuaType::TableGeneric(_)
+        | LuaType::Array(_)
+        | LuaType::Tuple(_)
+        | LuaType::Object(_) => true,
+        LuaType::Ref(type_decl_id) | LuaType::Def(type_decl_id) => {
+            let db = semantic_model.get_db();
+            let Some(type_decl) = db.get_type_index().get_type_decl(type_decl_id) else {
+                return false;
+            };
+            type_decl.is_class()
+                && type_decl
+                    .get_locations()
+                    .iter()
+                    .all(|location| !db.get_module_index().is_meta_file(&location.file_id))
+        }
+        _ => false,
+    }
+}
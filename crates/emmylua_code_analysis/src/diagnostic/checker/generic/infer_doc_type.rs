@@ -127,6 +127,7 @@ fn infer_buildin_or_ref_type(
         "unknown" => LuaType::Unknown,
         "nil" | "void" => LuaType::Nil,
         "any" => LuaType::Any,
+        "never" => LuaType::Never,
         "userdata" => LuaType::Userdata,
         "thread" => LuaType::Thread,
         "boolean" | "bool" => LuaType::Boolean,
@@ -272,6 +273,16 @@ fn infer_special_generic_type(
                 LuaAliasCallType::new(LuaAliasCallKind::RawGet, params).into(),
             ));
         }
+        "std.Captures" => {
+            let mut params = Vec::new();
+            for param in generic_type.get_generic_types()?.get_types() {
+                let param_type = infer_doc_type(semantic_model, &param);
+                params.push(param_type);
+            }
+            return Some(LuaType::Call(
+                LuaAliasCallType::new(LuaAliasCallKind::Captures, params).into(),
+            ));
+        }
         "TypeGuard" => {
             let first_doc_param_type = generic_type.get_generic_types()?.get_types().next()?;
             let first_param = infer_doc_type(semantic_model, &first_doc_param_type);
@@ -0,0 +1,173 @@
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaBlock, LuaGotoStat, LuaLabelStat, LuaLanguageLevel, LuaStat,
+    LuaSyntaxKind,
+};
+
+use crate::{DiagnosticCode, LuaDeclarationTree, LuaLabel, LuaScopeKind, SemanticModel};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct GotoLabelChecker;
+
+impl Checker for GotoLabelChecker {
+    const CODES: &[DiagnosticCode] = &[
+        DiagnosticCode::RedefinedLabel,
+        DiagnosticCode::UndefinedGoto,
+        DiagnosticCode::GotoIntoLocalScope,
+    ];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let file_id = semantic_model.get_file_id();
+        let Some(decl_tree) = semantic_model
+            .get_db()
+            .get_decl_index()
+            .get_decl_tree(&file_id)
+        else {
+            return;
+        };
+
+        // since lua 5.4 a label can not share the name of a visible label in an enclosing block
+        let check_enclosing = context.config.level >= LuaLanguageLevel::Lua54;
+        for label in decl_tree.get_labels() {
+            check_redefined_label(context, decl_tree, label, check_enclosing);
+        }
+
+        let root = semantic_model.get_root().clone();
+        for goto_stat in root.descendants::<LuaGotoStat>() {
+            check_goto_stat(context, semantic_model, goto_stat);
+        }
+    }
+}
+
+fn check_redefined_label(
+    context: &mut DiagnosticContext,
+    decl_tree: &LuaDeclarationTree,
+    label: &LuaLabel,
+    check_enclosing: bool,
+) -> Option<()> {
+    let mut scope = decl_tree.get_scope(&label.get_scope_id())?;
+    loop {
+        let redefined = decl_tree.get_labels().iter().any(|other| {
+            other.get_scope_id() == scope.get_id()
+                && other.get_name() == label.get_name()
+                && other.get_position() < label.get_position()
+        });
+        if redefined {
+            context.add_diagnostic(
+                DiagnosticCode::RedefinedLabel,
+                label.get_range(),
+                t!(
+                    "Label '%{name}' is already defined",
+                    name = label.get_name()
+                )
+                .to_string(),
+                None,
+            );
+            return Some(());
+        }
+
+        if !check_enclosing || scope.get_kind() == LuaScopeKind::Closure {
+            return Some(());
+        }
+
+        scope = decl_tree.get_scope(&scope.get_parent()?)?;
+    }
+}
+
+fn check_goto_stat(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    goto_stat: LuaGotoStat,
+) -> Option<()> {
+    let name_token = goto_stat.get_label_name_token()?;
+    let Some(label) = semantic_model.find_goto_label(&goto_stat) else {
+        let label_name = name_token.get_name_text();
+        context.add_diagnostic(
+            DiagnosticCode::UndefinedGoto,
+            name_token.get_range(),
+            t!(
+                "goto label '%{label_name}' not found",
+                label_name = label_name
+            )
+            .to_string(),
+            None,
+        );
+        return Some(());
+    };
+
+    // only a forward jump can enter the scope of a local
+    if label.get_position() < goto_stat.get_position() {
+        return Some(());
+    }
+
+    let label_stat = semantic_model
+        .get_root()
+        .syntax()
+        .token_at_offset(label.get_position())
+        .right_biased()?
+        .parent()
+        .and_then(LuaLabelStat::cast)?;
+    let block = label_stat.get_parent::<LuaBlock>()?;
+    if is_label_at_block_end(&block, &label_stat) {
+        return Some(());
+    }
+
+    let goto_anchor = goto_stat
+        .syntax()
+        .ancestors()
+        .find(|node| node.parent().as_ref() == Some(block.syntax()))?;
+    for stat in block.get_stats() {
+        let position = stat.get_position();
+        if position <= goto_anchor.text_range().start() {
+            continue;
+        }
+        if position >= label_stat.get_position() {
+            break;
+        }
+
+        let local_name = match &stat {
+            LuaStat::LocalStat(local_stat) => local_stat
+                .get_local_name_list()
+                .next()
+                .and_then(|local_name| local_name.get_name_token()),
+            LuaStat::LocalFuncStat(local_func_stat) => local_func_stat
+                .get_local_name()
+                .and_then(|local_name| local_name.get_name_token()),
+            _ => continue,
+        };
+
+        if let Some(local_name) = local_name {
+            context.add_diagnostic(
+                DiagnosticCode::GotoIntoLocalScope,
+                name_token.get_range(),
+                t!(
+                    "goto '%{label_name}' jumps into the scope of local '%{name}'",
+                    label_name = label.get_name(),
+                    name = local_name.get_name_text()
+                )
+                .to_string(),
+                None,
+            );
+            return Some(());
+        }
+    }
+
+    Some(())
+}
+
+/// A label followed only by void statements is treated as the end of the block,
+/// so the locals of the block are already out of scope.
+fn is_label_at_block_end(block: &LuaBlock, label_stat: &LuaLabelStat) -> bool {
+    if block
+        .syntax()
+        .parent()
+        .is_some_and(|parent| parent.kind() == LuaSyntaxKind::RepeatStat.into())
+    {
+        return false;
+    }
+
+    block
+        .get_stats()
+        .filter(|stat| stat.get_position() > label_stat.get_position())
+        .all(|stat| matches!(stat, LuaStat::LabelStat(_) | LuaStat::EmptyStat(_)))
+}
@@ -0,0 +1,223 @@
+use emmylua_parser::{LuaAstNode, LuaClosureExpr, LuaFuncStat, LuaVarExpr};
+use rowan::TextRange;
+
+use crate::{
+    AsyncState, DiagnosticCode, LuaFunctionType, LuaSemanticDeclId, LuaSignatureId, LuaType,
+    LuaTypeDeclId, SemanticModel, SignatureReturnStatus, VariadicType,
+};
+
+use super::{Checker, DiagnosticContext, humanize_lint_type};
+
+pub struct IncompatibleOverrideChecker;
+
+impl Checker for IncompatibleOverrideChecker {
+    const CODES: &[DiagnosticCode] = &[
+        DiagnosticCode::IncompatibleOverride,
+        DiagnosticCode::NothingToOverride,
+    ];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let root = semantic_model.get_root().clone();
+        for func_stat in root.descendants::<LuaFuncStat>() {
+            check_func_stat(context, semantic_model, func_stat);
+        }
+    }
+}
+
+fn check_func_stat(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    func_stat: LuaFuncStat,
+) -> Option<()> {
+    let LuaVarExpr::IndexExpr(index_expr) = func_stat.get_func_name()? else {
+        return None;
+    };
+    let name_token = index_expr.get_index_name_token()?;
+    let name_range = name_token.text_range();
+    let closure = func_stat.get_closure()?;
+    let signature_id = LuaSignatureId::from_closure(context.file_id, &closure);
+    let marked_override = context
+        .db
+        .get_property_index()
+        .get_property(&LuaSemanticDeclId::Signature(signature_id))
+        .and_then(|property| property.find_attribute_use(LuaTypeDeclId::new("override")))
+        .is_some();
+
+    let super_member = match semantic_model
+        .infer_expr(index_expr.get_prefix_expr()?)
+        .ok()?
+    {
+        LuaType::Def(type_decl_id) | LuaType::Ref(type_decl_id) => {
+            let member_key = semantic_model.get_member_key(&index_expr.get_index_key()?)?;
+            semantic_model.find_super_member(&type_decl_id, &member_key)
+        }
+        _ => None,
+    };
+    let Some(super_member) = super_member else {
+        if marked_override {
+            context.add_diagnostic(
+                DiagnosticCode::NothingToOverride,
+                name_range,
+                t!(
+                    "`%{name}` is marked as override, but no super type has it",
+                    name = name_token.text()
+                )
+                .to_string(),
+                None,
+            );
+        }
+        return Some(());
+    };
+
+    let signature = context.db.get_signature_index().get(&signature_id)?;
+    let func = signature.to_doc_func_type();
+    // 推断出的返回值不参与比较
+    let (super_func, super_doc_returns) = match &super_member.typ {
+        LuaType::DocFunction(func) => (func.clone(), true),
+        LuaType::Signature(super_signature_id) => {
+            let super_signature = context.db.get_signature_index().get(super_signature_id)?;
+            (
+                super_signature.to_doc_func_type(),
+                super_signature.resolve_return == SignatureReturnStatus::DocResolve,
+            )
+        }
+        _ => return None,
+    };
+
+    check_params(context, semantic_model, &closure, &func, &super_func);
+    if super_doc_returns && signature.resolve_return == SignatureReturnStatus::DocResolve {
+        check_returns(context, semantic_model, name_range, &func, &super_func);
+    }
+    if func.get_async_state() == AsyncState::Async
+        && super_func.get_async_state() != AsyncState::Async
+    {
+        context.add_diagnostic(
+            DiagnosticCode::IncompatibleOverride,
+            name_range,
+            t!(
+                "`%{name}` is async, but the method it overrides is not",
+                name = name_token.text()
+            )
+            .to_string(),
+            None,
+        );
+    }
+
+    Some(())
+}
+
+/// Each parameter has to accept what callers of the overridden method pass, and a required
+/// parameter needs a matching one in the overridden method
+fn check_params(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    closure: &LuaClosureExpr,
+    func: &LuaFunctionType,
+    super_func: &LuaFunctionType,
+) -> Option<()> {
+    let (params, self_offset) = get_method_params(func);
+    let (super_params, _) = get_method_params(super_func);
+    let param_ranges = closure
+        .get_params_list()?
+        .get_params()
+        .skip(self_offset)
+        .map(|param| param.get_range())
+        .collect::<Vec<_>>();
+    let param_range = |i: usize| param_ranges.get(i).copied().unwrap_or(closure.get_range());
+    let super_variadic = super_params.last().is_some_and(|(name, _)| name == "...");
+
+    for (i, (name, typ)) in params.iter().enumerate() {
+        if name == "..." {
+            break;
+        }
+        let Some(typ) = typ else {
+            continue;
+        };
+        let Some((_, super_typ)) = super_params.get(i) else {
+            if !super_variadic && !typ.is_optional() {
+                context.add_diagnostic(
+                    DiagnosticCode::IncompatibleOverride,
+                    param_range(i),
+                    t!(
+                        "Parameter `%{name}` is required, but the overridden method takes %{count} parameters",
+                        name = name,
+                        count = super_params.len()
+                    )
+                    .to_string(),
+                    None,
+                );
+            }
+            continue;
+        };
+        let Some(super_typ) = super_typ else {
+            continue;
+        };
+        if typ.contain_tpl() || super_typ.contain_tpl() {
+            continue;
+        }
+        if semantic_model.type_check(typ, super_typ).is_err() {
+            context.add_diagnostic(
+                DiagnosticCode::IncompatibleOverride,
+                param_range(i),
+                t!(
+                    "Parameter `%{name}` of type `%{typ}` does not accept `%{super_typ}` of the overridden method",
+                    name = name,
+                    typ = humanize_lint_type(context.db, typ),
+                    super_typ = humanize_lint_type(context.db, super_typ)
+                )
+                .to_string(),
+                None,
+            );
+        }
+    }
+
+    Some(())
+}
+
+/// Each return value has to be one the overridden method may return
+fn check_returns(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    range: TextRange,
+    func: &LuaFunctionType,
+    super_func: &LuaFunctionType,
+) {
+    let returns = get_returns(func);
+    let super_returns = get_returns(super_func);
+    for (typ, super_typ) in returns.iter().zip(super_returns.iter()) {
+        if typ.contain_tpl() || super_typ.contain_tpl() {
+            continue;
+        }
+        if semantic_model.type_check(super_typ, typ).is_err() {
+            context.add_diagnostic(
+                DiagnosticCode::IncompatibleOverride,
+                range,
+                t!(
+                    "Return type `%{typ}` is not compatible with `%{super_typ}` of the overridden method",
+                    typ = humanize_lint_type(context.db, typ),
+                    super_typ = humanize_lint_type(context.db, super_typ)
+                )
+                .to_string(),
+                None,
+            );
+        }
+    }
+}
+
+/// The parameters without `self`, and the number of parameters skipped
+fn get_method_params(func: &LuaFunctionType) -> (&[(String, Option<LuaType>)], usize) {
+    let params = func.get_params();
+    if !func.is_colon_define() && params.first().is_some_and(|(name, _)| name == "self") {
+        (&params[1..], 1)
+    } else {
+        (params, 0)
+    }
+}
+
+fn get_returns(func: &LuaFunctionType) -> Vec<LuaType> {
+    match func.get_variadic_ret() {
+        VariadicType::Base(LuaType::Nil) => Vec::new(),
+        VariadicType::Base(typ) => vec![typ],
+        VariadicType::Multi(types) => types,
+    }
+}
@@ -0,0 +1,77 @@
+use emmylua_parser::{LuaAstNode, LuaCallExpr, LuaExpr, LuaLiteralToken};
+
+use crate::{DiagnosticCode, LuaPatternErrorKind, SemanticModel, parse_lua_pattern};
+
+use super::{
+    Checker, DiagnosticContext,
+    attribute_util::{
+        get_attribute_param_index, get_call_attribute, get_string_literal, get_string_value_range,
+    },
+};
+
+pub struct InvalidPatternChecker;
+
+impl Checker for InvalidPatternChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::InvalidPattern];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let root = semantic_model.get_root().clone();
+        for call_expr in root.descendants::<LuaCallExpr>() {
+            check_call_expr(context, semantic_model, call_expr);
+        }
+    }
+}
+
+fn check_call_expr(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    call_expr: LuaCallExpr,
+) -> Option<()> {
+    let (attribute_use, args) = get_call_attribute(semantic_model, &call_expr, "lua_pattern")?;
+    let param_index = get_attribute_param_index(&attribute_use, 0)?;
+    // `string.find(s, pattern, init, true)` 按普通字符串查找
+    if let Some(plain_index) = get_attribute_param_index(&attribute_use, 1)
+        && let Some(plain_arg) = args.get(plain_index)
+        && !is_false_literal(plain_arg)
+    {
+        return None;
+    }
+
+    let string_token = get_string_literal(args.get(param_index)?)?;
+    let Err(error) = parse_lua_pattern(&string_token.get_value()) else {
+        return Some(());
+    };
+    let message = match &error.kind {
+        LuaPatternErrorKind::EndsWithEscape => t!("Malformed pattern, it ends with `%`"),
+        LuaPatternErrorKind::MissingBracket => t!("Malformed pattern, missing `]`"),
+        LuaPatternErrorKind::MissingBalanceArgs => t!("Missing arguments to `%b` in pattern"),
+        LuaPatternErrorKind::MissingFrontierSet => t!("Missing `[` after `%f` in pattern"),
+        LuaPatternErrorKind::UnfinishedCapture => t!("Unfinished capture in pattern"),
+        LuaPatternErrorKind::InvalidCapture => {
+            t!("Invalid pattern capture, no `(` matches this `)`")
+        }
+        LuaPatternErrorKind::InvalidCaptureIndex(index) => {
+            t!("Invalid capture index `%{index}` in pattern", index = index)
+        }
+        LuaPatternErrorKind::TooManyCaptures => t!("Too many captures in pattern"),
+    };
+    context.add_diagnostic(
+        DiagnosticCode::InvalidPattern,
+        get_string_value_range(&string_token, error.start, error.end),
+        message.to_string(),
+        None,
+    );
+
+    Some(())
+}
+
+fn is_false_literal(expr: &LuaExpr) -> bool {
+    let LuaExpr::LiteralExpr(literal_expr) = expr else {
+        return false;
+    };
+    match literal_expr.get_literal() {
+        Some(LuaLiteralToken::Bool(bool_token)) => !bool_token.is_true(),
+        Some(LuaLiteralToken::Nil(_)) => true,
+        _ => false,
+    }
+}
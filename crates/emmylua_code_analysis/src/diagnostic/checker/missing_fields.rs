@@ -6,6 +6,7 @@ use crate::{DiagnosticCode, LuaMemberOwner, LuaType, LuaTypeCache, LuaTypeDeclId
 
 use super::{Checker, DiagnosticContext, humanize_lint_type};
 use itertools::Itertools;
+use serde_json::Value;
 
 pub struct MissingFieldsChecker;
 
@@ -115,21 +116,28 @@ fn check_table_expr(
 
     let missing_fields = required_fields
         .difference(&current_fields)
-        .map(|s| format!("`{}`", s))
         .sorted()
-        .join(", ");
+        .cloned()
+        .collect::<Vec<_>>();
 
     if !missing_fields.is_empty() {
+        let fields = missing_fields
+            .iter()
+            .map(|field| format!("`{}`", field))
+            .join(", ");
         context.add_diagnostic(
             DiagnosticCode::MissingFields,
             expr.get_range(),
             t!(
                 "Missing required fields in type `%{typ}`: %{fields}",
                 typ = humanize_lint_type(db, &table_type),
-                fields = missing_fields
+                fields = fields
             )
             .to_string(),
-            None,
+            // the names are filled in by the quickfix
+            Some(Value::Array(
+                missing_fields.into_iter().map(Value::String).collect(),
+            )),
         );
     }
 
@@ -2,12 +2,14 @@ mod access_invisible;
 mod analyze_error;
 mod assign_type_mismatch;
 mod attribute_check;
+mod attribute_util;
 mod await_in_sync;
 mod cast_type_mismatch;
 mod check_field;
 mod check_param_count;
 mod check_return_count;
 mod circle_doc_class;
+mod circle_require;
 mod code_style;
 mod code_style_check;
 mod deprecated;
@@ -17,24 +19,32 @@ mod duplicate_index;
 mod duplicate_require;
 mod duplicate_type;
 mod enum_value_mismatch;
+mod format_string;
 mod generic;
 mod global_non_module;
+mod goto_label;
+mod incompatible_override;
 mod incomplete_signature_doc;
+mod invalid_pattern;
 mod local_const_reassign;
 mod missing_fields;
 mod need_check_nil;
+mod non_exhaustive_dispatch;
 mod param_type_check;
 mod readonly_check;
 mod redefined_local;
 mod require_module_visibility;
 mod return_type_mismatch;
+mod similar_names;
 mod syntax_error;
 mod unbalanced_assignments;
 mod undefined_doc_param;
 mod undefined_global;
+mod unimplemented_member;
 mod unknown_doc_tag;
 mod unnecessary_assert;
 mod unnecessary_if;
+mod unreachable_code;
 mod unused;
 
 use emmylua_parser::{
@@ -50,6 +60,8 @@ use crate::{
     semantic::SemanticModel,
 };
 
+pub(crate) use unused::{UnusedCheckResult, get_unused_check_result};
+
 use super::{
     DiagnosticCode,
     lua_diagnostic_code::{get_default_severity, is_code_default_enable},
@@ -82,6 +94,7 @@ pub fn check_file(context: &mut DiagnosticContext, semantic_model: &SemanticMode
     run_check::<undefined_global::UndefinedGlobalChecker>(context, semantic_model);
     run_check::<unnecessary_assert::UnnecessaryAssertChecker>(context, semantic_model);
     run_check::<unnecessary_if::UnnecessaryIfChecker>(context, semantic_model);
+    run_check::<unreachable_code::UnreachableCodeChecker>(context, semantic_model);
     run_check::<access_invisible::AccessInvisibleChecker>(context, semantic_model);
     run_check::<local_const_reassign::LocalConstReassignChecker>(context, semantic_model);
     run_check::<discard_returns::DiscardReturnsChecker>(context, semantic_model);
@@ -93,8 +106,15 @@ pub fn check_file(context: &mut DiagnosticContext, semantic_model: &SemanticMode
     run_check::<return_type_mismatch::ReturnTypeMismatch>(context, semantic_model);
     run_check::<undefined_doc_param::UndefinedDocParamChecker>(context, semantic_model);
     run_check::<redefined_local::RedefinedLocalChecker>(context, semantic_model);
+    run_check::<goto_label::GotoLabelChecker>(context, semantic_model);
     run_check::<check_field::CheckFieldChecker>(context, semantic_model);
+    run_check::<format_string::FormatStringChecker>(context, semantic_model);
+    run_check::<invalid_pattern::InvalidPatternChecker>(context, semantic_model);
+    run_check::<non_exhaustive_dispatch::NonExhaustiveDispatchChecker>(context, semantic_model);
+    run_check::<circle_require::CircleRequireChecker>(context, semantic_model);
+    run_check::<incompatible_override::IncompatibleOverrideChecker>(context, semantic_model);
     run_check::<circle_doc_class::CircleDocClassChecker>(context, semantic_model);
+    run_check::<unimplemented_member::UnimplementedMemberChecker>(context, semantic_model);
     run_check::<incomplete_signature_doc::IncompleteSignatureDocChecker>(context, semantic_model);
     run_check::<assign_type_mismatch::AssignTypeMismatchChecker>(context, semantic_model);
     run_check::<duplicate_require::DuplicateRequireChecker>(context, semantic_model);
@@ -0,0 +1,212 @@
+use emmylua_parser::{
+    BinaryOperator, LuaAstNode, LuaAstToken, LuaCommentOwner, LuaDocAttributeUse, LuaExpr,
+    LuaIfStat, LuaNameExpr, LuaStat, LuaTokenKind,
+};
+use serde_json::json;
+
+use crate::{DiagnosticCode, LuaMemberKey, LuaType, SemanticModel, TypeOps};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct NonExhaustiveDispatchChecker;
+
+impl Checker for NonExhaustiveDispatchChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::NonExhaustiveDispatch];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let root = semantic_model.get_root().clone();
+        for if_stat in root.descendants::<LuaIfStat>() {
+            check_if_stat(context, semantic_model, if_stat);
+        }
+    }
+}
+
+fn check_if_stat(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    if_stat: LuaIfStat,
+) -> Option<()> {
+    let else_block = if_stat
+        .get_else_clause()
+        .and_then(|else_clause| else_clause.get_block());
+    let else_errors = else_block.as_ref().is_some_and(|block| {
+        block.get_stats().any(|stat| match stat {
+            LuaStat::CallExprStat(call_expr_stat) => call_expr_stat
+                .get_call_expr()
+                .is_some_and(|call_expr| call_expr.is_error()),
+            _ => false,
+        })
+    });
+    if !else_errors && !is_marked_exhaustive(&if_stat) {
+        return None;
+    }
+
+    let mut pairs = Vec::new();
+    collect_eq_pairs(if_stat.get_condition_expr()?, &mut pairs)?;
+    for else_if_clause in if_stat.get_else_if_clause_list() {
+        collect_eq_pairs(else_if_clause.get_condition_expr()?, &mut pairs)?;
+    }
+
+    // 被分派的变量是第一个比较中类型不是常量的一侧
+    let (first_left, first_right) = pairs.first()?;
+    let var_expr = [first_left, first_right].into_iter().find(|expr| {
+        matches!(expr, LuaExpr::NameExpr(_) | LuaExpr::IndexExpr(_))
+            && semantic_model
+                .infer_expr((*expr).clone())
+                .is_ok_and(|typ| !typ.is_const())
+    })?;
+    let var_text = var_expr.syntax().text().to_string();
+    let mut comparisons = Vec::new();
+    let mut compared = Vec::new();
+    for (left, right) in &pairs {
+        let (var, value) = if left.syntax().text() == var_text.as_str() {
+            (left, right)
+        } else if right.syntax().text() == var_text.as_str() {
+            (right, left)
+        } else {
+            return None;
+        };
+        let value_type = semantic_model.infer_expr(value.clone()).ok()?;
+        compared.push(get_const_text(&value_type)?);
+        comparisons.push((var.clone(), value.clone(), value_type));
+    }
+
+    // 有 else 时其中的变量类型即为剩余的值, 否则从最后一个比较处的类型中去掉它比较的值
+    let position_expr = else_block
+        .and_then(|block| block.descendants::<LuaNameExpr>().next())
+        .map(LuaExpr::NameExpr);
+    let remaining = match position_expr {
+        Some(position_expr) => semantic_model.infer_var_type_at(var_expr.clone(), position_expr)?,
+        None => {
+            let (last_var, _, last_value_type) = comparisons.last()?;
+            let typ = semantic_model.infer_expr(last_var.clone()).ok()?;
+            TypeOps::Remove.apply(semantic_model.get_db(), &typ, last_value_type)
+        }
+    };
+
+    let remaining_types = match &remaining {
+        LuaType::Union(union) => union.into_vec(),
+        _ => vec![remaining.clone()],
+    };
+    let mut missing = Vec::new();
+    for typ in &remaining_types {
+        if typ.is_nil() || typ.is_never() {
+            continue;
+        }
+        // 剩余的不是有限个值, 无法判断
+        let text = get_const_text(typ)?;
+        // 去掉最后一个值时类型不会收窄为 never, 已比较过的值按值排除
+        if !compared.contains(&text) {
+            missing.push(text);
+        }
+    }
+    if missing.is_empty() {
+        return Some(());
+    }
+
+    let cases = get_enum_cases(semantic_model, var_expr, &comparisons, &missing).unwrap_or(missing);
+    context.add_diagnostic(
+        DiagnosticCode::NonExhaustiveDispatch,
+        if_stat.token_by_kind(LuaTokenKind::TkIf)?.get_range(),
+        t!(
+            "Missing cases for `%{name}`: %{cases}",
+            name = var_text,
+            cases = cases.join(", ")
+        )
+        .to_string(),
+        Some(json!({
+            "var": var_text,
+            "cases": cases,
+        })),
+    );
+
+    Some(())
+}
+
+fn is_marked_exhaustive(if_stat: &LuaIfStat) -> bool {
+    if_stat.get_comments().iter().any(|comment| {
+        comment
+            .descendants::<LuaDocAttributeUse>()
+            .filter_map(|attribute_use| attribute_use.get_type()?.get_name_text())
+            .any(|name| name == "exhaustive")
+    })
+}
+
+/// `a == b`, `(a == b)` and `a == b or c == d`, any other condition is not a dispatch
+fn collect_eq_pairs(expr: LuaExpr, pairs: &mut Vec<(LuaExpr, LuaExpr)>) -> Option<()> {
+    match expr {
+        LuaExpr::ParenExpr(paren_expr) => collect_eq_pairs(paren_expr.get_expr()?, pairs),
+        LuaExpr::BinaryExpr(binary_expr) => {
+            let (left, right) = binary_expr.get_exprs()?;
+            match binary_expr.get_op_token()?.get_op() {
+                BinaryOperator::OpEq => pairs.push((left, right)),
+                BinaryOperator::OpOr => {
+                    collect_eq_pairs(left, pairs)?;
+                    collect_eq_pairs(right, pairs)?;
+                }
+                _ => return None,
+            }
+            Some(())
+        }
+        _ => None,
+    }
+}
+
+/// The value written as a Lua literal
+fn get_const_text(typ: &LuaType) -> Option<String> {
+    match typ {
+        LuaType::StringConst(s) | LuaType::DocStringConst(s) => Some(format!("{:?}", s.as_str())),
+        LuaType::IntegerConst(i) | LuaType::DocIntegerConst(i) => Some(i.to_string()),
+        LuaType::FloatConst(f) => Some(f.to_string()),
+        LuaType::BooleanConst(b) | LuaType::DocBooleanConst(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// The missing values of an enum as its fields in declaration order, written with the prefix the
+/// chain already uses, e.g. `Color.Blue`
+fn get_enum_cases(
+    semantic_model: &SemanticModel,
+    var_expr: &LuaExpr,
+    comparisons: &[(LuaExpr, LuaExpr, LuaType)],
+    missing: &[String],
+) -> Option<Vec<String>> {
+    let db = semantic_model.get_db();
+    let (LuaType::Ref(type_decl_id) | LuaType::Def(type_decl_id)) =
+        semantic_model.infer_expr(var_expr.clone()).ok()?
+    else {
+        return None;
+    };
+    let type_decl = db.get_type_index().get_type_decl(&type_decl_id)?;
+    if !type_decl.is_enum() || type_decl.is_enum_key() {
+        return None;
+    }
+    let prefix = comparisons.iter().find_map(|(_, value, _)| match value {
+        LuaExpr::IndexExpr(index_expr) => Some(index_expr.get_prefix_expr()?.syntax().text()),
+        _ => None,
+    })?;
+
+    let mut members = db
+        .get_member_index()
+        .get_members(&type_decl_id.into())?
+        .into_iter()
+        .collect::<Vec<_>>();
+    members.sort_by_key(|member| member.get_sort_key());
+    let mut cases = Vec::new();
+    for member in members {
+        let LuaMemberKey::Name(name) = member.get_key() else {
+            continue;
+        };
+        let Some(value) = db
+            .get_type_index()
+            .get_type_cache(&member.get_id().into())
+            .and_then(|type_cache| get_const_text(type_cache.as_type()))
+        else {
+            continue;
+        };
+        if missing.contains(&value) {
+            cases.push(format!("{}.{}", prefix, name));
+        }
+    }
+    (cases.len() == missing.len()).then_some(cases)
+}
@@ -2,10 +2,14 @@ use std::collections::HashSet;
 
 use emmylua_parser::{LuaAstNode, LuaClosureExpr, LuaNameExpr};
 use rowan::TextRange;
+use serde_json::Value;
 
 use crate::{DiagnosticCode, LuaSignatureId, SemanticModel};
 
-use super::{Checker, DiagnosticContext};
+use super::{
+    Checker, DiagnosticContext,
+    similar_names::{format_names, get_similar_names},
+};
 
 pub struct UndefinedGlobalChecker;
 
@@ -83,16 +87,64 @@ fn check_name_expr(
         return Some(());
     }
 
+    let similar_names = get_similar_global_names(context, semantic_model, &name_text, name_range);
+    let mut message = t!("undefined global variable: %{name}", name = name_text).to_string();
+    if !similar_names.is_empty() {
+        message.push_str(". ");
+        message.push_str(&t!(
+            "Did you mean %{names}?",
+            names = format_names(&similar_names)
+        ));
+    }
     context.add_diagnostic(
         DiagnosticCode::UndefinedGlobal,
         name_range,
-        t!("undefined global variable: %{name}", name = name_text).to_string(),
-        None,
+        message,
+        Some(Value::Array(
+            similar_names.into_iter().map(Value::String).collect(),
+        )),
     );
 
     Some(())
 }
 
+/// The globals, the configured globals and the locals visible at the name
+fn get_similar_global_names(
+    context: &DiagnosticContext,
+    semantic_model: &SemanticModel,
+    name: &str,
+    name_range: TextRange,
+) -> Vec<String> {
+    let db = semantic_model.get_db();
+    let mut candidates = db
+        .get_global_index()
+        .get_all_global_names()
+        .filter(|global_name| !global_name.contains('.'))
+        .collect::<Vec<_>>();
+    candidates.extend(
+        context
+            .config
+            .global_disable_set
+            .iter()
+            .map(|it| it.as_str()),
+    );
+    let local_names = db
+        .get_decl_index()
+        .get_decl_tree(&semantic_model.get_file_id())
+        .and_then(|decl_tree| {
+            let decl_ids = decl_tree.get_env_decls(name_range.start())?;
+            Some(
+                decl_ids
+                    .iter()
+                    .filter_map(|decl_id| Some(decl_tree.get_decl(decl_id)?.get_name().to_string()))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .unwrap_or_default();
+    candidates.extend(local_names.iter().map(|it| it.as_str()));
+    get_similar_names(name, candidates)
+}
+
 fn check_self_name(semantic_model: &SemanticModel, name_expr: LuaNameExpr) -> Option<()> {
     let closure_expr = name_expr.ancestors::<LuaClosureExpr>();
     for closure_expr in closure_expr {
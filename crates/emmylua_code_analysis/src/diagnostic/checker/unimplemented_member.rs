@@ -0,0 +1,130 @@
+use emmylua_parser::{LuaAst, LuaAstNode, LuaAstToken, LuaComment, LuaDocTagClass};
+use itertools::Itertools;
+use serde_json::Value;
+
+use crate::{
+    DiagnosticCode, LuaMemberKey, LuaMemberOwner, LuaType, LuaTypeCache, LuaTypeDeclId,
+    SemanticModel,
+};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct UnimplementedMemberChecker;
+
+impl Checker for UnimplementedMemberChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::UnimplementedMember];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let root = semantic_model.get_root().clone();
+        for tag in root.descendants::<LuaDocTagClass>() {
+            check_doc_tag_class(context, semantic_model, &tag);
+        }
+    }
+}
+
+fn check_doc_tag_class(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    tag: &LuaDocTagClass,
+) -> Option<()> {
+    // only a class given to a table in code has to define the methods, a class written in
+    // comments alone is a declaration too
+    let comment = tag.get_parent::<LuaComment>()?;
+    if !matches!(
+        comment.get_owner()?,
+        LuaAst::LuaLocalStat(_) | LuaAst::LuaAssignStat(_)
+    ) {
+        return None;
+    }
+
+    let name_token = tag.get_name_token()?;
+    let class_decl = context
+        .db
+        .get_type_index()
+        .find_type_decl(context.file_id, name_token.get_name_text())?;
+    if !class_decl.is_class() {
+        return None;
+    }
+
+    let members = get_unimplemented_members(semantic_model, &class_decl.get_id());
+    if members.is_empty() {
+        return None;
+    }
+
+    let names = members
+        .iter()
+        .map(|(super_id, key)| format!("`{}:{}`", super_id.get_simple_name(), key.to_path()))
+        .join(", ");
+    context.add_diagnostic(
+        DiagnosticCode::UnimplementedMember,
+        name_token.get_range(),
+        t!(
+            "Class `%{name}` does not implement %{members}",
+            name = class_decl.get_name(),
+            members = names
+        )
+        .to_string(),
+        // the names are implemented by the quickfix
+        Some(Value::Array(
+            members
+                .into_iter()
+                .map(|(_, key)| Value::String(key.to_path()))
+                .collect(),
+        )),
+    );
+
+    Some(())
+}
+
+/// The methods a super type declares with `---@field` which neither the class nor another
+/// super type defines
+fn get_unimplemented_members(
+    semantic_model: &SemanticModel,
+    type_decl_id: &LuaTypeDeclId,
+) -> Vec<(LuaTypeDeclId, LuaMemberKey)> {
+    let db = semantic_model.get_db();
+    let self_type = LuaType::Ref(type_decl_id.clone());
+    let mut super_types = Vec::new();
+    type_decl_id.collect_super_types(db, &mut super_types);
+
+    let mut result: Vec<(LuaTypeDeclId, LuaMemberKey)> = Vec::new();
+    for super_type in super_types {
+        let LuaType::Ref(super_id) = super_type else {
+            continue;
+        };
+        let Some(members) = db
+            .get_member_index()
+            .get_sorted_members(&LuaMemberOwner::Type(super_id.clone()))
+        else {
+            continue;
+        };
+
+        for member in members {
+            let key = member.get_key();
+            if !member.get_feature().is_field_decl() || result.iter().any(|(_, found)| found == key)
+            {
+                continue;
+            }
+            let is_method = match db.get_type_index().get_type_cache(&member.get_id().into()) {
+                Some(LuaTypeCache::DocType(LuaType::DocFunction(func))) => {
+                    func.is_method(semantic_model, Some(&self_type))
+                }
+                _ => false,
+            };
+            if !is_method {
+                continue;
+            }
+
+            let is_defined = semantic_model
+                .get_member_info_with_key(&self_type, key.clone(), true)
+                .unwrap_or_default()
+                .iter()
+                .any(|info| info.feature.is_some_and(|feature| !feature.is_field_decl()));
+            if !is_defined {
+                result.push((super_id.clone(), key.clone()));
+            }
+        }
+    }
+
+    result
+}
@@ -0,0 +1,162 @@
+use emmylua_parser::{LuaAstNode, LuaBlock, LuaCallExprStat, LuaExpr, LuaStat};
+use rowan::TextRange;
+
+use crate::{DiagnosticCode, FlowTree, LuaType, SemanticModel};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct UnreachableCodeChecker;
+
+impl Checker for UnreachableCodeChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::UnreachableCode];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let db = semantic_model.get_db();
+        let Some(flow_tree) = db
+            .get_flow_index()
+            .get_flow_tree(&semantic_model.get_file_id())
+        else {
+            return;
+        };
+
+        let root = semantic_model.get_root().clone();
+        let mut reported_ranges: Vec<TextRange> = Vec::new();
+        for block in root.descendants::<LuaBlock>() {
+            let block_range = block.get_range();
+            if reported_ranges
+                .iter()
+                .any(|range| range.contains_range(block_range))
+            {
+                continue;
+            }
+
+            for range in check_block(semantic_model, flow_tree, block) {
+                context.add_diagnostic(
+                    DiagnosticCode::UnreachableCode,
+                    range,
+                    t!("Unreachable code").to_string(),
+                    None,
+                );
+                reported_ranges.push(range);
+            }
+        }
+    }
+}
+
+fn check_block(
+    semantic_model: &SemanticModel,
+    flow_tree: &FlowTree,
+    block: LuaBlock,
+) -> Vec<TextRange> {
+    let mut unreachable_ranges = Vec::new();
+    let mut unreachable_start: Option<TextRange> = None;
+    let mut unreachable_end: Option<TextRange> = None;
+    // set after a call to a function that never returns
+    let mut terminated = false;
+    for stat in block.get_stats() {
+        if matches!(stat, LuaStat::EmptyStat(_)) {
+            continue;
+        }
+
+        let reachable = if let LuaStat::LabelStat(_) = &stat {
+            // a label may be reached by goto even after the flow has ended
+            terminated = false;
+            is_stat_reachable(flow_tree, &stat)
+        } else {
+            !terminated && is_stat_reachable(flow_tree, &stat)
+        };
+
+        if reachable {
+            if let (Some(start), Some(end)) = (unreachable_start.take(), unreachable_end.take()) {
+                unreachable_ranges.push(TextRange::new(start.start(), end.end()));
+            }
+
+            if is_never_return_stat(semantic_model, &stat) {
+                terminated = true;
+            }
+        } else {
+            if unreachable_start.is_none() {
+                unreachable_start = Some(stat.get_range());
+            }
+            unreachable_end = Some(stat.get_range());
+        }
+    }
+
+    if let (Some(start), Some(end)) = (unreachable_start, unreachable_end) {
+        unreachable_ranges.push(TextRange::new(start.start(), end.end()));
+    }
+
+    unreachable_ranges
+}
+
+fn is_stat_reachable(flow_tree: &FlowTree, stat: &LuaStat) -> bool {
+    match flow_tree.get_flow_id(stat.get_syntax_id()) {
+        Some(flow_id) => flow_tree.is_reachable(flow_id),
+        None => true,
+    }
+}
+
+/// The flow tree is built before call types are resolved, so calls to functions
+/// annotated with `---@return never` are checked here.
+fn is_never_return_stat(semantic_model: &SemanticModel, stat: &LuaStat) -> bool {
+    match stat {
+        LuaStat::CallExprStat(call_expr_stat) => {
+            is_never_return_call(semantic_model, call_expr_stat)
+        }
+        LuaStat::DoStat(do_stat) => do_stat
+            .get_block()
+            .is_some_and(|block| is_never_return_block(semantic_model, &block)),
+        LuaStat::IfStat(if_stat) => {
+            if if_stat.get_else_clause().is_none() {
+                return false;
+            }
+
+            let mut blocks = vec![if_stat.get_block()];
+            blocks.extend(
+                if_stat
+                    .get_else_if_clause_list()
+                    .map(|clause| clause.get_block()),
+            );
+            blocks.push(
+                if_stat
+                    .get_else_clause()
+                    .and_then(|clause| clause.get_block()),
+            );
+            blocks.iter().all(|block| {
+                block
+                    .as_ref()
+                    .is_some_and(|block| is_never_return_block(semantic_model, block))
+            })
+        }
+        _ => false,
+    }
+}
+
+fn is_never_return_block(semantic_model: &SemanticModel, block: &LuaBlock) -> bool {
+    block.get_stats().any(|stat| match &stat {
+        LuaStat::ReturnStat(_) | LuaStat::BreakStat(_) | LuaStat::GotoStat(_) => true,
+        LuaStat::CallExprStat(call_expr_stat)
+            if call_expr_stat
+                .get_call_expr()
+                .is_some_and(|call_expr| call_expr.is_error()) =>
+        {
+            true
+        }
+        _ => is_never_return_stat(semantic_model, &stat),
+    })
+}
+
+fn is_never_return_call(semantic_model: &SemanticModel, call_expr_stat: &LuaCallExprStat) -> bool {
+    let Some(call_expr) = call_expr_stat.get_call_expr() else {
+        return false;
+    };
+
+    if call_expr.is_error() {
+        return false;
+    }
+
+    matches!(
+        semantic_model.infer_expr(LuaExpr::CallExpr(call_expr)),
+        Ok(LuaType::Never)
+    )
+}
@@ -71,13 +71,13 @@ impl Checker for UnusedChecker {
     }
 }
 
-enum UnusedCheckResult {
+pub(crate) enum UnusedCheckResult {
     Unused(TextRange),
     AssignedButNotRead(TextRange),
     UnusedSelf(TextRange),
 }
 
-fn get_unused_check_result(
+pub(crate) fn get_unused_check_result(
     ref_index: &LuaReferenceIndex,
     decl: &LuaDecl,
     root: &LuaChunk,
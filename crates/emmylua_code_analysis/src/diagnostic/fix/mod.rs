@@ -0,0 +1,3 @@
+mod organize_requires;
+
+pub use organize_requires::organize_requires;
@@ -0,0 +1,224 @@
+use emmylua_parser::{
+    LuaAstNode, LuaBlock, LuaComment, LuaExpr, LuaLiteralToken, LuaLocalStat, LuaSyntaxKind,
+    LuaTokenKind,
+};
+use rowan::TextRange;
+
+use crate::{EmmyrcRequireGroup, LuaDeclId, SemanticModel};
+
+use super::super::checker::{UnusedCheckResult, get_unused_check_result};
+
+struct RequireItem {
+    /// The statement with its attached comments
+    range: TextRange,
+    decl_id: LuaDeclId,
+    name: String,
+    module_path: String,
+    value_text: String,
+    group: usize,
+}
+
+/// Sort the top-level `local x = require("...")` block into the configured groups, remove the
+/// requires whose local is never used and merge the ones loading the same module. The edits
+/// don't overlap, `None` means the requires are already organized
+pub fn organize_requires(semantic_model: &SemanticModel) -> Option<Vec<(TextRange, String)>> {
+    let root = semantic_model.get_root();
+    let block = root.get_block()?;
+    let mut items = collect_require_items(semantic_model, &block)?;
+    let document = semantic_model.get_document();
+    let block_range = TextRange::new(items.first()?.range.start(), items.last()?.range.end());
+
+    let file_id = semantic_model.get_file_id();
+    let db = semantic_model.get_db();
+    let decl_tree = db.get_decl_index().get_decl_tree(&file_id)?;
+    let ref_index = db.get_reference_index();
+    let mut edits = Vec::new();
+
+    items.retain(|item| {
+        let Some(decl) = decl_tree.get_decl(&item.decl_id) else {
+            return true;
+        };
+        item.name.starts_with('_')
+            || !matches!(
+                get_unused_check_result(ref_index, decl, root),
+                Err(UnusedCheckResult::Unused(_))
+            )
+    });
+
+    let mut kept: Vec<RequireItem> = Vec::new();
+    for item in items {
+        let Some(first) = kept.iter().find(|first| {
+            first.module_path == item.module_path && first.value_text == item.value_text
+        }) else {
+            kept.push(item);
+            continue;
+        };
+        if first.name == item.name {
+            continue;
+        }
+
+        // the uses of the duplicate switch to the first local when it is visible at each of them
+        let cells = ref_index
+            .get_decl_references(&file_id, &item.decl_id)
+            .map(|decl_ref| decl_ref.cells.clone())
+            .unwrap_or_default();
+        let can_merge = cells.iter().all(|cell| {
+            decl_tree
+                .find_local_decl(&first.name, cell.range.start())
+                .is_some_and(|decl| decl.get_id() == first.decl_id)
+        });
+        if !can_merge {
+            kept.push(item);
+            continue;
+        }
+        for cell in cells {
+            edits.push((cell.range, first.name.clone()));
+        }
+    }
+
+    kept.sort_by(|a, b| {
+        (a.group, &a.module_path, &a.name).cmp(&(b.group, &b.module_path, &b.name))
+    });
+    let indent = get_line_indent(semantic_model, block_range)?;
+    let mut new_text = String::new();
+    for (i, item) in kept.iter().enumerate() {
+        if i > 0 {
+            new_text.push('\n');
+            if kept[i - 1].group != item.group {
+                new_text.push('\n');
+            }
+            new_text.push_str(&indent);
+        }
+        new_text.push_str(document.get_text_slice(item.range));
+    }
+
+    let mut replace_range = block_range;
+    if new_text.is_empty() {
+        // nothing is left of the block, its lines go with it
+        let mut token = root
+            .syntax()
+            .token_at_offset(block_range.end())
+            .right_biased();
+        while let Some(next) = token {
+            if next.kind() != LuaTokenKind::TkWhitespace.into()
+                && next.kind() != LuaTokenKind::TkEndOfLine.into()
+            {
+                break;
+            }
+            replace_range = replace_range.cover(next.text_range());
+            token = next.next_token();
+        }
+    }
+    if edits.is_empty() && document.get_text_slice(replace_range) == new_text {
+        return None;
+    }
+
+    edits.push((replace_range, new_text));
+    edits.sort_by_key(|(range, _)| range.start());
+    Some(edits)
+}
+
+fn collect_require_items(
+    semantic_model: &SemanticModel,
+    block: &LuaBlock,
+) -> Option<Vec<RequireItem>> {
+    let mut items: Vec<RequireItem> = Vec::new();
+    for node in block.syntax().children() {
+        if let Some(comment) = LuaComment::cast(node.clone()) {
+            match comment.get_owner() {
+                Some(owner) if owner.syntax().kind() == LuaSyntaxKind::LocalStat.into() => {
+                    continue;
+                }
+                _ if items.is_empty() => continue,
+                _ => break,
+            }
+        }
+
+        match LuaLocalStat::cast(node).and_then(|stat| get_require_item(semantic_model, stat)) {
+            Some(item) => items.push(item),
+            None if items.is_empty() => continue,
+            None => break,
+        }
+    }
+
+    if items.is_empty() { None } else { Some(items) }
+}
+
+fn get_require_item(semantic_model: &SemanticModel, stat: LuaLocalStat) -> Option<RequireItem> {
+    let mut names = stat.get_local_name_list();
+    let local_name = names.next()?;
+    let mut values = stat.get_value_exprs();
+    let value = values.next()?;
+    if names.next().is_some() || values.next().is_some() || local_name.get_attrib().is_some() {
+        return None;
+    }
+
+    // `require("a")` or a member of it like `require("a").b`
+    let mut expr = value.clone();
+    let call_expr = loop {
+        match expr {
+            LuaExpr::CallExpr(call_expr) if call_expr.is_require() => break call_expr,
+            LuaExpr::IndexExpr(index_expr) => expr = index_expr.get_prefix_expr()?,
+            _ => return None,
+        }
+    };
+    let LuaExpr::LiteralExpr(literal) = call_expr.get_args_list()?.get_args().next()? else {
+        return None;
+    };
+    let LuaLiteralToken::String(module_path) = literal.get_literal()? else {
+        return None;
+    };
+    let module_path = module_path.get_value();
+
+    let mut range = stat.get_range();
+    let parent = stat.syntax().parent()?;
+    for comment in parent.children().filter_map(LuaComment::cast) {
+        if comment
+            .get_owner()
+            .is_some_and(|owner| owner.syntax() == stat.syntax())
+        {
+            range = range.cover(comment.get_range());
+        }
+    }
+
+    let name = local_name.get_name_token()?.get_name_text().to_string();
+    let decl_id = LuaDeclId::new(semantic_model.get_file_id(), local_name.get_position());
+    let document = semantic_model.get_document();
+    Some(RequireItem {
+        range,
+        decl_id,
+        name,
+        value_text: document.get_text_slice(value.get_range()).to_string(),
+        group: get_require_group(semantic_model, &module_path),
+        module_path,
+    })
+}
+
+fn get_require_group(semantic_model: &SemanticModel, module_path: &str) -> usize {
+    let group = match semantic_model
+        .get_db()
+        .get_module_index()
+        .find_module(module_path)
+    {
+        Some(module_info) if module_info.workspace_id.is_std() => EmmyrcRequireGroup::Std,
+        Some(module_info) if module_info.workspace_id.is_library() => EmmyrcRequireGroup::Library,
+        _ => EmmyrcRequireGroup::Workspace,
+    };
+    let groups = &semantic_model.get_emmyrc().code_action.require_groups;
+    groups
+        .iter()
+        .position(|it| *it == group)
+        .unwrap_or(groups.len())
+}
+
+fn get_line_indent(semantic_model: &SemanticModel, range: TextRange) -> Option<String> {
+    let document = semantic_model.get_document();
+    let line = document.get_line(range.start())?;
+    let line_start = document.get_line_range(line)?.start();
+    let text = document.get_text_slice(TextRange::new(line_start, range.start()));
+    Some(
+        text.chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect(),
+    )
+}
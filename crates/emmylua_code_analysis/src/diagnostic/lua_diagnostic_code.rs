@@ -113,6 +113,26 @@ pub enum DiagnosticCode {
     AttributeMissingParameter,
     /// attribute-redundant-parameter
     AttributeRedundantParameter,
+    /// undefined-goto
+    UndefinedGoto,
+    /// goto-into-local-scope
+    GotoIntoLocalScope,
+    /// unimplemented-member
+    UnimplementedMember,
+    /// invalid-format-string
+    InvalidFormatString,
+    /// format-argument-mismatch
+    FormatArgumentMismatch,
+    /// invalid-pattern
+    InvalidPattern,
+    /// non-exhaustive-dispatch
+    NonExhaustiveDispatch,
+    /// circle-require
+    CircleRequire,
+    /// incompatible-override
+    IncompatibleOverride,
+    /// nothing-to-override
+    NothingToOverride,
 
     #[serde(other)]
     None,
@@ -141,6 +161,9 @@ pub fn get_default_severity(code: DiagnosticCode) -> DiagnosticSeverity {
         DiagnosticCode::DuplicateRequire => DiagnosticSeverity::HINT,
         DiagnosticCode::IterVariableReassign => DiagnosticSeverity::ERROR,
         DiagnosticCode::PreferredLocalAlias => DiagnosticSeverity::HINT,
+        DiagnosticCode::UndefinedGoto => DiagnosticSeverity::ERROR,
+        DiagnosticCode::GotoIntoLocalScope => DiagnosticSeverity::ERROR,
+        DiagnosticCode::NothingToOverride => DiagnosticSeverity::ERROR,
         _ => DiagnosticSeverity::WARNING,
     }
 }
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod test {
+    use lsp_types::NumberOrString;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{DiagnosticCode, FileId, VirtualWorkspace};
+
+    fn get_messages(ws: &VirtualWorkspace, file_id: FileId) -> Vec<String> {
+        let code = Some(NumberOrString::String(
+            DiagnosticCode::CircleRequire.get_name().to_string(),
+        ));
+        ws.analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|diagnostic| diagnostic.code == code)
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_circle_require() {
+        let mut ws = VirtualWorkspace::new();
+        let file_ids = ws.def_files(vec![
+            (
+                "a.lua",
+                r#"
+                local b = require("b")
+                return {}
+                "#,
+            ),
+            (
+                "b.lua",
+                r#"
+                local c = require("c")
+                return {}
+                "#,
+            ),
+            (
+                "c.lua",
+                r#"
+                local a = require("a")
+                local d = require("d")
+                return {}
+                "#,
+            ),
+            ("d.lua", "return {}"),
+        ]);
+
+        assert_eq!(
+            get_messages(&ws, file_ids[0]),
+            vec!["Circular require: a -> b -> c -> a"]
+        );
+        assert_eq!(
+            get_messages(&ws, file_ids[2]),
+            vec!["Circular require: c -> a -> b -> c"]
+        );
+        assert!(get_messages(&ws, file_ids[3]).is_empty());
+
+        let db = ws.analysis.compilation.get_db();
+        assert_eq!(
+            db.get_file_dependencies_index().get_require_cycles(),
+            vec![vec![file_ids[0], file_ids[1], file_ids[2]]]
+        );
+    }
+
+    #[test]
+    fn test_deferred_require() {
+        let mut ws = VirtualWorkspace::new();
+        let file_ids = ws.def_files(vec![
+            (
+                "a.lua",
+                r#"
+                local b = require("b")
+                return {}
+                "#,
+            ),
+            (
+                "b.lua",
+                r#"
+                local M = {}
+                function M.get_a()
+                    return require("a")
+                end
+                return M
+                "#,
+            ),
+        ]);
+
+        assert!(get_messages(&ws, file_ids[0]).is_empty());
+        assert!(get_messages(&ws, file_ids[1]).is_empty());
+    }
+}
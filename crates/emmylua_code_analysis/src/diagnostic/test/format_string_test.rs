@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_invalid_conversion() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::InvalidFormatString,
+            r#"
+            local s = string.format("%-5d%% %.3f %10s %q %x", 1, 2.5, "a", "b", 255)
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::InvalidFormatString,
+            r#"
+            local s = string.format("%y", 1)
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::InvalidFormatString,
+            r#"
+            local s = string.format("%100d", 1)
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::InvalidFormatString,
+            r#"
+            local s = ("progress: 50%"):format()
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_argument_count() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            local s = string.format("%s and %s", "a")
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            local s = ("%d"):format(1, 2)
+            "#
+        ));
+        // the number of values is unknown
+        assert!(ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            local function f(...)
+                return string.format("%s %s %s", ...)
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_argument_type() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            local s = string.format("%d", "10")
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            local s = string.format("%x", 1.5)
+            "#
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            ---@param n integer?
+            ---@param x number
+            local function f(n, x)
+                return string.format("%d %5.1f %s %q", n, x, n, true)
+            end
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            local s = string.format("%s", {})
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            ---@class Point
+            local Point = {}
+
+            ---@type Point
+            local p
+            local s = string.format("%s", p)
+            "#
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            ---@class Vector
+            local Vector = {}
+
+            function Vector:__tostring()
+                return "vector"
+            end
+
+            ---@type Vector
+            local v
+            local s = string.format("%s", v)
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            local s = string.format("%q", function() end)
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_format_string_attribute() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@class Logger
+            Logger = {}
+
+            ---@param level string
+            ---@param fmt string
+            ---@param ... any
+            ---@[format_string(2)]
+            function Logger:log(level, fmt, ...)
+            end
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            Logger:log("info", "%s took %d ms", "load", 12)
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            Logger:log("info", "%s took %d ms", "load")
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::FormatArgumentMismatch,
+            r#"
+            Logger.log(Logger, "info", "%d", "load")
+            "#
+        ));
+    }
+}
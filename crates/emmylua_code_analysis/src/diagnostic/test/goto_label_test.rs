@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_redefined_label() {
+        let mut ws = VirtualWorkspace::new();
+        assert!(!ws.check_code_for(
+            DiagnosticCode::RedefinedLabel,
+            r#"
+            ::a::
+            ::a::
+            "#
+        ));
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::RedefinedLabel,
+            r#"
+            ::b::
+            do
+                ::b::
+            end
+            "#
+        ));
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::RedefinedLabel,
+            r#"
+            do
+                ::c::
+            end
+            do
+                ::c::
+            end
+            local f = function()
+                ::c::
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_undefined_goto() {
+        let mut ws = VirtualWorkspace::new();
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UndefinedGoto,
+            r#"
+            goto missing
+            "#
+        ));
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UndefinedGoto,
+            r#"
+            do
+                ::inner::
+            end
+            goto inner
+            "#
+        ));
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UndefinedGoto,
+            r#"
+            ::outer::
+            local f = function()
+                goto outer
+            end
+            "#
+        ));
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::UndefinedGoto,
+            r#"
+            for i = 1, 10 do
+                if i % 2 == 0 then
+                    goto continue
+                end
+                print(i)
+                ::continue::
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_goto_into_local_scope() {
+        let mut ws = VirtualWorkspace::new();
+        assert!(!ws.check_code_for(
+            DiagnosticCode::GotoIntoLocalScope,
+            r#"
+            goto skip
+            local a = 1
+            ::skip::
+            print(a)
+            "#
+        ));
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::GotoIntoLocalScope,
+            r#"
+            for i = 1, 10 do
+                if i % 2 == 0 then
+                    goto continue
+                end
+                local a = i
+                print(a)
+                ::continue::
+            end
+            "#
+        ));
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::GotoIntoLocalScope,
+            r#"
+            local a = 1
+            ::top::
+            a = a + 1
+            if a < 10 then
+                goto top
+            end
+            "#
+        ));
+    }
+}
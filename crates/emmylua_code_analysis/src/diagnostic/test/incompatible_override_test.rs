@@ -0,0 +1,176 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_param_types() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class Shape
+            local Shape = {}
+
+            ---@param scale number
+            function Shape:resize(scale) end
+            "#,
+        );
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::IncompatibleOverride,
+            r#"
+            ---@class Circle: Shape
+            local Circle = {}
+
+            ---@param scale integer
+            function Circle:resize(scale) end
+            "#
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::IncompatibleOverride,
+            r#"
+            ---@class Square: Shape
+            local Square = {}
+
+            ---@param scale number|string
+            function Square:resize(scale) end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_param_count() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class Base
+            local Base = {}
+
+            ---@param a string
+            function Base:run(a) end
+
+            ---@param ... any
+            function Base:log(...) end
+            "#,
+        );
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::IncompatibleOverride,
+            r#"
+            ---@class Derived: Base
+            local Derived = {}
+
+            ---@param a string
+            ---@param b integer
+            function Derived:run(a, b) end
+            "#
+        ));
+        assert!(ws.check_code_for(
+            DiagnosticCode::IncompatibleOverride,
+            r#"
+            ---@class Derived2: Base
+            local Derived2 = {}
+
+            ---@param a string
+            ---@param b integer?
+            function Derived2:run(a, b) end
+
+            ---@param a string
+            ---@param b integer
+            function Derived2:log(a, b) end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_return_types() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class Reader
+            local Reader = {}
+
+            ---@return string?
+            function Reader:read() end
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::IncompatibleOverride,
+            r#"
+            ---@class FileReader: Reader
+            local FileReader = {}
+
+            ---@return string
+            function FileReader:read() return "" end
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::IncompatibleOverride,
+            r#"
+            ---@class NumberReader: Reader
+            local NumberReader = {}
+
+            ---@return number
+            function NumberReader:read() return 1 end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_async() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class Task
+            local Task = {}
+
+            function Task:start() end
+            "#,
+        );
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::IncompatibleOverride,
+            r#"
+            ---@class NetTask: Task
+            local NetTask = {}
+
+            ---@async
+            function NetTask:start() end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_override_attribute() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@class Animal
+            local Animal = {}
+
+            function Animal:speak() end
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::NothingToOverride,
+            r#"
+            ---@class Dog: Animal
+            local Dog = {}
+
+            ---@[override]
+            function Dog:speak() end
+            "#
+        ));
+        assert!(!ws.check_code_for(
+            DiagnosticCode::NothingToOverride,
+            r#"
+            ---@class Cat: Animal
+            local Cat = {}
+
+            ---@[override]
+            function Cat:speek() end
+            "#
+        ));
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_valid_pattern() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::InvalidPattern,
+            r#"
+            local s = "key = value"
+            local k, v = s:match("^(%w+)%s*=%s*(.-)$")
+            local a = string.find(s, "[]%]]+")
+            local b = string.gsub(s, "%b()", "")
+            local c = string.match(s, "%f[%w](%w+)%1()")
+            for w in string.gmatch(s, "[^%s]+") do
+            end
+            "#
+        ));
+        // plain search does not use the pattern
+        assert!(ws.check_code_for(
+            DiagnosticCode::InvalidPattern,
+            r#"
+            local a = string.find("a(b", "(", 1, true)
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        for pattern in [
+            "[a-z", "(%w+", "%w+)", "100%", "%b(", "%fx", "(%w)%2", "(%w%1)",
+        ] {
+            assert!(
+                !ws.check_code_for(
+                    DiagnosticCode::InvalidPattern,
+                    &format!(r#"local a = string.match("text", "{}")"#, pattern)
+                ),
+                "{}",
+                pattern
+            );
+        }
+        assert!(!ws.check_code_for(
+            DiagnosticCode::InvalidPattern,
+            r#"
+            local a = ("a(b"):find("(", 1, false)
+            "#
+        ));
+    }
+}
@@ -3,19 +3,25 @@ mod assign_type_mismatch_test;
 mod await_in_sync_test;
 mod cast_type_mismatch_test;
 mod check_return_count_test;
+mod circle_require_test;
 mod code_style;
 mod disable_line_test;
 mod duplicate_field_test;
 mod duplicate_index_test;
 mod duplicate_require_test;
 mod enum_value_mismatch_test;
+mod format_string_test;
 mod generic_constraint_mismatch_test;
 mod global_in_non_module_test;
+mod goto_label_test;
+mod incompatible_override_test;
 mod incomplete_signature_doc_test;
 mod inject_field_test;
+mod invalid_pattern_test;
 mod missing_fields_test;
 mod missing_parameter_test;
 mod need_check_nil_test;
+mod non_exhaustive_dispatch_test;
 mod param_type_check_test;
 mod readonly_check;
 mod redefined_local_test;
@@ -27,7 +33,9 @@ mod unbalanced_assignments_test;
 mod undefined_doc_param_test;
 mod undefined_field_test;
 mod undefined_global_test;
+mod unimplemented_member_test;
 mod unknown_doc_tag;
 mod unnecessary_assert_test;
 mod unnecessary_if_test;
+mod unreachable_code_test;
 mod unused_test;
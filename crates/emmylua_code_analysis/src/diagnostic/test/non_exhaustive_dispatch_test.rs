@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_enum_dispatch() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+            ---@enum Color
+            Color = { Red = 1, Green = 2, Blue = 3, Alpha = 4 }
+            "#,
+        );
+
+        assert_eq!(
+            ws.get_diagnostic_data(
+                DiagnosticCode::NonExhaustiveDispatch,
+                r#"
+                ---@param c Color
+                local function f(c)
+                    if c == Color.Red then
+                    elseif c == Color.Green then
+                    else
+                        error("unknown color")
+                    end
+                end
+                "#
+            ),
+            Some(json!({ "var": "c", "cases": ["Color.Blue", "Color.Alpha"] }))
+        );
+        assert!(ws.check_code_for(
+            DiagnosticCode::NonExhaustiveDispatch,
+            r#"
+            ---@param c Color
+            local function f(c)
+                if c == Color.Red or c == Color.Alpha then
+                elseif (c == Color.Green) or Color.Blue == c then
+                else
+                    error("unknown color")
+                end
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_literal_dispatch() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert_eq!(
+            ws.get_diagnostic_data(
+                DiagnosticCode::NonExhaustiveDispatch,
+                r#"
+                ---@param mode "read"|"write"|"append"
+                local function f(mode)
+                    if mode == "read" then
+                    elseif "append" == mode then
+                    else
+                        error("unknown mode")
+                    end
+                end
+                "#
+            ),
+            Some(json!({ "var": "mode", "cases": ["\"write\""] }))
+        );
+        assert_eq!(
+            ws.get_diagnostic_data(
+                DiagnosticCode::NonExhaustiveDispatch,
+                r#"
+                ---@enum (key) Op
+                local Op = { add = 1, sub = 2 }
+
+                ---@param op Op
+                local function f(op)
+                    if op == "add" then
+                    else
+                        error("unknown op")
+                    end
+                end
+                "#
+            ),
+            Some(json!({ "var": "op", "cases": ["\"sub\""] }))
+        );
+    }
+
+    #[test]
+    fn test_exhaustive_attribute() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        assert_eq!(
+            ws.get_diagnostic_data(
+                DiagnosticCode::NonExhaustiveDispatch,
+                r#"
+                ---@param n 1|2|3
+                local function f(n)
+                    ---@[exhaustive]
+                    if n == 1 then
+                    elseif n == 2 then
+                    end
+                end
+                "#
+            ),
+            Some(json!({ "var": "n", "cases": ["3"] }))
+        );
+        assert!(ws.check_code_for(
+            DiagnosticCode::NonExhaustiveDispatch,
+            r#"
+            ---@param n 1|2|3
+            local function f(n)
+                ---@[exhaustive]
+                if n == 1 or n == 3 then
+                elseif n == 2 then
+                end
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_not_dispatch() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        // without the `else` calling `error` or the attribute the chain is not checked
+        assert!(ws.check_code_for(
+            DiagnosticCode::NonExhaustiveDispatch,
+            r#"
+            ---@param mode "read"|"write"
+            local function f(mode)
+                if mode == "read" then
+                end
+            end
+            "#
+        ));
+        // the values of a string are not finite
+        assert!(ws.check_code_for(
+            DiagnosticCode::NonExhaustiveDispatch,
+            r#"
+            ---@param mode string
+            local function f(mode)
+                if mode == "read" then
+                else
+                    error("unknown mode")
+                end
+            end
+            "#
+        ));
+        // a condition that is not a comparison of the same variable
+        assert!(ws.check_code_for(
+            DiagnosticCode::NonExhaustiveDispatch,
+            r#"
+            ---@param mode "read"|"write"
+            ---@param other "read"|"write"
+            local function f(mode, other)
+                if mode == "read" then
+                elseif other == "write" then
+                else
+                    error("unknown mode")
+                end
+            end
+            "#
+        ));
+    }
+}
@@ -2,6 +2,8 @@
 mod test {
     use std::{ops::Deref, sync::Arc};
 
+    use serde_json::json;
+
     use crate::{DiagnosticCode, VirtualWorkspace};
 
     #[test]
@@ -736,6 +738,27 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_similar_names() {
+        let mut ws = VirtualWorkspace::new();
+        assert_eq!(
+            ws.get_diagnostic_data(
+                DiagnosticCode::UndefinedField,
+                r#"
+                ---@class Point
+                ---@field x number
+                ---@field y number
+                ---@field name string
+
+                ---@type Point
+                local p
+                print(p.nmae)
+                "#
+            ),
+            Some(json!(["name"]))
+        );
+    }
+
     // #[test]
     // fn test_export() {
     //     let mut ws = VirtualWorkspace::new();
@@ -1,5 +1,7 @@
 #[cfg(test)]
 mod test {
+    use serde_json::json;
+
     use crate::{DiagnosticCode, VirtualWorkspace};
 
     #[test]
@@ -20,4 +22,39 @@ mod test {
             "#
         ));
     }
+
+    #[test]
+    fn test_similar_names() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+        assert_eq!(
+            ws.get_diagnostic_data(
+                DiagnosticCode::UndefinedGlobal,
+                r#"
+                prnt("hello")
+                "#
+            ),
+            Some(json!(["print"]))
+        );
+        assert_eq!(
+            ws.get_diagnostic_data(
+                DiagnosticCode::UndefinedGlobal,
+                r#"
+                local counter = 1
+                local function f()
+                    return countr + 1
+                end
+                "#
+            ),
+            Some(json!(["counter"]))
+        );
+        assert_eq!(
+            ws.get_diagnostic_data(
+                DiagnosticCode::UndefinedGlobal,
+                r#"
+                return qwertyuiop
+                "#
+            ),
+            Some(json!([]))
+        );
+    }
 }
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_unimplemented_member() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class Shape
+            ---@field name string
+            ---@field area fun(self: Shape): number
+            ---@field draw fun(self: Shape, x: number, y: number)
+            "#,
+        );
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnimplementedMember,
+            r#"
+            ---@class Circle: Shape
+            local Circle = {}
+
+            function Circle:area()
+                return 0
+            end
+            "#
+        ));
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnimplementedMember,
+            r#"
+            ---@class Square: Shape
+            local Square = {}
+
+            function Square:area()
+                return 0
+            end
+
+            function Square:draw(x, y)
+            end
+            "#
+        ));
+
+        // a field holding a function is implemented by the table too
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnimplementedMember,
+            r#"
+            ---@class Rect: Shape
+            local Rect = {
+                area = function(self) return 0 end,
+                draw = function(self, x, y) end,
+            }
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_inherited_implementation() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class Drawable
+            ---@field draw fun(self: Drawable)
+
+            ---@class Widget: Drawable
+            local Widget = {}
+
+            function Widget:draw()
+            end
+            "#,
+        );
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnimplementedMember,
+            r#"
+            ---@class Button: Widget
+            local Button = {}
+            "#
+        ));
+
+        // a class written in comments alone declares the methods as well
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnimplementedMember,
+            r#"
+            ---@class Canvas: Drawable
+            "#
+        ));
+
+        // a callback field is not a method
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnimplementedMember,
+            r#"
+            ---@class Options
+            ---@field on_click fun(x: number)
+
+            ---@class ButtonOptions: Options
+            local ButtonOptions = {}
+            "#
+        ));
+    }
+}
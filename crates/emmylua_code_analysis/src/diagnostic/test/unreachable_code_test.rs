@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_after_error() {
+        let mut ws = VirtualWorkspace::new();
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnreachableCode,
+            r#"
+            local function f()
+                error("fail")
+                print(1)
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_after_break_and_goto() {
+        let mut ws = VirtualWorkspace::new();
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnreachableCode,
+            r#"
+            for i = 1, 10 do
+                break
+                print(i)
+            end
+            "#
+        ));
+
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnreachableCode,
+            r#"
+            goto done
+            print(1)
+            ::done::
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_all_branches_return() {
+        let mut ws = VirtualWorkspace::new();
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnreachableCode,
+            r#"
+            local function f(a)
+                if a then
+                    return 1
+                else
+                    error("fail")
+                end
+                print(a)
+            end
+            "#
+        ));
+
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnreachableCode,
+            r#"
+            local function f(a)
+                if a then
+                    return 1
+                elseif a == false then
+                    return 2
+                end
+                print(a)
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_never_return() {
+        let mut ws = VirtualWorkspace::new();
+        assert!(!ws.check_code_for(
+            DiagnosticCode::UnreachableCode,
+            r#"
+            ---@return never
+            local function fail()
+                error("fail")
+            end
+
+            local function f()
+                fail()
+                print(1)
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_label_reached_by_goto() {
+        let mut ws = VirtualWorkspace::new();
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnreachableCode,
+            r#"
+            for i = 1, 10 do
+                if i == 5 then
+                    goto continue
+                end
+                print(i)
+                ::continue::
+            end
+
+            local a = 1
+            goto skip
+            ::back::
+            print(a)
+            ::skip::
+            if a then
+                goto back
+            end
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_reachable_code() {
+        let mut ws = VirtualWorkspace::new();
+        assert!(ws.check_code_for(
+            DiagnosticCode::UnreachableCode,
+            r#"
+            local function f(a)
+                if not a then
+                    error("fail")
+                end
+                while a do
+                    if a > 1 then
+                        break
+                    end
+                    a = a + 1
+                end
+                return a
+            end
+            "#
+        ));
+    }
+}
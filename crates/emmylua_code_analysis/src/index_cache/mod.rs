@@ -0,0 +1,182 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{DbIndex, FileId};
+
+const MAGIC: &[u8; 8] = b"EMMYIDX3";
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+const HEADER_LEN: usize = 16;
+
+/// On-disk cache of the analyzed std and library files, with one entry for each file, so the
+/// unchanged files are restored instead of analyzed again.
+///
+/// The entry of a file is stored under the hash of its path and holds what the file added to
+/// the indexes. It is used when the content of the file and of the files it depends on did
+/// not change, and when the config and the loaded files are the same.
+#[derive(Debug, Clone)]
+pub struct LuaIndexCache {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LuaIndexCacheEntry {
+    /// The key of the config and of the files loaded with the file
+    pub env_key: u64,
+    pub file_id: FileId,
+    pub content_hash: u64,
+    /// The paths and content hashes of the files the file depends on
+    pub dependencies: Vec<(PathBuf, u64)>,
+    pub index: DbIndex,
+}
+
+impl LuaIndexCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The index cache directory of the current user for the workspace with the given roots
+    pub fn default_dir(workspace_roots: &[PathBuf]) -> Option<PathBuf> {
+        let mut roots = workspace_roots.to_vec();
+        roots.sort();
+        let mut hasher = DefaultHasher::new();
+        roots.hash(&mut hasher);
+        Some(
+            dirs::cache_dir()?
+                .join("emmylua_ls")
+                .join("index")
+                .join(format!("{:016x}", hasher.finish())),
+        )
+    }
+
+    pub fn load(&self, path: &Path) -> Option<LuaIndexCacheEntry> {
+        let data = fs::read(self.get_cache_path(path)).ok()?;
+        if data.len() < HEADER_LEN || &data[..MAGIC.len()] != MAGIC {
+            return None;
+        }
+
+        let checksum = u64::from_le_bytes(data[8..16].try_into().ok()?);
+        let payload = &data[HEADER_LEN..];
+        if hash_bytes(payload) != checksum {
+            log::warn!("index cache of {:?} is corrupted", path);
+            return None;
+        }
+
+        match bincode::deserialize(payload) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                log::warn!("failed to read index cache of {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path, entry: &LuaIndexCacheEntry) -> Option<()> {
+        let payload = match bincode::serialize(entry) {
+            Ok(payload) => payload,
+            Err(e) => {
+                log::warn!("failed to write index cache of {:?}: {}", path, e);
+                return None;
+            }
+        };
+
+        let mut data = Vec::with_capacity(HEADER_LEN + payload.len());
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&hash_bytes(&payload).to_le_bytes());
+        data.extend_from_slice(&payload);
+
+        let cache_path = self.get_cache_path(path);
+        let tmp_path = cache_path.with_extension("tmp");
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&tmp_path, data))
+            .and_then(|_| fs::rename(&tmp_path, &cache_path));
+        if let Err(e) = result {
+            log::warn!("failed to write index cache {:?}: {}", cache_path, e);
+            return None;
+        }
+
+        Some(())
+    }
+
+    // one entry for each file, a changed file replaces its entry
+    fn get_cache_path(&self, path: &Path) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        path.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.idx", hasher.finish()))
+    }
+}
+
+/// The key of the version, the config, the workspaces and the paths of the loaded files,
+/// the entries saved with another key are not used
+pub fn get_index_cache_env_key(db: &DbIndex) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    VERSION.hash(&mut hasher);
+    let config = serde_json::to_value(db.get_emmyrc()).ok()?;
+    hash_json_value(&config, &mut hasher);
+    for workspace in db.get_module_index().get_workspaces() {
+        workspace.root.hash(&mut hasher);
+        workspace.id.hash(&mut hasher);
+    }
+
+    let vfs = db.get_vfs();
+    let mut file_ids = vfs.get_all_file_ids();
+    file_ids.sort_by_key(|file_id| file_id.id);
+    for file_id in file_ids {
+        file_id.hash(&mut hasher);
+        vfs.get_file_path(&file_id).hash(&mut hasher);
+    }
+
+    Some(hasher.finish())
+}
+
+pub fn get_content_hash(text: &str) -> u64 {
+    hash_bytes(text.as_bytes())
+}
+
+// object keys are sorted, the config contains hash maps
+fn hash_json_value(value: &Value, hasher: &mut DefaultHasher) {
+    match value {
+        Value::Null => 0u8.hash(hasher),
+        Value::Bool(b) => {
+            1u8.hash(hasher);
+            b.hash(hasher);
+        }
+        Value::Number(n) => {
+            2u8.hash(hasher);
+            n.to_string().hash(hasher);
+        }
+        Value::String(s) => {
+            3u8.hash(hasher);
+            s.hash(hasher);
+        }
+        Value::Array(values) => {
+            4u8.hash(hasher);
+            values.len().hash(hasher);
+            for value in values {
+                hash_json_value(value, hasher);
+            }
+        }
+        Value::Object(map) => {
+            5u8.hash(hasher);
+            map.len().hash(hasher);
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, value) in entries {
+                key.hash(hasher);
+                hash_json_value(value, hasher);
+            }
+        }
+    }
+}
+
+fn hash_bytes(data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
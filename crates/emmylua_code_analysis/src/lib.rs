@@ -12,6 +12,7 @@ mod compilation;
 mod config;
 mod db_index;
 mod diagnostic;
+mod index_cache;
 mod locale;
 mod profile;
 mod resources;
@@ -24,12 +25,18 @@ pub use config::*;
 pub use db_index::*;
 pub use diagnostic::*;
 pub use emmylua_codestyle::*;
+pub use index_cache::LuaIndexCache;
+use index_cache::{LuaIndexCacheEntry, get_content_hash, get_index_cache_env_key};
 pub use locale::get_locale_code;
-use lsp_types::Uri;
+use lsp_types::{TextDocumentContentChangeEvent, Uri};
 pub use profile::Profile;
 use resources::load_resource_std;
 pub use semantic::*;
-use std::{collections::HashSet, path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
 pub use test_lib::VirtualWorkspace;
 use tokio_util::sync::CancellationToken;
 pub use vfs::*;
@@ -49,6 +56,7 @@ pub struct EmmyLuaAnalysis {
     pub diagnostic: LuaDiagnostic,
     pub emmyrc: Arc<Emmyrc>,
     lib_workspace_counter: u32,
+    index_cache: Option<LuaIndexCache>,
 }
 
 impl EmmyLuaAnalysis {
@@ -59,6 +67,7 @@ impl EmmyLuaAnalysis {
             diagnostic: LuaDiagnostic::new(),
             emmyrc,
             lib_workspace_counter: 2,
+            index_cache: None,
         }
     }
 
@@ -80,7 +89,12 @@ impl EmmyLuaAnalysis {
                 }
             })
             .collect::<Vec<_>>();
-        self.update_files_by_path(files);
+        self.update_files_with_index_cache(files);
+    }
+
+    /// Restore the std and library files from the cache instead of analyzing them again
+    pub fn set_index_cache(&mut self, index_cache: Option<LuaIndexCache>) {
+        self.index_cache = index_cache;
     }
 
     pub fn get_file_id(&self, uri: &Uri) -> Option<FileId> {
@@ -98,7 +112,11 @@ impl EmmyLuaAnalysis {
             .add_workspace_root(root, WorkspaceId::MAIN);
     }
 
-    pub fn add_library_workspace(&mut self, root: PathBuf) {
+    pub fn add_library_workspace(
+        &mut self,
+        root: PathBuf,
+        files: Vec<(PathBuf, Option<String>)>,
+    ) -> Vec<FileId> {
         let id = WorkspaceId {
             id: self.lib_workspace_counter,
         };
@@ -108,6 +126,7 @@ impl EmmyLuaAnalysis {
             .get_db_mut()
             .get_module_index_mut()
             .add_workspace_root(root, id);
+        self.update_files_with_index_cache(files)
     }
 
     pub fn update_file_by_uri(&mut self, uri: &Uri, text: Option<String>) -> Option<FileId> {
@@ -119,13 +138,44 @@ impl EmmyLuaAnalysis {
             .set_file_content(uri, text);
 
         self.compilation.remove_index(vec![file_id]);
-        if !is_removed {
+        if is_removed {
+            self.compilation.update_index(vec![]);
+        } else {
             self.compilation.update_index(vec![file_id]);
         }
 
         Some(file_id)
     }
 
+    /// Apply incremental text changes to an opened file and update its index.
+    ///
+    /// Only the parse is incremental, the block around an edit is reparsed instead of the whole
+    /// text. The index of the file is still rebuilt from the new tree as for a full update. When
+    /// the file is not loaded or a change does not fit its text, the text is kept as it is and
+    /// `None` is returned, the file on disk may not match the text of the editor.
+    pub fn change_file_by_uri(
+        &mut self,
+        uri: &Uri,
+        changes: Vec<TextDocumentContentChangeEvent>,
+    ) -> Option<FileId> {
+        let Some(file_id) = self
+            .compilation
+            .get_db_mut()
+            .get_vfs_mut()
+            .change_file_content(uri, changes)
+        else {
+            log::error!(
+                "{} is out of sync with the client, its changes are ignored",
+                uri.as_str()
+            );
+            return None;
+        };
+
+        self.compilation.remove_index(vec![file_id]);
+        self.compilation.update_index(vec![file_id]);
+        Some(file_id)
+    }
+
     pub fn update_file_by_path(&mut self, path: &PathBuf, text: Option<String>) -> Option<FileId> {
         let uri = file_path_to_uri(path)?;
         self.update_file_by_uri(&uri, text)
@@ -189,6 +239,7 @@ impl EmmyLuaAnalysis {
     pub fn remove_file_by_uri(&mut self, uri: &Uri) -> Option<FileId> {
         if let Some(file_id) = self.compilation.get_db_mut().get_vfs_mut().remove_file(uri) {
             self.compilation.remove_index(vec![file_id]);
+            self.compilation.update_index(vec![]);
             return Some(file_id);
         }
 
@@ -206,6 +257,190 @@ impl EmmyLuaAnalysis {
         self.update_files_by_uri(files)
     }
 
+    /// Load std or library files. When only std and library files are loaded before, the
+    /// unchanged files are restored from the index cache, the others are analyzed and saved to it.
+    fn update_files_with_index_cache(
+        &mut self,
+        files: Vec<(PathBuf, Option<String>)>,
+    ) -> Vec<FileId> {
+        let mut files = files;
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        let Some(index_cache) = self.get_index_cache(&files) else {
+            return self.update_files_by_path(files);
+        };
+
+        let mut file_ids = Vec::new();
+        for (path, text) in files {
+            if let Some(uri) = file_path_to_uri(&path) {
+                let file_id = self
+                    .compilation
+                    .get_db_mut()
+                    .get_vfs_mut()
+                    .set_file_content(&uri, text);
+                file_ids.push(file_id);
+            }
+        }
+        let Some(env_key) = get_index_cache_env_key(self.compilation.get_db()) else {
+            self.compilation.update_index(file_ids.clone());
+            return file_ids;
+        };
+
+        let restored_files = {
+            let _p = Profile::new("restore index cache");
+            let mut entries = HashMap::new();
+            let vfs = self.compilation.get_db().get_vfs();
+            for file_id in &file_ids {
+                let Some(path) = vfs.get_file_path(file_id) else {
+                    continue;
+                };
+                let Some(entry) = index_cache.load(path) else {
+                    continue;
+                };
+                if entry.env_key == env_key
+                    && entry.file_id == *file_id
+                    && vfs
+                        .get_file_content(file_id)
+                        .map(|text| get_content_hash(text))
+                        == Some(entry.content_hash)
+                {
+                    entries.insert(*file_id, entry);
+                }
+            }
+
+            // an entry is out of date when a file it depends on changed or is analyzed again
+            loop {
+                let outdated = entries
+                    .iter()
+                    .filter(|(_, entry)| {
+                        entry.dependencies.iter().any(|(path, content_hash)| {
+                            let Some(file_id) =
+                                file_path_to_uri(path).and_then(|uri| vfs.get_file_id(&uri))
+                            else {
+                                return true;
+                            };
+                            vfs.get_file_content(&file_id)
+                                .map(|text| get_content_hash(text))
+                                != Some(*content_hash)
+                                || (file_ids.contains(&file_id) && !entries.contains_key(&file_id))
+                        })
+                    })
+                    .map(|(file_id, _)| *file_id)
+                    .collect::<Vec<_>>();
+                if outdated.is_empty() {
+                    break;
+                }
+                for file_id in outdated {
+                    entries.remove(&file_id);
+                }
+            }
+
+            let restored_files = entries.keys().copied().collect::<HashSet<_>>();
+            let mut entries = entries.into_iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(file_id, _)| file_id.id);
+            let db = self.compilation.get_db_mut();
+            for (_, entry) in entries {
+                db.merge_file(entry.index);
+            }
+            restored_files
+        };
+
+        let analyzed_files = file_ids
+            .iter()
+            .filter(|file_id| !restored_files.contains(file_id))
+            .copied()
+            .collect::<Vec<_>>();
+        if analyzed_files.is_empty() {
+            return file_ids;
+        }
+
+        self.compilation.update_index(analyzed_files.clone());
+        // restored files that now use what an analyzed file declares are analyzed again too
+        let dependency_index = self.compilation.get_db().get_file_dependencies_index();
+        let saved_files = file_ids
+            .iter()
+            .filter(|file_id| {
+                analyzed_files.contains(file_id)
+                    || dependency_index
+                        .get_required_files(file_id)
+                        .is_some_and(|required| {
+                            analyzed_files.iter().any(|id| required.contains(id))
+                        })
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        for file_id in saved_files {
+            self.save_index_cache_entry(&index_cache, env_key, file_id);
+        }
+        file_ids
+    }
+
+    fn get_index_cache(&self, files: &[(PathBuf, Option<String>)]) -> Option<LuaIndexCache> {
+        let index_cache = self.index_cache.as_ref()?;
+        if !self.emmyrc.workspace.enable_index_cache || files.is_empty() {
+            return None;
+        }
+
+        // the file ids of the restored files must not depend on the order the main files are opened
+        let db = self.compilation.get_db();
+        let vfs = db.get_vfs();
+        let module_index = db.get_module_index();
+        if vfs.get_all_file_ids().into_iter().any(|file_id| {
+            module_index
+                .get_workspace_id(file_id)
+                .is_none_or(|workspace_id| workspace_id.is_main())
+        }) {
+            return None;
+        }
+
+        for (path, text) in files {
+            let uri = file_path_to_uri(path)?;
+            if vfs.get_file_id(&uri).is_some() || text.is_none() {
+                return None;
+            }
+        }
+
+        Some(index_cache.clone())
+    }
+
+    /// Save what the file added to the indexes with the files it depends on
+    fn save_index_cache_entry(
+        &mut self,
+        index_cache: &LuaIndexCache,
+        env_key: u64,
+        file_id: FileId,
+    ) -> Option<()> {
+        let db = self.compilation.get_db_mut();
+        let vfs = db.get_vfs();
+        let path = vfs.get_file_path(&file_id)?.clone();
+        let content_hash = get_content_hash(vfs.get_file_content(&file_id)?);
+        let mut dependencies = Vec::new();
+        if let Some(required_files) = db
+            .get_file_dependencies_index()
+            .get_required_files(&file_id)
+        {
+            for required_file in required_files {
+                let Some(required_path) = vfs.get_file_path(required_file) else {
+                    continue;
+                };
+                let Some(text) = vfs.get_file_content(required_file) else {
+                    continue;
+                };
+                dependencies.push((required_path.clone(), get_content_hash(text)));
+            }
+        }
+
+        let entry = LuaIndexCacheEntry {
+            env_key,
+            file_id,
+            content_hash,
+            dependencies,
+            index: db.take_file(file_id),
+        };
+        index_cache.save(&path, &entry);
+        db.merge_file(entry.index);
+        Some(())
+    }
+
     pub fn update_config(&mut self, config: Arc<Emmyrc>) {
         self.emmyrc = config.clone();
         self.compilation.update_config(config.clone());
@@ -4,8 +4,9 @@ use crate::{
     DbIndex, LuaAliasCallKind, LuaAliasCallType, LuaMemberKey, LuaType, TypeOps, VariadicType,
     get_member_map,
     semantic::{
+        LuaPatternCapture,
         member::{find_members, infer_raw_member_type},
-        type_check,
+        parse_lua_pattern, type_check,
     },
 };
 
@@ -79,6 +80,13 @@ pub fn instantiate_alias_call(
 
             return instantiate_rawget_call(db, &operands[0], &operands[1]);
         }
+        LuaAliasCallKind::Captures => {
+            if operands.len() != 1 {
+                return LuaType::Unknown;
+            }
+
+            return instantiate_captures_call(db, &operands[0]);
+        }
         _ => {}
     }
 
@@ -250,3 +258,29 @@ fn instantiate_rawget_call(db: &DbIndex, owner: &LuaType, key: &LuaType) -> LuaT
 
     infer_raw_member_type(db, owner, &member_key).unwrap_or(LuaType::Unknown)
 }
+
+/// The values `string.match` returns for the pattern, all of them are nil when it does not match
+fn instantiate_captures_call(db: &DbIndex, pattern: &LuaType) -> LuaType {
+    let nullable_string = TypeOps::Union.apply(db, &LuaType::String, &LuaType::Nil);
+    let captures = match pattern {
+        LuaType::StringConst(s) | LuaType::DocStringConst(s) => parse_lua_pattern(s).ok(),
+        _ => None,
+    };
+    let Some(captures) = captures else {
+        return LuaType::Variadic(VariadicType::Base(nullable_string).into());
+    };
+    if captures.is_empty() {
+        return nullable_string;
+    }
+
+    let types = captures
+        .iter()
+        .map(|capture| match capture {
+            LuaPatternCapture::String => nullable_string.clone(),
+            LuaPatternCapture::Position => {
+                TypeOps::Union.apply(db, &LuaType::Integer, &LuaType::Nil)
+            }
+        })
+        .collect::<Vec<_>>();
+    LuaType::Variadic(VariadicType::Multi(types).into())
+}
@@ -74,6 +74,33 @@ mod test {
         assert_eq!(h, expected);
     }
 
+    #[test]
+    fn test_pattern_captures() {
+        let mut ws = crate::VirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+        ---@type string
+        local s
+        key, value, pos = string.match(s, "(%w+)=(%w+)()")
+        word = s:match("%a+")
+        any = string.match(s, s)
+        for k, v in string.gmatch(s, "(%w+)=()") do
+            gk, gv = k, v
+        end
+        "#,
+        );
+
+        let nullable_string = ws.ty("string?");
+        let nullable_integer = ws.ty("integer?");
+        assert_eq!(ws.expr_ty("key"), nullable_string);
+        assert_eq!(ws.expr_ty("value"), nullable_string);
+        assert_eq!(ws.expr_ty("pos"), nullable_integer);
+        assert_eq!(ws.expr_ty("word"), nullable_string);
+        assert_eq!(ws.expr_ty("any"), nullable_string);
+        assert_eq!(ws.expr_ty("gk"), ws.ty("string"));
+        assert_eq!(ws.expr_ty("gv"), ws.ty("integer"));
+    }
+
     #[test]
     fn test_unpack() {
         let mut ws = crate::VirtualWorkspace::new_with_init_std_lib();
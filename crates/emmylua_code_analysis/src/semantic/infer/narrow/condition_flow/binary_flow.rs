@@ -133,17 +133,37 @@ fn try_get_at_eq_or_neq_expr(
         return Ok(ResultTypeOrContinue::Result(result_type));
     }
 
-    maybe_var_eq_narrow(
+    result_type = maybe_var_eq_narrow(
         db,
         tree,
         cache,
         root,
         var_ref_id,
         flow_node,
-        left_expr,
-        right_expr,
+        left_expr.clone(),
+        right_expr.clone(),
         condition_flow,
-    )
+    )?;
+    if let ResultTypeOrContinue::Result(result_type) = result_type {
+        return Ok(ResultTypeOrContinue::Result(result_type));
+    }
+
+    // `"value" == x`
+    if matches!(right_expr, LuaExpr::NameExpr(_) | LuaExpr::IndexExpr(_)) {
+        return maybe_var_eq_narrow(
+            db,
+            tree,
+            cache,
+            root,
+            var_ref_id,
+            flow_node,
+            right_expr,
+            left_expr,
+            condition_flow,
+        );
+    }
+
+    Ok(ResultTypeOrContinue::Continue)
 }
 
 #[allow(clippy::too_many_arguments)]
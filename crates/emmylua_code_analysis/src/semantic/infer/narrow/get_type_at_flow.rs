@@ -47,6 +47,11 @@ pub fn get_type_at_flow(
             FlowNodeKind::LoopLabel | FlowNodeKind::Break | FlowNodeKind::Return => {
                 antecedent_flow_id = get_single_antecedent(tree, flow_node)?;
             }
+            FlowNodeKind::NamedLabel(_) if flow_node.antecedent.is_none() => {
+                // only reachable by goto
+                result_type = get_var_ref_type(db, cache, var_ref_id)?;
+                break;
+            }
             FlowNodeKind::BranchLabel | FlowNodeKind::NamedLabel(_) => {
                 let multi_antecedents = get_multi_antecedents(tree, flow_node)?;
 
@@ -36,6 +36,17 @@ pub fn infer_expr_narrow_type(
     get_type_at_flow::get_type_at_flow(db, flow_tree, cache, &root, &var_ref_id, flow_id)
 }
 
+/// The type of the variable `var_expr` refers to, narrowed at the flow of `position_expr`
+pub fn infer_var_type_at(
+    db: &DbIndex,
+    cache: &mut LuaInferCache,
+    var_expr: LuaExpr,
+    position_expr: LuaExpr,
+) -> InferResult {
+    let var_ref_id = get_var_expr_var_ref_id(db, cache, var_expr).ok_or(InferFailReason::None)?;
+    infer_expr_narrow_type(db, cache, position_expr, var_ref_id)
+}
+
 fn get_var_ref_type(db: &DbIndex, cache: &mut LuaInferCache, var_ref_id: &VarRefId) -> InferResult {
     if let Some(decl_id) = var_ref_id.get_decl_id_ref() {
         let decl = db
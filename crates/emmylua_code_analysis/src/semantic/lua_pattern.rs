@@ -0,0 +1,143 @@
+/// `LUA_MAXCAPTURES` of the reference implementation
+const MAX_CAPTURES: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LuaPatternCapture {
+    /// `(...)` captures a substring
+    String,
+    /// `()` captures the position
+    Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LuaPatternErrorKind {
+    /// a `%` at the end of the pattern
+    EndsWithEscape,
+    /// a `[` set without `]`
+    MissingBracket,
+    /// `%b` without the two delimiters
+    MissingBalanceArgs,
+    /// `%f` not followed by a set
+    MissingFrontierSet,
+    /// a `(` without `)`
+    UnfinishedCapture,
+    /// a `)` without `(`
+    InvalidCapture,
+    /// `%0`-`%9` not referring to a closed capture
+    InvalidCaptureIndex(String),
+    TooManyCaptures,
+}
+
+/// The first error of the pattern, `start` and `end` are byte offsets in the pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LuaPatternError {
+    pub kind: LuaPatternErrorKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Check the pattern the way `string.find` and friends parse it, and list its captures in the
+/// order of their opening parentheses
+pub fn parse_lua_pattern(pattern: &str) -> Result<Vec<LuaPatternCapture>, LuaPatternError> {
+    let bytes = pattern.as_bytes();
+    let error = |kind, start, end| Err(LuaPatternError { kind, start, end });
+    let mut captures = Vec::new();
+    // 未闭合的捕获在 `captures` 中的下标与 `(` 的位置
+    let mut open_captures: Vec<(usize, usize)> = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' => {
+                if captures.len() >= MAX_CAPTURES {
+                    return error(LuaPatternErrorKind::TooManyCaptures, i, i + 1);
+                }
+                if bytes.get(i + 1) == Some(&b')') {
+                    captures.push(LuaPatternCapture::Position);
+                    i += 2;
+                } else {
+                    open_captures.push((captures.len(), i));
+                    captures.push(LuaPatternCapture::String);
+                    i += 1;
+                }
+            }
+            b')' => {
+                if open_captures.pop().is_none() {
+                    return error(LuaPatternErrorKind::InvalidCapture, i, i + 1);
+                }
+                i += 1;
+            }
+            b'%' => {
+                let Some(&next) = bytes.get(i + 1) else {
+                    return error(LuaPatternErrorKind::EndsWithEscape, i, i + 1);
+                };
+                match next {
+                    b'b' => {
+                        if i + 3 >= bytes.len() {
+                            return error(LuaPatternErrorKind::MissingBalanceArgs, i, bytes.len());
+                        }
+                        i += 4;
+                    }
+                    b'f' => {
+                        if bytes.get(i + 2) != Some(&b'[') {
+                            return error(LuaPatternErrorKind::MissingFrontierSet, i, i + 2);
+                        }
+                        i = skip_set(bytes, i + 2)?;
+                    }
+                    b'0'..=b'9' => {
+                        let index = (next - b'0') as usize;
+                        let closed = index >= 1
+                            && index <= captures.len()
+                            && !open_captures.iter().any(|(open, _)| *open == index - 1);
+                        if !closed {
+                            return error(
+                                LuaPatternErrorKind::InvalidCaptureIndex(
+                                    pattern[i..i + 2].to_string(),
+                                ),
+                                i,
+                                i + 2,
+                            );
+                        }
+                        i += 2;
+                    }
+                    _ => i += 2,
+                }
+            }
+            b'[' => i = skip_set(bytes, i)?,
+            _ => i += 1,
+        }
+        while i < bytes.len() && !pattern.is_char_boundary(i) {
+            i += 1;
+        }
+    }
+
+    if let Some((_, start)) = open_captures.first() {
+        return error(LuaPatternErrorKind::UnfinishedCapture, *start, *start + 1);
+    }
+    Ok(captures)
+}
+
+/// The offset after the `]` of the set starting at `start`, the first `]` after `[` or `[^`
+/// belongs to the set
+fn skip_set(bytes: &[u8], start: usize) -> Result<usize, LuaPatternError> {
+    let mut i = start + 1;
+    if bytes.get(i) == Some(&b'^') {
+        i += 1;
+    }
+    loop {
+        if i >= bytes.len() {
+            return Err(LuaPatternError {
+                kind: LuaPatternErrorKind::MissingBracket,
+                start,
+                end: start + 1,
+            });
+        }
+        let c = bytes[i];
+        i += 1;
+        if c == b'%' && i < bytes.len() {
+            i += 1;
+        }
+        if bytes.get(i) == Some(&b']') {
+            return Ok(i + 1);
+        }
+    }
+}
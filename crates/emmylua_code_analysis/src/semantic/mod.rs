@@ -3,6 +3,7 @@ mod decl;
 mod generic;
 mod guard;
 mod infer;
+mod lua_pattern;
 mod member;
 mod overload_resolve;
 mod reference;
@@ -17,8 +18,8 @@ use std::sync::Arc;
 pub use cache::{CacheEntry, CacheOptions, LuaAnalysisPhase, LuaInferCache};
 pub use decl::{enum_variable_is_param, parse_require_module_info};
 use emmylua_parser::{
-    LuaCallExpr, LuaChunk, LuaExpr, LuaIndexExpr, LuaIndexKey, LuaParseError, LuaSyntaxNode,
-    LuaSyntaxToken, LuaTableExpr,
+    LuaAstNode, LuaCallExpr, LuaChunk, LuaExpr, LuaGotoStat, LuaIndexExpr, LuaIndexKey,
+    LuaLabelStat, LuaParseError, LuaSyntaxNode, LuaSyntaxToken, LuaTableExpr, LuaTokenKind,
 };
 pub use infer::infer_index_expr;
 use infer::{infer_bind_value_type, infer_expr_list_types};
@@ -42,7 +43,9 @@ use visibility::check_visibility;
 
 pub use crate::semantic::member::find_members_with_key;
 use crate::semantic::type_check::check_type_compact_detail;
-use crate::{Emmyrc, LuaDocument, LuaSemanticDeclId, ModuleInfo, db_index::LuaTypeDeclId};
+use crate::{
+    Emmyrc, LuaDocument, LuaLabel, LuaSemanticDeclId, ModuleInfo, db_index::LuaTypeDeclId,
+};
 use crate::{
     FileId,
     db_index::{DbIndex, LuaType},
@@ -54,6 +57,7 @@ pub use infer::InferFailReason;
 pub use infer::infer_call_expr_func;
 pub(crate) use infer::infer_expr;
 pub use infer::infer_param;
+pub(crate) use lua_pattern::{LuaPatternCapture, LuaPatternErrorKind, parse_lua_pattern};
 use overload_resolve::resolve_signature;
 pub use semantic_info::SemanticDeclLevel;
 pub use type_check::{TypeCheckFailReason, TypeCheckResult};
@@ -124,6 +128,18 @@ impl<'a> SemanticModel<'a> {
         infer_expr(self.db, &mut self.infer_cache.borrow_mut(), expr)
     }
 
+    /// The type of the variable `var_expr` refers to at `position_expr`, a name or index
+    /// expression, narrowed by the conditions leading there
+    pub fn infer_var_type_at(&self, var_expr: LuaExpr, position_expr: LuaExpr) -> Option<LuaType> {
+        infer::infer_var_type_at(
+            self.db,
+            &mut self.infer_cache.borrow_mut(),
+            var_expr,
+            position_expr,
+        )
+        .ok()
+    }
+
     pub fn infer_table_should_be(&self, table: LuaTableExpr) -> Option<LuaType> {
         infer_table_should_be(self.db, &mut self.infer_cache.borrow_mut(), table).ok()
     }
@@ -148,6 +164,28 @@ impl<'a> SemanticModel<'a> {
         get_member_map(self.db, prefix_type)
     }
 
+    /// The member of the super types of the class that its member with the key overrides
+    pub fn find_super_member(
+        &self,
+        type_decl_id: &LuaTypeDeclId,
+        member_key: &LuaMemberKey,
+    ) -> Option<LuaMemberInfo> {
+        let super_types = self.db.get_type_index().get_super_types(type_decl_id)?;
+        let guard = InferGuard::new();
+        super_types.into_iter().find_map(|super_type| {
+            let super_type_id = match &super_type {
+                LuaType::Ref(id) => id,
+                LuaType::Generic(generic) => generic.get_base_type_id_ref(),
+                _ => return None,
+            };
+            guard.check(super_type_id).ok()?;
+            get_member_map(self.db, &super_type)?
+                .remove(member_key)?
+                .into_iter()
+                .next()
+        })
+    }
+
     pub fn type_check(&self, source: &LuaType, compact_type: &LuaType) -> TypeCheckResult {
         check_type_compact(self.db, source, compact_type)
     }
@@ -316,4 +354,44 @@ impl<'a> SemanticModel<'a> {
         let cache = &mut self.infer_cache.borrow_mut();
         infer_index_expr(self.db, cache, index_expr, false).ok()
     }
+
+    /// Find the label a goto statement jumps to
+    pub fn find_goto_label(&self, goto_stat: &LuaGotoStat) -> Option<&'a LuaLabel> {
+        let name_token = goto_stat.get_label_name_token()?;
+        self.db
+            .get_decl_index()
+            .get_decl_tree(&self.file_id)?
+            .find_label(name_token.get_name_text(), goto_stat.get_position())
+    }
+
+    /// Find the label referenced by the name token of a label or goto statement
+    pub fn find_token_label(&self, token: &LuaSyntaxToken) -> Option<&'a LuaLabel> {
+        if token.kind() != LuaTokenKind::TkName.into() {
+            return None;
+        }
+
+        let parent = token.parent()?;
+        if let Some(goto_stat) = LuaGotoStat::cast(parent.clone()) {
+            return self.find_goto_label(&goto_stat);
+        }
+
+        LuaLabelStat::cast(parent)?;
+        self.db
+            .get_decl_index()
+            .get_decl_tree(&self.file_id)?
+            .get_labels()
+            .iter()
+            .find(|label| label.get_range() == token.text_range())
+    }
+
+    /// Find all goto statements jumping to the label
+    pub fn get_label_gotos(&self, label: &LuaLabel) -> Vec<LuaGotoStat> {
+        self.root
+            .descendants::<LuaGotoStat>()
+            .filter(|goto_stat| {
+                self.find_goto_label(goto_stat)
+                    .is_some_and(|target| target == label)
+            })
+            .collect()
+    }
 }
@@ -170,6 +170,25 @@ impl VirtualWorkspace {
         true
     }
 
+    /// The data of the first `diagnostic_code` diagnostic in the code
+    pub fn get_diagnostic_data(
+        &mut self,
+        diagnostic_code: DiagnosticCode,
+        block_str: &str,
+    ) -> Option<serde_json::Value> {
+        let file_id = self.def(block_str);
+        let diagnostics = self
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())?;
+        let code_string = Some(NumberOrString::String(
+            diagnostic_code.get_name().to_string(),
+        ));
+        diagnostics
+            .into_iter()
+            .find(|diagnostic| diagnostic.code == code_string)?
+            .data
+    }
+
     pub fn check_code_for_namespace(
         &mut self,
         diagnostic_code: DiagnosticCode,
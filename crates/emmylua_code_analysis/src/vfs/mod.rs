@@ -9,8 +9,8 @@ use emmylua_parser::{LineIndex, LuaParseError, LuaParser, LuaSyntaxTree};
 pub use file_id::{FileId, InFiled};
 pub use file_uri_handler::{file_path_to_uri, uri_to_file_path};
 pub use loader::{LuaFileInfo, load_workspace_files, read_file_with_encoding};
-use lsp_types::Uri;
-use rowan::NodeCache;
+use lsp_types::{Position, TextDocumentContentChangeEvent, Uri};
+use rowan::{NodeCache, TextRange, TextSize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -105,6 +105,68 @@ impl Vfs {
         fid
     }
 
+    /// Apply the changes of an incremental text sync to an opened file. The syntax tree is
+    /// reparsed incrementally, reusing the nodes outside of the edited block when possible.
+    ///
+    /// Returns `None` and leaves the file unchanged when it is not loaded or a range does not fit
+    /// its text, the document is out of sync with the client then.
+    pub fn change_file_content(
+        &mut self,
+        uri: &Uri,
+        changes: Vec<TextDocumentContentChangeEvent>,
+    ) -> Option<FileId> {
+        let fid = self.get_file_id(uri)?;
+        let emmyrc = self.emmyrc.clone()?;
+        let mut text = self.get_file_content(&fid)?.clone();
+        let mut line_index = self.line_index_map.get(&fid)?.clone();
+        let mut tree = self.tree_map.get(&fid).cloned();
+        for change in changes {
+            let Some(range) = change.range else {
+                text = change.text;
+                tree = Some(LuaParser::parse(
+                    &text,
+                    emmyrc.get_parse_config(&mut self.node_cache),
+                ));
+                line_index = LineIndex::parse(&text);
+                continue;
+            };
+
+            let start = get_utf16_offset(&text, &line_index, range.start);
+            let end = get_utf16_offset(&text, &line_index, range.end);
+            let edit_range = match (start, end) {
+                (Some(start), Some(end)) if start <= end => TextRange::new(start, end),
+                _ => {
+                    log::warn!("invalid change range {:?} for {}", range, uri.as_str());
+                    return None;
+                }
+            };
+
+            text.replace_range(
+                usize::from(edit_range.start())..usize::from(edit_range.end()),
+                &change.text,
+            );
+            let parse_config = emmyrc.get_parse_config(&mut self.node_cache);
+            tree = Some(match &tree {
+                Some(old_tree) => LuaParser::reparse(
+                    old_tree,
+                    &text,
+                    edit_range,
+                    TextSize::of(change.text.as_str()),
+                    parse_config,
+                ),
+                None => LuaParser::parse(&text, parse_config),
+            });
+            line_index = LineIndex::parse(&text);
+        }
+
+        if let Some(tree) = tree {
+            self.tree_map.insert(fid, tree);
+        }
+        self.line_index_map.insert(fid, line_index);
+        self.file_data[fid.id as usize] = Some(text);
+        Some(fid)
+    }
+
     pub fn remove_file(&mut self, uri: &Uri) -> Option<FileId> {
         let fid = self.get_file_id(uri)?;
         if let Some(path) = self.file_path_map.remove(&fid.id) {
@@ -172,3 +234,110 @@ impl Vfs {
         self.node_cache = NodeCache::default();
     }
 }
+
+/// The offset of an LSP position, whose character counts UTF-16 code units. A character past the
+/// end of the line is clamped to it, a character inside a surrogate pair is an error.
+fn get_utf16_offset(text: &str, line_index: &LineIndex, position: Position) -> Option<TextSize> {
+    let line = position.line as usize;
+    let line_start = usize::from(line_index.get_line_offset(line)?);
+    let line_end = line_index
+        .get_line_offset(line + 1)
+        .map_or(text.len(), usize::from);
+    let line_text = text
+        .get(line_start..line_end)?
+        .trim_end_matches(['\n', '\r']);
+    let character = position.character as usize;
+    let mut units = 0;
+    let mut offset = line_start;
+    for c in line_text.chars() {
+        if units >= character {
+            break;
+        }
+        units += c.len_utf16();
+        offset += c.len_utf8();
+    }
+    if units > character {
+        return None;
+    }
+    Some(TextSize::from(offset as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Range;
+
+    fn create_vfs() -> Vfs {
+        let mut vfs = Vfs::new();
+        vfs.update_config(Emmyrc::default().into());
+        vfs
+    }
+
+    fn change(
+        start: (u32, u32),
+        end: (u32, u32),
+        text: &str,
+    ) -> Vec<TextDocumentContentChangeEvent> {
+        vec![TextDocumentContentChangeEvent {
+            range: Some(Range::new(
+                Position::new(start.0, start.1),
+                Position::new(end.0, end.1),
+            )),
+            range_length: None,
+            text: text.to_string(),
+        }]
+    }
+
+    #[test]
+    fn test_change_after_emoji() {
+        let mut vfs = create_vfs();
+        let uri = VirtualUrlGenerator::new().new_uri("emoji.lua");
+        let id = vfs.set_file_content(&uri, Some("local s = \"😀\" .. a\n".to_string()));
+
+        // `😀` 占两个 UTF-16 单元, `a` 位于第 18 列
+        vfs.change_file_content(&uri, change((0, 18), (0, 19), "b"));
+        assert_eq!(
+            vfs.get_file_content(&id).unwrap(),
+            "local s = \"😀\" .. b\n"
+        );
+        vfs.change_file_content(&uri, change((0, 13), (0, 13), "😀"));
+        assert_eq!(
+            vfs.get_file_content(&id).unwrap(),
+            "local s = \"😀😀\" .. b\n"
+        );
+    }
+
+    #[test]
+    fn test_change_past_line_end() {
+        let mut vfs = create_vfs();
+        let uri = VirtualUrlGenerator::new().new_uri("line_end.lua");
+        let id = vfs.set_file_content(&uri, Some("local a\nlocal b\n".to_string()));
+
+        vfs.change_file_content(&uri, change((0, 100), (0, 100), " = 1"));
+        assert_eq!(vfs.get_file_content(&id).unwrap(), "local a = 1\nlocal b\n");
+    }
+
+    #[test]
+    fn test_change_out_of_sync() {
+        let mut vfs = create_vfs();
+        let uri = VirtualUrlGenerator::new().new_uri("out_of_sync.lua");
+        let id = vfs.set_file_content(&uri, Some("local s = \"😀\"\n".to_string()));
+
+        // 位于代理对中间
+        assert!(
+            vfs.change_file_content(&uri, change((0, 12), (0, 12), "x"))
+                .is_none()
+        );
+        assert!(
+            vfs.change_file_content(&uri, change((5, 0), (5, 0), "x"))
+                .is_none()
+        );
+        assert_eq!(vfs.get_file_content(&id).unwrap(), "local s = \"😀\"\n");
+
+        let unknown_uri = VirtualUrlGenerator::new().new_uri("unknown.lua");
+        assert!(
+            vfs.change_file_content(&unknown_uri, change((0, 0), (0, 0), "x"))
+                .is_none()
+        );
+    }
+}
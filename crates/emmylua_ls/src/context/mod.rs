@@ -2,6 +2,7 @@ mod client;
 mod client_id;
 mod file_diagnostic;
 mod lsp_features;
+mod semantic_tokens_cache;
 mod snapshot;
 mod status_bar;
 mod workspace_manager;
@@ -13,6 +14,7 @@ pub use file_diagnostic::FileDiagnostic;
 pub use lsp_features::LspFeatures;
 use lsp_server::{Connection, ErrorCode, Message, RequestId, Response};
 use lsp_types::ClientCapabilities;
+pub use semantic_tokens_cache::SemanticTokensCache;
 pub use snapshot::ServerContextSnapshot;
 pub use status_bar::ProgressTask;
 pub use status_bar::StatusBar;
@@ -33,7 +35,7 @@ use crate::context::snapshot::ServerContextInner;
 // ## Global Lock Order (Low to High Priority):
 // 1. **diagnostic_tokens** (Mutex) - File diagnostic task tokens
 // 2. **workspace_diagnostic_token** (Mutex) - Workspace diagnostic task token
-// 3. **update_token** (Mutex) - Reindex/config update token
+// 3. **update_token** (Mutex) - Config update token
 // 4. **analysis** (RwLock - READ) - Read-only access to EmmyLuaAnalysis
 // 5. **workspace_manager** (RwLock - READ) - Read-only access to WorkspaceManager
 // 6. **workspace_manager** (RwLock - WRITE) - Exclusive access to WorkspaceManager
@@ -90,6 +92,7 @@ use crate::context::snapshot::ServerContextInner;
 // - `workspace_initialized` (AtomicBool)
 // - `workspace_diagnostic_level` (AtomicU8)
 // - `workspace_version` (AtomicI64)
+// - `semantic_tokens_cache` (std Mutex, never held across `.await` or another lock)
 //
 // ## Notes:
 // - Use `drop(lock_guard)` explicitly to release locks early when needed
@@ -122,7 +125,6 @@ impl ServerContext {
         let lsp_features = Arc::new(LspFeatures::new(client_capabilities));
         let workspace_manager = Arc::new(RwLock::new(WorkspaceManager::new(
             analysis.clone(),
-            client.clone(),
             status_bar.clone(),
             file_diagnostic.clone(),
             lsp_features.clone(),
@@ -138,6 +140,7 @@ impl ServerContext {
                 workspace_manager,
                 status_bar,
                 lsp_features,
+                semantic_tokens_cache: Arc::new(SemanticTokensCache::new()),
             }),
         }
     }
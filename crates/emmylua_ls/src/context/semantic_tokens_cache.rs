@@ -0,0 +1,49 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use lsp_types::{SemanticToken, Uri};
+
+/// The last semantic tokens sent for each document, the base of the next delta request.
+///
+/// The lock is never held across an `.await`, so it is outside of the lock ordering.
+#[derive(Debug, Default)]
+pub struct SemanticTokensCache {
+    next_result_id: AtomicU64,
+    tokens: Mutex<HashMap<Uri, (String, Vec<SemanticToken>)>>,
+}
+
+impl SemanticTokensCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store the tokens of the document and return their result id
+    pub fn save(&self, uri: Uri, tokens: Vec<SemanticToken>) -> String {
+        let result_id = (self.next_result_id.fetch_add(1, Ordering::Relaxed) + 1).to_string();
+        if let Ok(mut cache) = self.tokens.lock() {
+            cache.insert(uri, (result_id.clone(), tokens));
+        }
+        result_id
+    }
+
+    /// The tokens last sent for the document, if they have the given result id
+    pub fn take(&self, uri: &Uri, result_id: &str) -> Option<Vec<SemanticToken>> {
+        let mut cache = self.tokens.lock().ok()?;
+        if cache.get(uri)?.0 != result_id {
+            return None;
+        }
+
+        cache.remove(uri).map(|(_, tokens)| tokens)
+    }
+
+    pub fn remove(&self, uri: &Uri) {
+        if let Ok(mut cache) = self.tokens.lock() {
+            cache.remove(uri);
+        }
+    }
+}
@@ -3,7 +3,7 @@ use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU8, Ordering};
 use std::{path::PathBuf, sync::Arc, time::Duration};
 
-use super::{ClientProxy, FileDiagnostic, StatusBar};
+use super::{FileDiagnostic, StatusBar};
 use crate::context::lsp_features::LspFeatures;
 use crate::handlers::{ClientConfig, init_analysis};
 use emmylua_code_analysis::{EmmyLuaAnalysis, Emmyrc, load_configs};
@@ -16,7 +16,6 @@ use wax::Pattern;
 
 pub struct WorkspaceManager {
     analysis: Arc<RwLock<EmmyLuaAnalysis>>,
-    client: Arc<ClientProxy>,
     status_bar: Arc<StatusBar>,
     update_token: Arc<Mutex<Option<Arc<ReindexToken>>>>,
     file_diagnostic: Arc<FileDiagnostic>,
@@ -34,14 +33,12 @@ pub struct WorkspaceManager {
 impl WorkspaceManager {
     pub fn new(
         analysis: Arc<RwLock<EmmyLuaAnalysis>>,
-        client: Arc<ClientProxy>,
         status_bar: Arc<StatusBar>,
         file_diagnostic: Arc<FileDiagnostic>,
         lsp_features: Arc<LspFeatures>,
     ) -> Self {
         Self {
             analysis,
-            client,
             status_bar,
             client_config: ClientConfig::default(),
             workspace_folders: Vec::new(),
@@ -161,64 +158,6 @@ impl WorkspaceManager {
         Some(())
     }
 
-    pub async fn extend_reindex_delay(&self) -> Option<()> {
-        let update_token = self.update_token.lock().await;
-        if let Some(token) = update_token.as_ref() {
-            token.set_resleep().await;
-        }
-
-        Some(())
-    }
-
-    pub async fn reindex_workspace(&self, delay: Duration) -> Option<()> {
-        log::info!("reindex workspace with delay: {:?}", delay);
-        let mut update_token = self.update_token.lock().await;
-        if let Some(token) = update_token.as_ref() {
-            token.cancel();
-            log::info!("cancel reindex workspace");
-        }
-
-        let cancel_token = Arc::new(ReindexToken::new(delay));
-        update_token.replace(cancel_token.clone());
-        drop(update_token);
-        let analysis = self.analysis.clone();
-        let file_diagnostic = self.file_diagnostic.clone();
-        let lsp_features = self.lsp_features.clone();
-        let client = self.client.clone();
-        let workspace_diagnostic_status = self.workspace_diagnostic_level.clone();
-        tokio::spawn(async move {
-            cancel_token.wait_for_reindex().await;
-            if cancel_token.is_cancelled() {
-                return;
-            }
-
-            // Perform reindex with minimal lock holding time
-            {
-                let mut analysis = analysis.write().await;
-                // 在重新索引之前清理不存在的文件
-                analysis.cleanup_nonexistent_files();
-                analysis.reindex();
-                // Release lock immediately after reindex
-            }
-
-            // Cancel diagnostics and update status without holding analysis lock
-            file_diagnostic.cancel_workspace_diagnostic().await;
-            workspace_diagnostic_status
-                .store(WorkspaceDiagnosticLevel::Fast.to_u8(), Ordering::Release);
-
-            // Trigger diagnostics refresh
-            if lsp_features.supports_workspace_diagnostic() {
-                client.refresh_workspace_diagnostics();
-            } else {
-                file_diagnostic
-                    .add_workspace_diagnostic_task(500, true)
-                    .await;
-            }
-        });
-
-        Some(())
-    }
-
     pub fn is_workspace_file(&self, uri: &Uri) -> bool {
         if self.workspace_folders.is_empty() {
             return true;
@@ -333,7 +272,6 @@ fn merge_client_config(client_config: ClientConfig, emmyrc: &mut Emmyrc) -> Opti
 pub struct ReindexToken {
     cancel_token: CancellationToken,
     time_sleep: Duration,
-    need_re_sleep: Mutex<bool>,
 }
 
 impl ReindexToken {
@@ -341,26 +279,13 @@ impl ReindexToken {
         Self {
             cancel_token: CancellationToken::new(),
             time_sleep,
-            need_re_sleep: Mutex::new(false),
         }
     }
 
     pub async fn wait_for_reindex(&self) {
-        loop {
-            tokio::select! {
-                _ = tokio::time::sleep(self.time_sleep) => {
-                    // 获取锁来安全地访问和修改 need_re_sleep
-                    let mut need_re_sleep = self.need_re_sleep.lock().await;
-                    if *need_re_sleep {
-                        *need_re_sleep = false;
-                    } else {
-                        break;
-                    }
-                }
-                _ = self.cancel_token.cancelled() => {
-                    break;
-                }
-            }
+        tokio::select! {
+            _ = tokio::time::sleep(self.time_sleep) => {}
+            _ = self.cancel_token.cancelled() => {}
         }
     }
 
@@ -371,12 +296,6 @@ impl ReindexToken {
     pub fn is_cancelled(&self) -> bool {
         self.cancel_token.is_cancelled()
     }
-
-    pub async fn set_resleep(&self) {
-        // 获取锁来安全地修改 need_re_sleep
-        let mut need_re_sleep = self.need_re_sleep.lock().await;
-        *need_re_sleep = true;
-    }
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,45 @@
+use emmylua_code_analysis::SemanticModel;
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Position};
+
+use crate::handlers::command::{FunctionDefinition, SignatureParam, make_change_signature_command};
+
+/// The command carries the current parameters, the client asks for the new ones before
+/// running it. Without such a client the command changes nothing, the action is offered when
+/// the config says the client does it.
+pub fn build_change_signature(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    position: Position,
+) -> Option<()> {
+    if !semantic_model.get_emmyrc().code_action.change_signature {
+        return None;
+    }
+    let definition = FunctionDefinition::find(semantic_model, position)?;
+    let params = definition
+        .get_params()
+        .iter()
+        .enumerate()
+        .map(|(index, param)| {
+            Some(SignatureParam {
+                name: param.get_name_token()?.get_name_text().to_string(),
+                old_index: Some(index),
+                default_value: None,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let title = t!("Change signature");
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: title.to_string(),
+        kind: Some(CodeActionKind::REFACTOR),
+        command: Some(make_change_signature_command(
+            title.as_ref(),
+            semantic_model.get_file_id(),
+            position,
+            params,
+        )),
+        ..Default::default()
+    }));
+
+    Some(())
+}
@@ -0,0 +1,333 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use emmylua_code_analysis::{
+    LuaCompilation, LuaDocument, LuaSemanticDeclId, LuaType, SemanticDeclLevel, SemanticModel,
+};
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaCallExpr, LuaCommentOwner, LuaDocTag, LuaDocTagParam, LuaExpr,
+    LuaFuncStat, LuaIndexExpr, LuaIndexKey, LuaVarExpr,
+};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Position, Range, TextEdit, Uri, WorkspaceEdit,
+};
+use rowan::{TextRange, TextSize};
+
+use super::build_extract_code::get_indent;
+
+/// Switch a function statement between `function M.f(self, x)` and `function M:f(x)`, with the
+/// calls that pass the receiver the same way
+#[allow(clippy::mutable_key_type)]
+pub fn build_convert_method_definition(
+    semantic_model: &SemanticModel,
+    compilation: &LuaCompilation,
+    actions: &mut Vec<CodeActionOrCommand>,
+    position: Position,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(position.line as usize, position.character as usize)?;
+    let root = semantic_model.get_root();
+    let token = root.syntax().token_at_offset(offset).right_biased()?;
+    let func_stat = token.parent_ancestors().find_map(LuaFuncStat::cast)?;
+    let LuaVarExpr::IndexExpr(index_expr) = func_stat.get_func_name()? else {
+        return None;
+    };
+    if offset > index_expr.get_range().end() {
+        return None;
+    }
+    let index_token = index_expr.get_index_token()?;
+    let to_colon = if index_token.is_dot() {
+        true
+    } else if index_token.is_colon() {
+        false
+    } else {
+        return None;
+    };
+    let LuaSemanticDeclId::Member(member_id) = semantic_model.find_decl(
+        index_expr.syntax().clone().into(),
+        SemanticDeclLevel::NoTrace,
+    )?
+    else {
+        return None;
+    };
+    let param_list = func_stat.get_closure()?.get_params_list()?;
+    let params = param_list.get_params().collect::<Vec<_>>();
+    let self_doc = func_stat.get_left_comment().and_then(|comment| {
+        let params = comment
+            .get_doc_tags()
+            .filter_map(|tag| match tag {
+                LuaDocTag::Param(param) => Some(param),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        Some((find_self_doc(&params), params.first()?.clone()))
+    });
+
+    let mut edits = vec![TextEdit {
+        range: document.to_lsp_range(index_token.get_range())?,
+        new_text: if to_colon { ":" } else { "." }.to_string(),
+    }];
+    if to_colon {
+        // the implicit `self` of `:` takes the place of the first parameter
+        let first_param = params.first()?;
+        if first_param.get_name_token()?.get_name_text() != "self" {
+            return None;
+        }
+        let range = match params.get(1) {
+            Some(next_param) => {
+                TextRange::new(first_param.get_position(), next_param.get_position())
+            }
+            None => first_param.get_range(),
+        };
+        edits.push(TextEdit {
+            range: document.to_lsp_range(range)?,
+            new_text: String::new(),
+        });
+        if let Some((Some(self_doc), _)) = &self_doc {
+            let line = document.get_line(self_doc.get_position())?;
+            edits.push(TextEdit {
+                range: document.to_lsp_range(document.get_line_range(line)?)?,
+                new_text: String::new(),
+            });
+        }
+    } else {
+        let (offset, new_text) = match params.first() {
+            Some(first_param) => (first_param.get_position(), "self, "),
+            None => (param_list.get_position() + TextSize::from(1), "self"),
+        };
+        let position = document.to_lsp_position(offset)?;
+        edits.push(TextEdit {
+            range: Range::new(position, position),
+            new_text: new_text.to_string(),
+        });
+        // documented parameters stay complete with the new one
+        if let Some((None, first_doc)) = &self_doc
+            && let Some(type_name) = get_self_type_name(semantic_model, &index_expr)
+        {
+            let line_start = document
+                .get_line_range(document.get_line(first_doc.get_position())?)?
+                .start();
+            let position = document.to_lsp_position(line_start)?;
+            let indent = get_indent(&document, func_stat.get_position()).unwrap_or_default();
+            edits.push(TextEdit {
+                range: Range::new(position, position),
+                new_text: format!("{}---@param self {}\n", indent, type_name),
+            });
+        }
+    }
+
+    let mut changes: HashMap<Uri, Vec<TextEdit>> = HashMap::from([(document.get_uri(), edits)]);
+    add_call_edits(
+        semantic_model,
+        compilation,
+        LuaSemanticDeclId::Member(member_id),
+        &index_expr,
+        to_colon,
+        &mut changes,
+    );
+
+    let prefix_text = document.get_text_slice(index_expr.get_prefix_expr()?.get_range());
+    let key_text = document.get_text_slice(TextRange::new(
+        index_token.get_range().end(),
+        index_expr.get_range().end(),
+    ));
+    let new_name = format!(
+        "{}{}{}",
+        prefix_text,
+        if to_colon { ":" } else { "." },
+        key_text
+    );
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Convert to `%{name}`", name = new_name).to_string(),
+        kind: Some(CodeActionKind::REFACTOR_REWRITE),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+fn find_self_doc(params: &[LuaDocTagParam]) -> Option<LuaDocTagParam> {
+    params
+        .iter()
+        .find(|param| {
+            param
+                .get_name_token()
+                .is_some_and(|name| name.get_name_text() == "self")
+        })
+        .cloned()
+}
+
+fn get_self_type_name(semantic_model: &SemanticModel, index_expr: &LuaIndexExpr) -> Option<String> {
+    let prefix_type = semantic_model
+        .infer_expr(index_expr.get_prefix_expr()?)
+        .ok()?;
+    match prefix_type {
+        LuaType::Def(type_decl_id) | LuaType::Ref(type_decl_id) => {
+            Some(type_decl_id.get_name().to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Rewrite the calls `obj.f(obj, x)` to `obj:f(x)` or back, the calls with another receiver
+/// keep working as they are
+#[allow(clippy::mutable_key_type)]
+fn add_call_edits(
+    semantic_model: &SemanticModel,
+    compilation: &LuaCompilation,
+    semantic_id: LuaSemanticDeclId,
+    definition: &LuaIndexExpr,
+    to_colon: bool,
+    changes: &mut HashMap<Uri, Vec<TextEdit>>,
+) -> Option<()> {
+    let LuaSemanticDeclId::Member(member_id) = &semantic_id else {
+        return None;
+    };
+    let db = semantic_model.get_db();
+    let member = db.get_member_index().get_member(member_id)?;
+    let index_references = db
+        .get_reference_index()
+        .get_index_references(member.get_key())?;
+
+    let mut semantic_cache = HashMap::new();
+    for in_filed_syntax_id in index_references {
+        let file_id = in_filed_syntax_id.file_id;
+        if file_id == semantic_model.get_file_id()
+            && in_filed_syntax_id.value.get_range() == definition.get_range()
+        {
+            continue;
+        }
+        let semantic_model = match semantic_cache.entry(file_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let Some(semantic_model) = compilation.get_semantic_model(file_id) else {
+                    continue;
+                };
+                entry.insert(semantic_model)
+            }
+        };
+        let root = semantic_model.get_root();
+        let Some(index_expr) = in_filed_syntax_id
+            .value
+            .to_node_from_root(root.syntax())
+            .and_then(LuaIndexExpr::cast)
+        else {
+            continue;
+        };
+        let Some(call_expr) = index_expr.get_parent::<LuaCallExpr>() else {
+            continue;
+        };
+        if call_expr
+            .get_prefix_expr()
+            .is_none_or(|prefix_expr| prefix_expr.syntax() != index_expr.syntax())
+            || !semantic_model.is_reference_to(
+                index_expr.syntax().clone(),
+                semantic_id.clone(),
+                SemanticDeclLevel::default(),
+            )
+        {
+            continue;
+        }
+
+        let document = semantic_model.get_document();
+        let edits = match to_colon {
+            true => build_colon_call_edits(&document, &index_expr, &call_expr),
+            false => build_dot_call_edits(&document, &index_expr, &call_expr),
+        };
+        if let Some(edits) = edits {
+            changes.entry(document.get_uri()).or_default().extend(edits);
+        }
+    }
+
+    Some(())
+}
+
+/// `obj.f(obj, x)` to `obj:f(x)`
+fn build_colon_call_edits(
+    document: &LuaDocument,
+    index_expr: &LuaIndexExpr,
+    call_expr: &LuaCallExpr,
+) -> Option<Vec<TextEdit>> {
+    let index_token = index_expr.get_index_token()?;
+    let prefix_expr = index_expr.get_prefix_expr()?;
+    let args = call_expr.get_args_list()?.get_args().collect::<Vec<_>>();
+    let first_arg = args.first()?;
+    let prefix_text = document.get_text_slice(prefix_expr.get_range());
+    if !index_token.is_dot()
+        || !is_simple_prefix(&prefix_expr)
+        || document.get_text_slice(first_arg.get_range()) != prefix_text
+    {
+        return None;
+    }
+
+    let range = match args.get(1) {
+        Some(next_arg) => TextRange::new(first_arg.get_position(), next_arg.get_position()),
+        None => first_arg.get_range(),
+    };
+    Some(vec![
+        TextEdit {
+            range: document.to_lsp_range(index_token.get_range())?,
+            new_text: ":".to_string(),
+        },
+        TextEdit {
+            range: document.to_lsp_range(range)?,
+            new_text: String::new(),
+        },
+    ])
+}
+
+/// `obj:f(x)` to `obj.f(obj, x)`
+fn build_dot_call_edits(
+    document: &LuaDocument,
+    index_expr: &LuaIndexExpr,
+    call_expr: &LuaCallExpr,
+) -> Option<Vec<TextEdit>> {
+    let index_token = index_expr.get_index_token()?;
+    let prefix_expr = index_expr.get_prefix_expr()?;
+    let args_list = call_expr.get_args_list()?;
+    if !index_token.is_colon()
+        || !is_simple_prefix(&prefix_expr)
+        || args_list.is_single_arg_no_parens()
+    {
+        return None;
+    }
+
+    let prefix_text = document.get_text_slice(prefix_expr.get_range());
+    let (offset, new_text) = match args_list.get_args().next() {
+        Some(first_arg) => (first_arg.get_position(), format!("{}, ", prefix_text)),
+        None => (
+            args_list.get_position() + TextSize::from(1),
+            prefix_text.to_string(),
+        ),
+    };
+    let position = document.to_lsp_position(offset)?;
+    Some(vec![
+        TextEdit {
+            range: document.to_lsp_range(index_token.get_range())?,
+            new_text: ".".to_string(),
+        },
+        TextEdit {
+            range: Range::new(position, position),
+            new_text,
+        },
+    ])
+}
+
+/// A receiver that can be written twice without evaluating anything more, like `a.b.c`
+fn is_simple_prefix(expr: &LuaExpr) -> bool {
+    match expr {
+        LuaExpr::NameExpr(_) => true,
+        LuaExpr::IndexExpr(index_expr) => {
+            matches!(
+                index_expr.get_index_key(),
+                Some(LuaIndexKey::Name(_) | LuaIndexKey::String(_) | LuaIndexKey::Integer(_))
+            ) && index_expr
+                .get_prefix_expr()
+                .is_some_and(|prefix_expr| is_simple_prefix(&prefix_expr))
+        }
+        _ => false,
+    }
+}
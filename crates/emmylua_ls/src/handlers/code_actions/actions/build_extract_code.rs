@@ -0,0 +1,544 @@
+use std::collections::{HashMap, HashSet};
+
+use emmylua_code_analysis::{
+    DbIndex, LuaDecl, LuaDocument, LuaType, RenderLevel, SemanticModel, humanize_type, infer_param,
+};
+use emmylua_parser::{
+    BinaryOperator, LuaAssignStat, LuaAstNode, LuaAstToken, LuaBinaryExpr, LuaBlock,
+    LuaCommentOwner, LuaExpr, LuaLiteralToken, LuaNameExpr, LuaStat, LuaSyntaxKind, LuaSyntaxNode,
+    LuaTokenKind,
+};
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit, WorkspaceEdit};
+use rowan::{NodeOrToken, TextRange, TextSize};
+
+use super::build_inline_code::is_multi_value_position;
+
+const LUA_KEYWORDS: [&str; 22] = [
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in",
+    "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+pub fn build_extract_local(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let selection = get_selection(&document, range)?;
+    let root = semantic_model.get_root();
+    let covering_node = match root.syntax().covering_element(selection) {
+        NodeOrToken::Node(node) => node,
+        NodeOrToken::Token(token) => token.parent()?,
+    };
+    let expr = covering_node
+        .ancestors()
+        .filter_map(LuaExpr::cast)
+        .find(|expr| expr.get_range() == selection)?;
+    if !can_extract_expr(&expr) {
+        return None;
+    }
+
+    // the new local goes before the statement and the comment attached to it
+    let stat = expr.ancestors::<LuaStat>().next()?;
+    let insert_offset = match stat.get_left_comment() {
+        Some(comment) => comment.get_position(),
+        None => stat.get_position(),
+    };
+    let name = get_unique_name(semantic_model, &suggest_local_name(&expr));
+    let expr_text = document.get_text_slice(expr.get_range());
+    let new_text = match get_indent(&document, insert_offset) {
+        Some(indent) => format!("local {} = {}\n{}", name, expr_text, indent),
+        None => format!("local {} = {} ", name, expr_text),
+    };
+
+    let insert_position = document.to_lsp_position(insert_offset)?;
+    let edits = vec![
+        TextEdit {
+            range: Range::new(insert_position, insert_position),
+            new_text,
+        },
+        TextEdit {
+            range: document.to_lsp_range(selection)?,
+            new_text: name,
+        },
+    ];
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Extract to local variable").to_string(),
+        kind: Some(CodeActionKind::REFACTOR_EXTRACT),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), edits)])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+fn can_extract_expr(expr: &LuaExpr) -> bool {
+    let Some(parent) = expr.syntax().parent() else {
+        return false;
+    };
+    // a local keeps only the first of the values used there
+    if is_multi_value_expr(expr) && is_multi_value_position(expr.syntax()) {
+        return false;
+    }
+    match parent.kind().into() {
+        // a call statement, or the name of a function statement
+        LuaSyntaxKind::CallExprStat | LuaSyntaxKind::FuncStat => return false,
+        LuaSyntaxKind::AssignStat => {
+            // the assigned variables
+            if let Some(assign_stat) = LuaAssignStat::cast(parent)
+                && let Some(assign_op) = assign_stat.get_assign_op()
+                && expr.get_position() < assign_op.get_position()
+            {
+                return false;
+            }
+        }
+        _ => {}
+    }
+
+    // conditions evaluated on every iteration or only after the previous branches, and the
+    // operands of `and` and `or` that may not be evaluated
+    let mut child = expr.syntax().clone();
+    for node in expr.syntax().ancestors() {
+        match node.kind().into() {
+            LuaSyntaxKind::ElseIfClauseStat
+            | LuaSyntaxKind::WhileStat
+            | LuaSyntaxKind::RepeatStat => return false,
+            LuaSyntaxKind::BinaryExpr => {
+                if let Some(binary_expr) = LuaBinaryExpr::cast(node.clone())
+                    && let Some(op_token) = binary_expr.get_op_token()
+                    && matches!(
+                        op_token.get_op(),
+                        BinaryOperator::OpAnd | BinaryOperator::OpOr
+                    )
+                    && binary_expr
+                        .get_left_expr()
+                        .is_none_or(|left_expr| left_expr.syntax() != &child)
+                {
+                    return false;
+                }
+            }
+            kind if LuaStat::can_cast(kind) => return true,
+            _ => {}
+        }
+        child = node;
+    }
+
+    false
+}
+
+fn is_multi_value_expr(expr: &LuaExpr) -> bool {
+    match expr {
+        LuaExpr::CallExpr(_) => true,
+        LuaExpr::LiteralExpr(literal_expr) => {
+            matches!(literal_expr.get_literal(), Some(LuaLiteralToken::Dots(_)))
+        }
+        _ => false,
+    }
+}
+
+fn suggest_local_name(expr: &LuaExpr) -> String {
+    let name = match expr {
+        LuaExpr::CallExpr(call_expr) => match call_expr.get_prefix_expr() {
+            Some(LuaExpr::NameExpr(name_expr)) => name_expr.get_name_text(),
+            Some(LuaExpr::IndexExpr(index_expr)) => index_expr
+                .get_index_key()
+                .and_then(|key| key.get_name().map(|name| name.get_name_text().to_string())),
+            _ => None,
+        }
+        .map(|name| strip_getter_prefix(&name)),
+        LuaExpr::IndexExpr(index_expr) => index_expr
+            .get_index_key()
+            .and_then(|key| key.get_name().map(|name| name.get_name_text().to_string())),
+        LuaExpr::NameExpr(name_expr) => name_expr.get_name_text(),
+        _ => None,
+    };
+
+    name.filter(|name| is_valid_name(name))
+        .unwrap_or_else(|| "value".to_string())
+}
+
+// `getName` and `get_name` give `name`
+fn strip_getter_prefix(name: &str) -> String {
+    let stripped = name
+        .strip_prefix("get_")
+        .or_else(|| name.strip_prefix("get"))
+        .unwrap_or(name);
+    let mut chars = stripped.chars();
+    match chars.next() {
+        Some(first) if name != stripped => first.to_lowercase().chain(chars).collect(),
+        Some(_) => stripped.to_string(),
+        None => name.to_string(),
+    }
+}
+
+pub(super) fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !LUA_KEYWORDS.contains(&name)
+}
+
+pub fn build_extract_function(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let selection = get_selection(&document, range)?;
+    let root = semantic_model.get_root();
+    let covering_node = match root.syntax().covering_element(selection) {
+        NodeOrToken::Node(node) => node,
+        NodeOrToken::Token(token) => token.parent()?,
+    };
+    let block = covering_node.ancestors().find_map(LuaBlock::cast)?;
+    let stats = block
+        .get_stats()
+        .filter(|stat| {
+            stat.get_range()
+                .intersect(selection)
+                .is_some_and(|range| !range.is_empty())
+        })
+        .collect::<Vec<_>>();
+    let (first_stat, last_stat) = (stats.first()?, stats.last()?);
+    if stats
+        .iter()
+        .any(|stat| !selection.contains_range(stat.get_range()))
+        || stats
+            .iter()
+            .any(|stat| !can_extract_stat(stat.syntax(), block.syntax()))
+    {
+        return None;
+    }
+    let stats_range = TextRange::new(first_stat.get_position(), last_stat.get_range().end());
+
+    // the function goes before the top level statement that contains the selection
+    let top_stat = first_stat
+        .syntax()
+        .ancestors()
+        .filter_map(LuaStat::cast)
+        .last()?;
+    let insert_offset = match top_stat.get_left_comment() {
+        Some(comment) => comment.get_position(),
+        None => top_stat.get_position(),
+    };
+
+    let flow = analyze_extract_flow(semantic_model, stats_range, insert_offset)?;
+    let name = get_unique_name(semantic_model, "extracted_function");
+    let param_names = flow
+        .params
+        .iter()
+        .map(|decl| decl.get_name())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let return_decls = flow
+        .written_outer
+        .iter()
+        .chain(flow.returned_inner.iter())
+        .collect::<Vec<_>>();
+    let return_names = return_decls
+        .iter()
+        .map(|decl| decl.get_name())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // the function
+    let mut lines = Vec::new();
+    for decl in &flow.params {
+        if let Some(typ) = get_decl_doc_type(semantic_model, decl) {
+            lines.push(format!("---@param {} {}", decl.get_name(), typ));
+        }
+    }
+    let return_types = return_decls
+        .iter()
+        .map(|decl| get_decl_doc_type(semantic_model, decl))
+        .collect::<Vec<_>>();
+    if return_types.iter().any(|typ| typ.is_some()) {
+        for typ in return_types {
+            lines.push(format!(
+                "---@return {}",
+                typ.unwrap_or_else(|| "any".to_string())
+            ));
+        }
+    }
+    lines.push(format!("local function {}({})", name, param_names));
+    let base_indent = get_indent(&document, stats_range.start()).unwrap_or_default();
+    for line in document.get_text_slice(stats_range).lines() {
+        let line = line.strip_prefix(base_indent.as_str()).unwrap_or(line);
+        if line.trim().is_empty() {
+            lines.push(String::new());
+        } else {
+            lines.push(format!("    {}", line));
+        }
+    }
+    if !return_names.is_empty() {
+        lines.push(format!("    return {}", return_names));
+    }
+    lines.push("end".to_string());
+    let top_indent = get_indent(&document, insert_offset).unwrap_or_default();
+    let function_text = format!(
+        "{}\n\n{}",
+        lines.join(&format!("\n{}", top_indent)),
+        top_indent
+    );
+
+    // the call
+    let call = format!("{}({})", name, param_names);
+    let call_text = match (
+        flow.written_outer.is_empty(),
+        flow.returned_inner.is_empty(),
+    ) {
+        (true, true) => call,
+        (true, false) => format!("local {} = {}", return_names, call),
+        (false, true) => format!("{} = {}", return_names, call),
+        (false, false) => {
+            let inner_names = flow
+                .returned_inner
+                .iter()
+                .map(|decl| decl.get_name())
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "local {}\n{}{} = {}",
+                inner_names, base_indent, return_names, call
+            )
+        }
+    };
+
+    let edits = if insert_offset == stats_range.start() {
+        vec![TextEdit {
+            range: document.to_lsp_range(stats_range)?,
+            new_text: format!("{}{}", function_text, call_text),
+        }]
+    } else {
+        let insert_position = document.to_lsp_position(insert_offset)?;
+        vec![
+            TextEdit {
+                range: Range::new(insert_position, insert_position),
+                new_text: function_text,
+            },
+            TextEdit {
+                range: document.to_lsp_range(stats_range)?,
+                new_text: call_text,
+            },
+        ]
+    };
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Extract to function").to_string(),
+        kind: Some(CodeActionKind::REFACTOR_EXTRACT),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), edits)])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+/// `return`, `break`, `goto`, labels and `...` only keep their meaning inside the selected
+/// statements when they belong to a nested function or loop
+fn can_extract_stat(stat: &LuaSyntaxNode, block: &LuaSyntaxNode) -> bool {
+    for node_or_token in stat.descendants_with_tokens() {
+        let (in_closure, in_loop) = {
+            let mut in_closure = false;
+            let mut in_loop = false;
+            let parent = match &node_or_token {
+                NodeOrToken::Node(node) => node.parent(),
+                NodeOrToken::Token(token) => token.parent(),
+            };
+            for ancestor in parent.iter().flat_map(|parent| parent.ancestors()) {
+                if &ancestor == block {
+                    break;
+                }
+                match ancestor.kind().into() {
+                    LuaSyntaxKind::ClosureExpr => in_closure = true,
+                    LuaSyntaxKind::WhileStat
+                    | LuaSyntaxKind::RepeatStat
+                    | LuaSyntaxKind::ForStat
+                    | LuaSyntaxKind::ForRangeStat => in_loop = true,
+                    _ => {}
+                }
+            }
+            (in_closure, in_loop)
+        };
+
+        let can_extract = match &node_or_token {
+            NodeOrToken::Node(node) => match node.kind().into() {
+                LuaSyntaxKind::ReturnStat | LuaSyntaxKind::GotoStat | LuaSyntaxKind::LabelStat => {
+                    in_closure
+                }
+                LuaSyntaxKind::BreakStat => in_closure || in_loop,
+                _ => true,
+            },
+            NodeOrToken::Token(token) => token.kind() != LuaTokenKind::TkDots.into() || in_closure,
+        };
+        if !can_extract {
+            return false;
+        }
+    }
+
+    true
+}
+
+struct ExtractFlow<'a> {
+    /// locals read by the selection that the new function can not see
+    params: Vec<&'a LuaDecl>,
+    /// locals of those that the selection assigns, they are returned and assigned back
+    written_outer: Vec<&'a LuaDecl>,
+    /// locals declared by the selection and used after it
+    returned_inner: Vec<&'a LuaDecl>,
+}
+
+fn analyze_extract_flow<'a>(
+    semantic_model: &'a SemanticModel,
+    stats_range: TextRange,
+    insert_offset: TextSize,
+) -> Option<ExtractFlow<'a>> {
+    let db = semantic_model.get_db();
+    let file_id = semantic_model.get_file_id();
+    let decl_tree = db.get_decl_index().get_decl_tree(&file_id)?;
+    let references = db.get_reference_index().get_decl_references_map(&file_id)?;
+
+    let mut params = Vec::new();
+    let mut written_outer = Vec::new();
+    let mut returned_inner = Vec::new();
+    for (decl_id, decl_references) in references {
+        let Some(decl) = decl_tree.get_decl(decl_id) else {
+            continue;
+        };
+        if decl.is_global() {
+            continue;
+        }
+
+        if stats_range.contains_range(decl.get_range()) {
+            if decl_references
+                .cells
+                .iter()
+                .any(|cell| cell.range.start() >= stats_range.end())
+            {
+                returned_inner.push(decl);
+            }
+            continue;
+        }
+
+        let selection_cells = decl_references
+            .cells
+            .iter()
+            .filter(|cell| stats_range.contains_range(cell.range))
+            .collect::<Vec<_>>();
+        let Some(first_cell) = selection_cells.iter().map(|cell| cell.range.start()).min() else {
+            continue;
+        };
+        // upvalues of the new function
+        if decl.get_position() < insert_offset
+            && decl_tree
+                .find_local_decl(decl.get_name(), insert_offset)
+                .is_some_and(|visible_decl| visible_decl.get_id() == *decl_id)
+        {
+            continue;
+        }
+
+        params.push((first_cell, decl));
+        if selection_cells.iter().any(|cell| cell.is_write) {
+            written_outer.push((first_cell, decl));
+        }
+    }
+
+    params.sort_by_key(|(position, _)| *position);
+    written_outer.sort_by_key(|(position, _)| *position);
+    returned_inner.sort_by_key(|decl| decl.get_position());
+    Some(ExtractFlow {
+        params: params.into_iter().map(|(_, decl)| decl).collect(),
+        written_outer: written_outer.into_iter().map(|(_, decl)| decl).collect(),
+        returned_inner,
+    })
+}
+
+fn get_decl_doc_type(semantic_model: &SemanticModel, decl: &LuaDecl) -> Option<String> {
+    let db = semantic_model.get_db();
+    let typ = if decl.is_param() {
+        infer_param(db, decl).ok()?
+    } else {
+        semantic_model.get_type(decl.get_id().into())
+    };
+
+    humanize_doc_type(db, &typ)
+}
+
+fn humanize_doc_type(db: &DbIndex, typ: &LuaType) -> Option<String> {
+    let name = match typ {
+        LuaType::Unknown | LuaType::Any | LuaType::Nil => return None,
+        LuaType::IntegerConst(_) | LuaType::DocIntegerConst(_) => "integer".to_string(),
+        LuaType::FloatConst(_) => "number".to_string(),
+        LuaType::BooleanConst(_) | LuaType::DocBooleanConst(_) => "boolean".to_string(),
+        LuaType::StringConst(_) | LuaType::DocStringConst(_) => "string".to_string(),
+        LuaType::TableConst(_) => "table".to_string(),
+        _ => humanize_type(db, typ, RenderLevel::Simple),
+    };
+
+    Some(name)
+}
+
+/// The selected range without the surrounding whitespace
+fn get_selection(document: &LuaDocument, range: Range) -> Option<TextRange> {
+    let range = document.to_rowan_range(range)?;
+    let text = document.get_text_slice(range);
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let leading = text.len() - text.trim_start().len();
+    let start = range.start() + TextSize::from(leading as u32);
+    Some(TextRange::at(start, TextSize::of(trimmed)))
+}
+
+/// The whitespace before `offset` on its line, `None` when there is other text before it
+pub(super) fn get_indent(document: &LuaDocument, offset: TextSize) -> Option<String> {
+    let line = document.get_line(offset)?;
+    let line_start = document.get_line_range(line)?.start();
+    let prefix = document.get_text_slice(TextRange::new(line_start, offset));
+    prefix
+        .chars()
+        .all(|c| c == ' ' || c == '\t')
+        .then(|| prefix.to_string())
+}
+
+/// `base`, or `base` with a number when the file already uses that name
+pub(super) fn get_unique_name(semantic_model: &SemanticModel, base: &str) -> String {
+    let mut names = HashSet::new();
+    let file_id = semantic_model.get_file_id();
+    if let Some(decl_tree) = semantic_model
+        .get_db()
+        .get_decl_index()
+        .get_decl_tree(&file_id)
+    {
+        names.extend(
+            decl_tree
+                .get_decls()
+                .values()
+                .map(|decl| decl.get_name().to_string()),
+        );
+    }
+    names.extend(
+        semantic_model
+            .get_root()
+            .descendants::<LuaNameExpr>()
+            .filter_map(|name_expr| name_expr.get_name_text()),
+    );
+
+    if !names.contains(base) {
+        return base.to_string();
+    }
+    (1..)
+        .map(|i| format!("{}{}", base, i))
+        .find(|name| !names.contains(name))
+        .unwrap_or_else(|| base.to_string())
+}
@@ -1,10 +1,24 @@
 use std::collections::HashMap;
 
-use crate::handlers::command::make_auto_doc_tag_command;
-use emmylua_code_analysis::SemanticModel;
-use emmylua_parser::{LuaAstNode, LuaExpr};
+use super::build_extract_code::{get_indent, is_valid_name};
+use crate::{
+    handlers::{
+        code_actions::build_actions::mark_fix_all_safe,
+        command::{make_add_global_command, make_auto_doc_tag_command, make_auto_require},
+    },
+    util::module_name_convert,
+};
+use emmylua_code_analysis::{
+    LuaDocument, LuaFunctionType, LuaMemberKey, LuaType, LuaTypeDeclId, RenderLevel, SemanticModel,
+    VariadicType, check_export_visibility, humanize_type,
+};
+use emmylua_parser::{
+    LuaAst, LuaAstNode, LuaAstToken, LuaComment, LuaDocTagClass, LuaExpr, LuaFuncStat, LuaIfStat,
+    LuaStat, LuaSyntaxKind, LuaTableExpr, LuaTokenKind, LuaVarExpr,
+};
+use itertools::Itertools;
 use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit, WorkspaceEdit};
-use rowan::{NodeOrToken, TokenAtOffset};
+use rowan::{Direction, NodeOrToken, TextRange, TextSize, TokenAtOffset};
 
 pub fn build_need_check_nil(
     semantic_model: &SemanticModel,
@@ -75,3 +89,684 @@ pub fn build_add_doc_tag(
 
     Some(())
 }
+
+/// Insert the required fields the diagnostic reported, with a placeholder value of each
+/// field type
+pub fn build_fill_missing_fields(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+    data: &Option<serde_json::Value>,
+) -> Option<()> {
+    let names = data
+        .as_ref()?
+        .as_array()?
+        .iter()
+        .map(|name| name.as_str())
+        .collect::<Option<Vec<_>>>()?;
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(range.start.line as usize, range.start.character as usize)?;
+    let root = semantic_model.get_root();
+    let table_expr = root
+        .syntax()
+        .token_at_offset(offset)
+        .right_biased()?
+        .parent()
+        .and_then(LuaTableExpr::cast)?;
+    let table_type = match semantic_model.infer_table_should_be(table_expr.clone())? {
+        LuaType::Union(union) => union.into_vec().into_iter().find(|typ| {
+            matches!(
+                typ,
+                LuaType::Ref(_)
+                    | LuaType::Object(_)
+                    | LuaType::Generic(_)
+                    | LuaType::Intersection(_)
+            )
+        })?,
+        typ => typ,
+    };
+    let members = semantic_model
+        .get_member_info_map(&table_type)
+        .unwrap_or_default();
+
+    let mut visited = Vec::new();
+    let fields = names
+        .iter()
+        .map(|name| {
+            let value = members
+                .iter()
+                .find(|(key, _)| key.to_path() == *name)
+                .and_then(|(_, infos)| infos.first())
+                .map(|info| build_placeholder(semantic_model, &info.typ, &mut visited))
+                .unwrap_or_else(|| "nil".to_string());
+            format!("{} = {}", build_field_key(name), value)
+        })
+        .collect::<Vec<_>>();
+    let text_edit = build_fields_edit(&document, &table_expr, &fields)?;
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Fill missing fields").to_string(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), vec![text_edit])])),
+            ..Default::default()
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+fn build_field_key(name: &str) -> String {
+    // integer keys are already written as `[1]`
+    if name.starts_with('[') || is_valid_name(name) {
+        name.to_string()
+    } else {
+        format!("[{:?}]", name)
+    }
+}
+
+/// A value of the type, a class gets a constructor of its own required fields
+fn build_placeholder(
+    semantic_model: &SemanticModel,
+    typ: &LuaType,
+    visited: &mut Vec<LuaTypeDeclId>,
+) -> String {
+    match typ {
+        LuaType::String => "\"\"".to_string(),
+        LuaType::StringConst(s) | LuaType::DocStringConst(s) => format!("{:?}", s.as_str()),
+        LuaType::Integer | LuaType::Number => "0".to_string(),
+        LuaType::IntegerConst(i) | LuaType::DocIntegerConst(i) => i.to_string(),
+        LuaType::FloatConst(f) => f.to_string(),
+        LuaType::Boolean => "false".to_string(),
+        LuaType::BooleanConst(b) | LuaType::DocBooleanConst(b) => b.to_string(),
+        LuaType::Table
+        | LuaType::TableConst(_)
+        | LuaType::TableGeneric(_)
+        | LuaType::Array(_)
+        | LuaType::Tuple(_)
+        | LuaType::Object(_) => "{}".to_string(),
+        LuaType::Function | LuaType::DocFunction(_) | LuaType::Signature(_) => {
+            "function() end".to_string()
+        }
+        LuaType::Union(union) => match union.into_vec().first() {
+            Some(first) => build_placeholder(semantic_model, first, visited),
+            None => "nil".to_string(),
+        },
+        LuaType::Ref(type_decl_id) | LuaType::Def(type_decl_id) => {
+            let Some(type_decl) = semantic_model
+                .get_db()
+                .get_type_index()
+                .get_type_decl(type_decl_id)
+            else {
+                return "nil".to_string();
+            };
+            if type_decl.is_class() {
+                build_constructor(semantic_model, typ, type_decl_id, visited)
+            } else if let Some(origin) = type_decl.get_alias_origin(semantic_model.get_db(), None) {
+                build_placeholder(semantic_model, &origin, visited)
+            } else {
+                "nil".to_string()
+            }
+        }
+        LuaType::Generic(generic) => {
+            build_constructor(semantic_model, typ, generic.get_base_type_id_ref(), visited)
+        }
+        _ => "nil".to_string(),
+    }
+}
+
+fn build_constructor(
+    semantic_model: &SemanticModel,
+    typ: &LuaType,
+    type_decl_id: &LuaTypeDeclId,
+    visited: &mut Vec<LuaTypeDeclId>,
+) -> String {
+    // a class with a field of its own type stops at an empty table
+    if visited.contains(type_decl_id) {
+        return "{}".to_string();
+    }
+    let Some(members) = semantic_model.get_member_info_map(typ) else {
+        return "{}".to_string();
+    };
+
+    visited.push(type_decl_id.clone());
+    let fields = members
+        .iter()
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .filter_map(|(key, infos)| {
+            let name = key.to_path();
+            let typ = &infos.first()?.typ;
+            if name.is_empty() || typ.is_nullable() || typ.is_any() {
+                return None;
+            }
+            Some(format!(
+                "{} = {}",
+                build_field_key(&name),
+                build_placeholder(semantic_model, typ, visited)
+            ))
+        })
+        .collect::<Vec<_>>();
+    visited.pop();
+
+    if fields.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", fields.join(", "))
+    }
+}
+
+/// Append the fields in the layout of the table, one per line and with the same indent and
+/// trailing separator when the table spans several lines
+fn build_fields_edit(
+    document: &LuaDocument,
+    table_expr: &LuaTableExpr,
+    fields: &[String],
+) -> Option<TextEdit> {
+    let syntax = table_expr.syntax();
+    let l_brace = syntax.first_token()?;
+    let r_brace = syntax.last_token()?;
+    if r_brace.kind() != LuaTokenKind::TkRightBrace.into() {
+        return None;
+    }
+    let multi_line = document.get_line(l_brace.text_range().start())?
+        != document.get_line(r_brace.text_range().start())?;
+    let separators = syntax
+        .children_with_tokens()
+        .filter(|element| {
+            element.kind() == LuaTokenKind::TkComma.into()
+                || element.kind() == LuaTokenKind::TkSemicolon.into()
+        })
+        .collect::<Vec<_>>();
+
+    let Some(last_field) = table_expr.get_fields().last() else {
+        let (text, indent) = if multi_line {
+            let indent = get_line_indent(document, l_brace.text_range().start())?;
+            let fields_text = fields
+                .iter()
+                .map(|field| format!("\n{}    {},", indent, field))
+                .collect::<String>();
+            let r_indent = get_line_indent(document, r_brace.text_range().start())?;
+            (fields_text, format!("\n{}", r_indent))
+        } else {
+            (format!(" {}", fields.join(", ")), " ".to_string())
+        };
+        return Some(TextEdit {
+            range: document.to_lsp_range(TextRange::new(
+                l_brace.text_range().end(),
+                r_brace.text_range().start(),
+            ))?,
+            new_text: format!("{}{}", text, indent),
+        });
+    };
+
+    let trailing = separators
+        .iter()
+        .find(|separator| separator.text_range().start() >= last_field.get_range().end());
+    let separator = trailing
+        .or(separators.first())
+        .and_then(|separator| separator.as_token())
+        .map(|separator| separator.text().to_string())
+        .unwrap_or_else(|| ",".to_string());
+    let mut anchor = trailing
+        .map(|separator| separator.text_range().end())
+        .unwrap_or(last_field.get_range().end());
+    let field_prefix = if multi_line {
+        // a comment after the last field stays on its line
+        if let Some(comment) = syntax.children().find(|node| {
+            node.kind() == LuaSyntaxKind::Comment.into() && node.text_range().start() >= anchor
+        }) && document.get_line(comment.text_range().start()) == document.get_line(anchor)
+        {
+            anchor = comment.text_range().end();
+        }
+        format!(
+            "\n{}",
+            get_line_indent(document, last_field.get_position())?
+        )
+    } else {
+        " ".to_string()
+    };
+
+    let mut new_text = String::new();
+    if trailing.is_none() {
+        new_text.push_str(&separator);
+    }
+    let fields_text = fields
+        .iter()
+        .map(|field| format!("{}{}", field_prefix, field))
+        .join(&separator);
+    new_text.push_str(&fields_text);
+    if trailing.is_some() {
+        new_text.push_str(&separator);
+    }
+    let position = document.to_lsp_position(anchor)?;
+    Some(TextEdit {
+        range: Range::new(position, position),
+        new_text,
+    })
+}
+
+/// The whitespace the line of `offset` starts with
+fn get_line_indent(document: &LuaDocument, offset: TextSize) -> Option<String> {
+    let line_start = document.get_line_range(document.get_line(offset)?)?.start();
+    let text = document.get_text_slice(TextRange::new(line_start, offset));
+    Some(
+        text.chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect(),
+    )
+}
+
+/// Add a stub for each method of the super types the class leaves undefined, with the
+/// `---@param` and `---@return` docs of the declared signature
+pub fn build_implement_members(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+    data: &Option<serde_json::Value>,
+) -> Option<()> {
+    let names = data
+        .as_ref()?
+        .as_array()?
+        .iter()
+        .map(|name| name.as_str())
+        .collect::<Option<Vec<_>>>()?;
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(range.start.line as usize, range.start.character as usize)?;
+    let root = semantic_model.get_root();
+    let tag = root
+        .syntax()
+        .token_at_offset(offset)
+        .right_biased()?
+        .parent_ancestors()
+        .find_map(LuaDocTagClass::cast)?;
+    let class_stat = match tag.get_parent::<LuaComment>()?.get_owner()? {
+        LuaAst::LuaLocalStat(local_stat) => LuaStat::LocalStat(local_stat),
+        LuaAst::LuaAssignStat(assign_stat) => LuaStat::AssignStat(assign_stat),
+        _ => return None,
+    };
+    let class_name = match &class_stat {
+        LuaStat::LocalStat(local_stat) => local_stat
+            .get_local_name_list()
+            .next()?
+            .get_name_token()?
+            .get_name_text()
+            .to_string(),
+        LuaStat::AssignStat(assign_stat) => {
+            let (vars, _) = assign_stat.get_var_and_expr_list();
+            document
+                .get_text_slice(vars.first()?.get_range())
+                .to_string()
+        }
+        _ => return None,
+    };
+    let type_decl = semantic_model.get_db().get_type_index().find_type_decl(
+        semantic_model.get_file_id(),
+        tag.get_name_token()?.get_name_text(),
+    )?;
+    let self_type = LuaType::Ref(type_decl.get_id());
+
+    let indent = get_indent(&document, class_stat.get_position()).unwrap_or_default();
+    let mut stubs = String::new();
+    for name in names {
+        let func = semantic_model
+            .get_member_info_with_key(&self_type, LuaMemberKey::Name(name.into()), true)?
+            .into_iter()
+            .find_map(|info| match info.typ {
+                LuaType::DocFunction(func)
+                    if info.feature.is_some_and(|feature| feature.is_field_decl()) =>
+                {
+                    Some(func)
+                }
+                _ => None,
+            })?;
+        stubs.push_str(&build_method_stub(
+            semantic_model,
+            &class_name,
+            name,
+            &func,
+            &indent,
+        ));
+    }
+
+    // after the methods the class already defines next to it
+    let anchor = class_stat
+        .syntax()
+        .siblings(Direction::Next)
+        .filter_map(LuaFuncStat::cast)
+        .filter(|func_stat| {
+            let Some(LuaVarExpr::IndexExpr(index_expr)) = func_stat.get_func_name() else {
+                return false;
+            };
+            index_expr
+                .get_prefix_expr()
+                .is_some_and(|prefix| document.get_text_slice(prefix.get_range()) == class_name)
+        })
+        .last()
+        .map(|func_stat| func_stat.get_range().end())
+        .unwrap_or(class_stat.get_range().end());
+    let position = document.to_lsp_position(anchor)?;
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Implement missing methods").to_string(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(
+                document.get_uri(),
+                vec![TextEdit {
+                    range: Range::new(position, position),
+                    new_text: stubs,
+                }],
+            )])),
+            ..Default::default()
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+fn build_method_stub(
+    semantic_model: &SemanticModel,
+    class_name: &str,
+    name: &str,
+    func: &LuaFunctionType,
+    indent: &str,
+) -> String {
+    let db = semantic_model.get_db();
+    // the declared `self` becomes the implicit one of `:`
+    let params = if func.is_colon_define() {
+        func.get_params()
+    } else {
+        func.get_params().get(1..).unwrap_or_default()
+    };
+
+    let mut lines = Vec::new();
+    for (param_name, typ) in params {
+        if let Some(typ) = typ {
+            lines.push(format!(
+                "---@param {} {}",
+                param_name,
+                humanize_type(db, typ, RenderLevel::Simple)
+            ));
+        }
+    }
+    let returns = match func.get_variadic_ret() {
+        VariadicType::Base(LuaType::Nil | LuaType::Unknown) => Vec::new(),
+        VariadicType::Base(typ) => vec![typ],
+        VariadicType::Multi(types) => types,
+    };
+    for typ in returns {
+        lines.push(format!(
+            "---@return {}",
+            humanize_type(db, &typ, RenderLevel::Simple)
+        ));
+    }
+    lines.push(format!(
+        "function {}:{}({})",
+        class_name,
+        name,
+        params.iter().map(|(param_name, _)| param_name).join(", ")
+    ));
+    lines.push("    error(\"not implemented\")".to_string());
+    lines.push("end".to_string());
+
+    let mut stub = String::from("\n");
+    for line in lines {
+        stub.push_str(&format!("\n{}{}", indent, line));
+    }
+    stub
+}
+
+/// Add a branch for each value the `if` chain does not compare with, before its `else` or `end`
+pub fn build_add_missing_cases(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+    data: &Option<serde_json::Value>,
+) -> Option<()> {
+    let data = data.as_ref()?;
+    let var = data.get("var")?.as_str()?;
+    let cases = data
+        .get("cases")?
+        .as_array()?
+        .iter()
+        .map(|case| case.as_str())
+        .collect::<Option<Vec<_>>>()?;
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(range.start.line as usize, range.start.character as usize)?;
+    let root = semantic_model.get_root();
+    let if_stat = root
+        .syntax()
+        .token_at_offset(offset)
+        .right_biased()?
+        .parent_ancestors()
+        .find_map(LuaIfStat::cast)?;
+    let anchor = match if_stat.get_else_clause() {
+        Some(else_clause) => else_clause.get_position(),
+        None => if_stat.token_by_kind(LuaTokenKind::TkEnd)?.get_position(),
+    };
+    let indent = get_line_indent(&document, anchor)?;
+    let mut branches = String::new();
+    for case in cases {
+        branches.push_str(&format!(
+            "elseif {} == {} then\n{}    error(\"not implemented\")\n{}",
+            var, case, indent, indent
+        ));
+    }
+    // the branches go right before the `else` or `end`, which moves to a line of its own when
+    // the chain is written on one line
+    let line_start = document.get_line_range(document.get_line(anchor)?)?.start();
+    let prefix = document.get_text_slice(TextRange::new(line_start, anchor));
+    let (start, new_text) = if prefix.trim().is_empty() {
+        (anchor, branches)
+    } else {
+        let start = line_start + TextSize::from(prefix.trim_end().len() as u32);
+        (start, format!("\n{}{}", indent, branches))
+    };
+    let range = Range::new(
+        document.to_lsp_position(start)?,
+        document.to_lsp_position(anchor)?,
+    );
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Add missing cases").to_string(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(
+                document.get_uri(),
+                vec![TextEdit { range, new_text }],
+            )])),
+            ..Default::default()
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+/// The spelling suggestions the diagnostic carries, each replaces the name
+pub fn build_replace_similar_name(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+    data: &Option<serde_json::Value>,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    // a string key keeps its quotes, a name only takes the names that are valid there
+    let quote = match document
+        .get_text_slice(document.to_rowan_range(range)?)
+        .chars()
+        .next()
+    {
+        Some(quote @ ('"' | '\'')) => Some(quote),
+        Some('[') => Some('"'),
+        _ => None,
+    };
+    let replacements = data
+        .as_ref()?
+        .as_array()?
+        .iter()
+        .filter_map(|it| it.as_str())
+        .filter_map(|name| match quote {
+            Some(quote) if !name.contains([quote, '\\', '\n']) => {
+                Some((name, format!("{}{}{}", quote, name, quote)))
+            }
+            None if is_valid_name(name) => Some((name, name.to_string())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for (name, new_text) in &replacements {
+        actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+            title: t!("Change to `%{name}`", name = name).to_string(),
+            kind: Some(CodeActionKind::QUICKFIX),
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(
+                    document.get_uri(),
+                    vec![TextEdit {
+                        range,
+                        new_text: new_text.clone(),
+                    }],
+                )])),
+                ..Default::default()
+            }),
+            // only an unambiguous spelling is the preferred fix
+            is_preferred: Some(replacements.len() == 1),
+            ..Default::default()
+        }));
+    }
+
+    Some(())
+}
+
+/// Replace the index expression with the local alias the diagnostic names, when the alias is
+/// the local the name resolves to there
+pub fn build_use_local_alias(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+    data: &Option<serde_json::Value>,
+) -> Option<()> {
+    let data = data.as_ref()?;
+    let name = data.get("name")?.as_str()?;
+    let position = TextSize::from(data.get("position")?.as_u64()? as u32);
+    let document = semantic_model.get_document();
+    let offset = document.to_rowan_range(range)?.start();
+    let decl = semantic_model
+        .get_db()
+        .get_decl_index()
+        .get_decl_tree(&semantic_model.get_file_id())?
+        .find_local_decl(name, offset)?;
+    // 别名被同名的局部变量遮蔽时, 替换会改变含义
+    if decl.get_position() != position {
+        return None;
+    }
+
+    actions.push(CodeActionOrCommand::CodeAction(mark_fix_all_safe(
+        CodeAction {
+            title: t!("Use local alias `%{name}`", name = name).to_string(),
+            kind: Some(CodeActionKind::QUICKFIX),
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(
+                    document.get_uri(),
+                    vec![TextEdit {
+                        range,
+                        new_text: name.to_string(),
+                    }],
+                )])),
+                ..Default::default()
+            }),
+            is_preferred: Some(true),
+            ..Default::default()
+        },
+    )));
+
+    Some(())
+}
+
+pub fn build_add_global(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) -> Option<()> {
+    let name = get_range_text(semantic_model, range)?;
+    let title = t!("Add `%{name}` to diagnostics.globals", name = name);
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: title.to_string(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        command: Some(make_add_global_command(title.as_ref(), &name)),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+/// Require the modules whose local name or exported member is the undefined global
+pub fn build_require_global(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) -> Option<()> {
+    let name = get_range_text(semantic_model, range)?;
+    let emmyrc = semantic_model.get_emmyrc();
+    let file_conversion = emmyrc.completion.auto_require_naming_convention;
+    let version_number = emmyrc.runtime.version.to_lua_version_number();
+    let file_id = semantic_model.get_file_id();
+    let module_index = semantic_model.get_db().get_module_index();
+    for module_info in module_index.get_module_infos() {
+        if !module_info.is_visible(&version_number)
+            || module_info.file_id == file_id
+            || module_index.is_std(&module_info.file_id)
+            || !check_export_visibility(semantic_model, module_info).unwrap_or(false)
+        {
+            continue;
+        }
+        let Some(export_type) = &module_info.export_type else {
+            continue;
+        };
+
+        let member_name = if module_name_convert(module_info, file_conversion) == name {
+            None
+        } else if module_info.get_export(semantic_model.get_db()).is_some()
+            && matches!(export_type, LuaType::TableConst(_) | LuaType::Def(_))
+            && semantic_model
+                .get_member_infos(export_type)
+                .unwrap_or_default()
+                .iter()
+                .any(|member_info| member_info.key.to_path() == name)
+        {
+            Some(name.clone())
+        } else {
+            continue;
+        };
+
+        let title = t!(
+            "Require `%{name}` from `%{module}`",
+            name = name,
+            module = module_info.full_module_name
+        );
+        actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+            title: title.to_string(),
+            kind: Some(CodeActionKind::QUICKFIX),
+            command: Some(make_auto_require(
+                title.as_ref(),
+                file_id,
+                module_info.file_id,
+                range.start,
+                member_name,
+            )),
+            ..Default::default()
+        }));
+    }
+
+    Some(())
+}
+
+fn get_range_text(semantic_model: &SemanticModel, range: Range) -> Option<String> {
+    let document = semantic_model.get_document();
+    let range = document.to_rowan_range(range)?;
+    Some(document.get_text_slice(range).to_string())
+}
@@ -0,0 +1,107 @@
+use std::collections::{HashMap, HashSet};
+
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, LuaCompilation, SemanticModel};
+use emmylua_parser::{LuaAstNode, LuaStat, LuaVarExpr};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Position, TextEdit, WorkspaceEdit,
+};
+
+use crate::handlers::command::{
+    FunctionDefinition, build_class_annotations, build_function_annotations,
+    make_generate_annotations_command,
+};
+
+/// The docs of the function or table class defined at the cursor, along with the command doing
+/// the same for the whole file. The types of the parameters need their calls, the command of the
+/// function searches them when it runs.
+pub fn build_generate_annotations(
+    semantic_model: &SemanticModel,
+    compilation: &LuaCompilation,
+    actions: &mut Vec<CodeActionOrCommand>,
+    position: Position,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let title = t!("Generate annotations");
+    match FunctionDefinition::find(semantic_model, position) {
+        Some(definition) => {
+            build_function_annotations(semantic_model, compilation, &definition, false)?;
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: title.to_string(),
+                kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                command: Some(make_generate_annotations_command(
+                    title.as_ref(),
+                    Some(document.get_uri()),
+                    Some(position),
+                )),
+                ..Default::default()
+            }));
+        }
+        None => {
+            let stat = find_table_stat(semantic_model, position)?;
+            let text_edit = build_class_annotations(semantic_model, &stat, &mut HashSet::new())?;
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: title.to_string(),
+                kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(document.get_uri(), vec![text_edit])])),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }));
+        }
+    }
+
+    let title = t!("Generate annotations for the file");
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: title.to_string(),
+        kind: Some(CodeActionKind::SOURCE),
+        command: Some(make_generate_annotations_command(
+            title.as_ref(),
+            Some(document.get_uri()),
+            None,
+        )),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+/// The docs of the function or table class defined at `position`
+pub fn generate_definition_annotations(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+) -> Option<TextEdit> {
+    let compilation = &analysis.compilation;
+    let semantic_model = compilation.get_semantic_model(file_id)?;
+    match FunctionDefinition::find(&semantic_model, position) {
+        Some(definition) => {
+            build_function_annotations(&semantic_model, compilation, &definition, true)
+        }
+        None => {
+            let stat = find_table_stat(&semantic_model, position)?;
+            build_class_annotations(&semantic_model, &stat, &mut HashSet::new())
+        }
+    }
+}
+
+/// `local T = {}` or `T = {}` with the cursor on `T`
+fn find_table_stat(semantic_model: &SemanticModel, position: Position) -> Option<LuaStat> {
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(position.line as usize, position.character as usize)?;
+    let root = semantic_model.get_root();
+    let token = root.syntax().token_at_offset(offset).right_biased()?;
+    let stat = token.parent_ancestors().find_map(LuaStat::cast)?;
+    let name_range = match &stat {
+        LuaStat::LocalStat(local_stat) => local_stat.get_local_name_list().next()?.get_range(),
+        LuaStat::AssignStat(assign_stat) => match assign_stat.get_var_and_expr_list().0.first()? {
+            LuaVarExpr::NameExpr(name_expr) => name_expr.get_range(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    if !name_range.contains(offset) && name_range.end() != offset {
+        return None;
+    }
+    Some(stat)
+}
@@ -0,0 +1,669 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{DeclReference, LuaDecl, LuaDeclId, SemanticModel};
+use emmylua_parser::{
+    LuaAstNode, LuaBinaryExpr, LuaBlock, LuaCallExpr, LuaCallExprStat, LuaClosureExpr,
+    LuaCommentOwner, LuaExpr, LuaIndexExpr, LuaLocalFuncStat, LuaLocalName, LuaLocalStat,
+    LuaNameExpr, LuaStat, LuaSyntaxKind, LuaSyntaxNode, LuaSyntaxToken, LuaTableExpr, LuaTokenKind,
+    UNARY_PRIORITY,
+};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Position, TextEdit, WorkspaceEdit,
+};
+use rowan::{Direction, NodeOrToken, TextRange, TextSize, TokenAtOffset};
+
+use super::build_extract_code::get_indent;
+
+pub fn build_inline_variable(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    position: Position,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let root = semantic_model.get_root();
+    let token = get_name_token(semantic_model, position)?;
+    let decl = get_local_decl(semantic_model, &token)?;
+    let local_name = LuaLocalName::cast(decl.get_syntax_id().to_node_from_root(root.syntax())?)?;
+    if local_name
+        .get_attrib()
+        .is_some_and(|attrib| attrib.is_close())
+    {
+        return None;
+    }
+    let local_stat = local_name.get_parent::<LuaLocalStat>()?;
+    let mut value_exprs = local_stat.get_value_exprs();
+    let value = value_exprs.next()?;
+    if value_exprs.next().is_some() || local_stat.get_local_name_list().count() != 1 {
+        return None;
+    }
+
+    let references = get_decl_references(semantic_model, &decl.get_id())?;
+    if references.mutable || references.cells.is_empty() {
+        return None;
+    }
+    let uses = references
+        .cells
+        .iter()
+        .map(|cell| find_name_expr(root.syntax(), cell.range))
+        .collect::<Option<Vec<_>>>()?;
+
+    // a value with side effects or its own identity is only moved, never copied. Neither it nor
+    // a field or global read, which a call or assignment may change, moves past other code
+    let is_stable = is_pure_expr(&value) && !reads_shared_state(semantic_model, &value);
+    if !is_stable && (uses.len() > 1 || !is_evaluated_next(&local_stat, uses.first()?.syntax())) {
+        return None;
+    }
+    let block = local_stat.syntax().parent()?;
+    for name_expr in &uses {
+        if !can_move_node(
+            semantic_model,
+            value.syntax(),
+            value.get_range(),
+            name_expr.syntax(),
+        ) || (!is_stable && is_repeated(name_expr.syntax(), &block))
+        {
+            return None;
+        }
+    }
+    let last_use = uses
+        .iter()
+        .map(|name_expr| name_expr.get_range().end())
+        .max()?;
+    if is_written_in(
+        semantic_model,
+        value.syntax(),
+        TextRange::new(local_stat.get_range().end(), last_use),
+    ) {
+        return None;
+    }
+
+    let value_text = document.get_text_slice(value.get_range());
+    let mut edits = vec![TextEdit {
+        range: document.to_lsp_range(get_stat_removal_range(
+            semantic_model,
+            &LuaStat::LocalStat(local_stat),
+        )?)?,
+        new_text: String::new(),
+    }];
+    for name_expr in &uses {
+        edits.push(TextEdit {
+            range: document.to_lsp_range(name_expr.get_range())?,
+            new_text: wrap_expr_text(&value, value_text, name_expr.syntax()),
+        });
+    }
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Inline variable").to_string(),
+        kind: Some(CodeActionKind::REFACTOR_INLINE),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), edits)])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+pub fn build_inline_function(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    position: Position,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let root = semantic_model.get_root();
+    let token = get_name_token(semantic_model, position)?;
+    let name_expr = LuaNameExpr::cast(token.parent()?)?;
+    let call_expr = name_expr.get_parent::<LuaCallExpr>()?;
+    if call_expr.get_prefix_expr()?.syntax() != name_expr.syntax() {
+        return None;
+    }
+    let decl = get_local_decl(semantic_model, &token)?;
+    if get_decl_references(semantic_model, &decl.get_id())?.mutable {
+        return None;
+    }
+
+    let decl_node = decl.get_syntax_id().to_node_from_root(root.syntax())?;
+    let closure = match decl_node.parent().and_then(LuaLocalFuncStat::cast) {
+        Some(local_func_stat) => local_func_stat.get_closure()?,
+        None => LuaClosureExpr::cast(
+            decl.get_value_syntax_id()?
+                .to_node_from_root(root.syntax())?,
+        )?,
+    };
+    let params = closure.get_params_list()?.get_params().collect::<Vec<_>>();
+    if params.iter().any(|param| param.is_dots()) {
+        return None;
+    }
+    let args = call_expr.get_args_list()?.get_args().collect::<Vec<_>>();
+    // a call in the last argument may fill several parameters
+    if args.len() > params.len()
+        || (args.len() < params.len() && matches!(args.last(), Some(LuaExpr::CallExpr(_))))
+    {
+        return None;
+    }
+    let block = closure.get_block()?;
+    let stats = block.get_stats().collect::<Vec<_>>();
+    let (first_stat, last_stat) = (stats.first()?, stats.last()?);
+    let body_range = TextRange::new(first_stat.get_position(), last_stat.get_range().end());
+    if !can_move_node(
+        semantic_model,
+        block.syntax(),
+        closure.get_range(),
+        call_expr.syntax(),
+    ) {
+        return None;
+    }
+    let inline_call = InlineCall {
+        semantic_model,
+        closure: &closure,
+        params: params
+            .iter()
+            .map(|param| param.get_position())
+            .collect::<Vec<_>>(),
+        args: &args,
+    };
+
+    let (range, new_text) = match call_expr.get_parent::<LuaCallExprStat>() {
+        Some(call_stat) => {
+            if !is_nested_return_only(&block) {
+                return None;
+            }
+            let indent = get_indent(&document, call_stat.get_position()).unwrap_or_default();
+            let body_indent = get_indent(&document, body_range.start()).unwrap_or_default();
+            let has_locals = stats
+                .iter()
+                .any(|stat| matches!(stat, LuaStat::LocalStat(_) | LuaStat::LocalFuncStat(_)));
+            let new_text = match inline_call.substitute(body_range, false) {
+                Some(body) if !has_locals => reindent(&body, &body_indent, &indent),
+                Some(body) => format!(
+                    "do\n{}    {}\n{}end",
+                    indent,
+                    reindent(&body, &body_indent, &format!("{}    ", indent)),
+                    indent
+                ),
+                // the arguments are evaluated once, before the body
+                None => {
+                    let mut lines = vec!["do".to_string()];
+                    if !params.is_empty() {
+                        let param_names = params
+                            .iter()
+                            .filter_map(|param| {
+                                Some(param.get_name_token()?.get_name_text().to_string())
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let arg_texts = args
+                            .iter()
+                            .map(|arg| document.get_text_slice(arg.get_range()))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        if args.is_empty() {
+                            lines.push(format!("    local {}", param_names));
+                        } else {
+                            lines.push(format!("    local {} = {}", param_names, arg_texts));
+                        }
+                    }
+                    let body = document.get_text_slice(body_range);
+                    lines.push(format!(
+                        "    {}",
+                        reindent(body, &body_indent, &format!("{}    ", indent))
+                    ));
+                    lines.push("end".to_string());
+                    lines.join(&format!("\n{}", indent))
+                }
+            };
+            (call_stat.get_range(), new_text)
+        }
+        None => {
+            // only a function returning a single expression fits in an expression
+            let [LuaStat::ReturnStat(return_stat)] = stats.as_slice() else {
+                return None;
+            };
+            let mut exprs = return_stat.get_expr_list();
+            let return_expr = exprs.next()?;
+            if exprs.next().is_some() {
+                return None;
+            }
+            let text = inline_call.substitute(return_expr.get_range(), true)?;
+            let new_text = if needs_parens(&return_expr, call_expr.syntax()) {
+                format!("({})", text)
+            } else {
+                text
+            };
+            (call_expr.get_range(), new_text)
+        }
+    };
+
+    let edits = vec![TextEdit {
+        range: document.to_lsp_range(range)?,
+        new_text,
+    }];
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Inline function").to_string(),
+        kind: Some(CodeActionKind::REFACTOR_INLINE),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), edits)])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+struct InlineCall<'a> {
+    semantic_model: &'a SemanticModel<'a>,
+    closure: &'a LuaClosureExpr,
+    /// the positions of the parameter declarations
+    params: Vec<TextSize>,
+    args: &'a [LuaExpr],
+}
+
+impl InlineCall<'_> {
+    /// The text in `range` of the function body with the parameters replaced by the arguments,
+    /// `None` when an argument can not take the place of its parameter
+    fn substitute(&self, range: TextRange, allow_impure: bool) -> Option<String> {
+        let document = self.semantic_model.get_document();
+        let db = self.semantic_model.get_db();
+        let file_id = self.semantic_model.get_file_id();
+        let decl_tree = db.get_decl_index().get_decl_tree(&file_id)?;
+
+        let mut uses: Vec<Vec<LuaNameExpr>> = vec![Vec::new(); self.params.len()];
+        for name_expr in self.closure.descendants::<LuaNameExpr>() {
+            if !range.contains_range(name_expr.get_range()) {
+                continue;
+            }
+            let Some(decl_id) = db
+                .get_reference_index()
+                .get_var_reference_decl(&file_id, name_expr.get_range())
+            else {
+                continue;
+            };
+            if let Some(idx) = self
+                .params
+                .iter()
+                .position(|position| *position == decl_id.position)
+            {
+                uses[idx].push(name_expr);
+            }
+        }
+
+        let mut edits = Vec::new();
+        for (idx, param_uses) in uses.iter().enumerate() {
+            let param_decl_id = LuaDeclId::new(file_id, self.params[idx]);
+            if get_decl_references(self.semantic_model, &param_decl_id)
+                .is_some_and(|references| references.mutable)
+            {
+                return None;
+            }
+            let Some(arg) = self.args.get(idx) else {
+                edits.extend(
+                    param_uses
+                        .iter()
+                        .map(|name_expr| (name_expr.get_range(), "nil".to_string())),
+                );
+                continue;
+            };
+            if !is_pure_expr(arg)
+                && (!allow_impure
+                    || param_uses.len() != 1
+                    || param_uses
+                        .iter()
+                        .any(|name_expr| is_repeated(name_expr.syntax(), self.closure.syntax())))
+            {
+                return None;
+            }
+
+            let arg_text = document.get_text_slice(arg.get_range());
+            for name_expr in param_uses {
+                // the names of the argument must not be hidden by the locals of the body
+                let is_shadowed = arg.descendants::<LuaNameExpr>().any(|arg_name| {
+                    arg_name.get_name_text().is_some_and(|name| {
+                        decl_tree
+                            .find_local_decl(&name, name_expr.get_position())
+                            .is_some_and(|decl| {
+                                self.closure.get_range().contains(decl.get_position())
+                                    && !self.params.contains(&decl.get_position())
+                            })
+                    })
+                });
+                if is_shadowed {
+                    return None;
+                }
+                edits.push((
+                    name_expr.get_range(),
+                    wrap_expr_text(arg, arg_text, name_expr.syntax()),
+                ));
+            }
+        }
+
+        let mut text = document.get_text_slice(range).to_string();
+        edits.sort_by_key(|(edit_range, _)| std::cmp::Reverse(edit_range.start()));
+        for (edit_range, new_text) in edits {
+            let edit_range = edit_range - range.start();
+            text.replace_range(
+                usize::from(edit_range.start())..usize::from(edit_range.end()),
+                &new_text,
+            );
+        }
+
+        Some(text)
+    }
+}
+
+/// Whether every `return` in the function body belongs to a nested function, a call statement
+/// can not take the place of any other
+fn is_nested_return_only(block: &LuaBlock) -> bool {
+    block
+        .syntax()
+        .descendants()
+        .filter(|node| node.kind() == LuaSyntaxKind::ReturnStat.into())
+        .all(|node| {
+            node.ancestors()
+                .take_while(|ancestor| ancestor != block.syntax())
+                .any(|ancestor| ancestor.kind() == LuaSyntaxKind::ClosureExpr.into())
+        })
+}
+
+fn get_name_token(semantic_model: &SemanticModel, position: Position) -> Option<LuaSyntaxToken> {
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(position.line as usize, position.character as usize)?;
+    let root = semantic_model.get_root();
+    if offset > root.syntax().text_range().end() {
+        return None;
+    }
+
+    let token = match root.syntax().token_at_offset(offset) {
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(left, right) => {
+            if left.kind() == LuaTokenKind::TkName.into() {
+                left
+            } else {
+                right
+            }
+        }
+        TokenAtOffset::None => return None,
+    };
+    (token.kind() == LuaTokenKind::TkName.into()).then_some(token)
+}
+
+/// The local declared or read by the name token, parameters excluded
+fn get_local_decl<'a>(
+    semantic_model: &'a SemanticModel,
+    token: &LuaSyntaxToken,
+) -> Option<&'a LuaDecl> {
+    let db = semantic_model.get_db();
+    let file_id = semantic_model.get_file_id();
+    let parent = token.parent()?;
+    let decl_id = match LuaLocalName::cast(parent.clone()) {
+        Some(local_name) => LuaDeclId::new(file_id, local_name.get_position()),
+        None => db
+            .get_reference_index()
+            .get_var_reference_decl(&file_id, LuaNameExpr::cast(parent)?.get_range())?,
+    };
+    let decl = db.get_decl_index().get_decl(&decl_id)?;
+    (decl.is_local() && !decl.is_param()).then_some(decl)
+}
+
+fn get_decl_references<'a>(
+    semantic_model: &'a SemanticModel,
+    decl_id: &LuaDeclId,
+) -> Option<&'a DeclReference> {
+    semantic_model
+        .get_db()
+        .get_reference_index()
+        .get_decl_references(&decl_id.file_id, decl_id)
+}
+
+fn find_name_expr(root: &LuaSyntaxNode, range: TextRange) -> Option<LuaNameExpr> {
+    let node = match root.covering_element(range) {
+        NodeOrToken::Node(node) => node,
+        NodeOrToken::Token(token) => token.parent()?,
+    };
+    node.ancestors()
+        .filter_map(LuaNameExpr::cast)
+        .find(|name_expr| name_expr.get_range() == range)
+}
+
+/// The range removed with the statement: its comment, its indent and its line break
+pub(super) fn get_stat_removal_range(
+    semantic_model: &SemanticModel,
+    stat: &LuaStat,
+) -> Option<TextRange> {
+    let document = semantic_model.get_document();
+    let mut start = match stat.get_left_comment() {
+        Some(comment) => comment.get_position(),
+        None => stat.get_position(),
+    };
+    let mut end = stat.get_range().end();
+    if get_indent(&document, start).is_some() {
+        let line = document.get_line(end)?;
+        let line_range = document.get_line_range(line)?;
+        let rest = document.get_text_slice(TextRange::new(end, line_range.end()));
+        if rest.trim().is_empty() {
+            start = document.get_line_range(document.get_line(start)?)?.start();
+            end = line_range.end();
+        }
+    }
+
+    Some(TextRange::new(start, end))
+}
+
+/// Whether evaluating `expr` has no side effect and gives no new object
+fn is_pure_expr(expr: &LuaExpr) -> bool {
+    !expr.syntax().descendants().any(|node| {
+        let kind = node.kind().into();
+        LuaCallExpr::can_cast(kind)
+            || LuaTableExpr::can_cast(kind)
+            || kind == LuaSyntaxKind::ClosureExpr
+    })
+}
+
+/// Whether `expr` reads a field or a global, whose value other code may change
+fn reads_shared_state(semantic_model: &SemanticModel, expr: &LuaExpr) -> bool {
+    let db = semantic_model.get_db();
+    let file_id = semantic_model.get_file_id();
+    let decl_tree = db.get_decl_index().get_decl_tree(&file_id);
+    expr.syntax().descendants().any(|node| {
+        if LuaIndexExpr::can_cast(node.kind().into()) {
+            return true;
+        }
+        let Some(name_expr) = LuaNameExpr::cast(node) else {
+            return false;
+        };
+        db.get_reference_index()
+            .get_var_reference_decl(&file_id, name_expr.get_range())
+            .and_then(|decl_id| decl_tree?.get_decl(&decl_id))
+            .is_none_or(|decl| decl.is_global())
+    })
+}
+
+/// Whether `node` is the first thing evaluated after `stat`: it is in the next statement, and no
+/// statement or call of that statement runs before it
+fn is_evaluated_next(stat: &LuaLocalStat, node: &LuaSyntaxNode) -> bool {
+    let Some(next_stat) = stat
+        .syntax()
+        .siblings(Direction::Next)
+        .skip(1)
+        .find_map(LuaStat::cast)
+    else {
+        return false;
+    };
+    if !next_stat.get_range().contains_range(node.text_range()) {
+        return false;
+    }
+    let start = node.text_range().start();
+    !next_stat.syntax().descendants().any(|descendant| {
+        let kind = descendant.kind().into();
+        (LuaStat::can_cast(kind) || LuaCallExpr::can_cast(kind))
+            && descendant.text_range().end() <= start
+    })
+}
+
+/// Whether `node` may run several times for one run of `outer`
+fn is_repeated(node: &LuaSyntaxNode, outer: &LuaSyntaxNode) -> bool {
+    node.ancestors()
+        .take_while(|ancestor| ancestor != outer)
+        .any(|ancestor| {
+            matches!(
+                ancestor.kind().into(),
+                LuaSyntaxKind::WhileStat
+                    | LuaSyntaxKind::RepeatStat
+                    | LuaSyntaxKind::ForStat
+                    | LuaSyntaxKind::ForRangeStat
+                    | LuaSyntaxKind::ClosureExpr
+            )
+        })
+}
+
+/// Whether the names read in `node` still mean the same at `target`, the names declared
+/// in `scope` are moved with it
+fn can_move_node(
+    semantic_model: &SemanticModel,
+    node: &LuaSyntaxNode,
+    scope: TextRange,
+    target: &LuaSyntaxNode,
+) -> bool {
+    let db = semantic_model.get_db();
+    let file_id = semantic_model.get_file_id();
+    let Some(decl_tree) = db.get_decl_index().get_decl_tree(&file_id) else {
+        return false;
+    };
+    let target_closure = target.ancestors().find_map(LuaClosureExpr::cast);
+
+    for node_or_token in node.descendants_with_tokens() {
+        match node_or_token {
+            NodeOrToken::Token(token) if token.kind() == LuaTokenKind::TkDots.into() => {
+                let closure = token.parent_ancestors().find_map(LuaClosureExpr::cast);
+                let is_moved = closure
+                    .as_ref()
+                    .is_some_and(|closure| scope.contains_range(closure.get_range()));
+                if !is_moved && closure != target_closure {
+                    return false;
+                }
+            }
+            NodeOrToken::Node(node) => {
+                let Some(name_expr) = LuaNameExpr::cast(node) else {
+                    continue;
+                };
+                let Some(name) = name_expr.get_name_text() else {
+                    continue;
+                };
+                let original = db
+                    .get_reference_index()
+                    .get_var_reference_decl(&file_id, name_expr.get_range())
+                    .and_then(|decl_id| decl_tree.get_decl(&decl_id))
+                    .filter(|decl| !decl.is_global())
+                    .map(|decl| decl.get_id());
+                if original.is_some_and(|decl_id| scope.contains(decl_id.position)) {
+                    continue;
+                }
+                let visible = decl_tree
+                    .find_local_decl(&name, target.text_range().start())
+                    .filter(|decl| !decl.is_global())
+                    .map(|decl| decl.get_id());
+                if visible != original {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    true
+}
+
+/// Whether a local read in `node` is assigned in `range`
+fn is_written_in(semantic_model: &SemanticModel, node: &LuaSyntaxNode, range: TextRange) -> bool {
+    let db = semantic_model.get_db();
+    let file_id = semantic_model.get_file_id();
+    node.descendants()
+        .filter_map(LuaNameExpr::cast)
+        .any(|name_expr| {
+            db.get_reference_index()
+                .get_var_reference_decl(&file_id, name_expr.get_range())
+                .and_then(|decl_id| get_decl_references(semantic_model, &decl_id))
+                .is_some_and(|references| {
+                    references
+                        .cells
+                        .iter()
+                        .any(|cell| cell.is_write && range.contains_range(cell.range))
+                })
+        })
+}
+
+/// The text of `expr` to put in the place of `target`
+fn wrap_expr_text(expr: &LuaExpr, text: &str, target: &LuaSyntaxNode) -> String {
+    // `f()` gives a single value where `x` was read, even as the last argument
+    let is_truncated = matches!(expr, LuaExpr::CallExpr(_)) && is_multi_value_position(target);
+    if is_truncated || needs_parens(expr, target) {
+        format!("({})", text)
+    } else {
+        text.to_string()
+    }
+}
+
+fn needs_parens(expr: &LuaExpr, target: &LuaSyntaxNode) -> bool {
+    let Some(parent) = target.parent() else {
+        return false;
+    };
+    let is_prefix = LuaIndexExpr::cast(parent.clone())
+        .and_then(|index_expr| index_expr.get_prefix_expr())
+        .or_else(|| LuaCallExpr::cast(parent.clone())?.get_prefix_expr())
+        .is_some_and(|prefix_expr| prefix_expr.syntax() == target);
+    let priority = match expr {
+        LuaExpr::BinaryExpr(binary_expr) => match binary_expr.get_op_token() {
+            Some(op_token) => op_token.get_op().get_priority().left,
+            None => return true,
+        },
+        LuaExpr::UnaryExpr(_) => UNARY_PRIORITY,
+        LuaExpr::LiteralExpr(_) | LuaExpr::TableExpr(_) | LuaExpr::ClosureExpr(_) => {
+            return is_prefix;
+        }
+        _ => return false,
+    };
+    if is_prefix {
+        return true;
+    }
+
+    match LuaBinaryExpr::cast(parent.clone()) {
+        Some(parent_expr) => parent_expr
+            .get_op_token()
+            .is_none_or(|op_token| priority <= op_token.get_op().get_priority().left),
+        // `- -x` must not become a comment
+        None => parent.kind() == LuaSyntaxKind::UnaryExpr.into(),
+    }
+}
+
+/// Whether all the values of a call at `target` are used
+pub(super) fn is_multi_value_position(target: &LuaSyntaxNode) -> bool {
+    let Some(parent) = target.parent() else {
+        return false;
+    };
+    match parent.kind().into() {
+        LuaSyntaxKind::CallArgList
+        | LuaSyntaxKind::ReturnStat
+        | LuaSyntaxKind::LocalStat
+        | LuaSyntaxKind::AssignStat => target.next_sibling().is_none(),
+        LuaSyntaxKind::TableFieldValue => parent.next_sibling().is_none(),
+        _ => false,
+    }
+}
+
+/// `text` with the indent of its following lines changed from `from` to `to`
+fn reindent(text: &str, from: &str, to: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                line.to_string()
+            } else if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", to, line.strip_prefix(from).unwrap_or(line))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,612 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use emmylua_code_analysis::{
+    EmmyLuaAnalysis, FileId, LuaCompilation, LuaDeclId, LuaSemanticDeclId, SemanticDeclLevel,
+    SemanticModel,
+};
+use emmylua_parser::{
+    LuaAstNode, LuaCallExpr, LuaCommentOwner, LuaExpr, LuaIndexExpr, LuaIndexKey, LuaLiteralToken,
+    LuaLocalStat, LuaNameExpr, LuaStat, LuaVarExpr,
+};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Position, TextEdit, Uri, WorkspaceEdit,
+};
+use rowan::TextRange;
+
+use crate::{handlers::command::make_move_symbol_command, util::module_name_convert};
+
+use super::{
+    build_extract_code::{get_indent, get_unique_name},
+    build_inline_code::get_stat_removal_range,
+};
+
+/// The command carries the symbol, the user picks the target module when it runs. It is offered
+/// when the main workspace has another module.
+pub fn build_move_symbol(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    position: Position,
+) -> Option<()> {
+    let symbol = ModuleMember::find(semantic_model, position)?;
+    let file_id = semantic_model.get_file_id();
+    let module_index = semantic_model.get_db().get_module_index();
+    if !module_index
+        .get_module_infos()
+        .iter()
+        .any(|info| info.file_id != file_id && module_index.is_main(&info.file_id))
+    {
+        return None;
+    }
+    let title = t!("Move `%{name}` to another module", name = symbol.name);
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: title.to_string(),
+        kind: Some(CodeActionKind::REFACTOR),
+        command: Some(make_move_symbol_command(
+            title.as_ref(),
+            semantic_model.get_file_id(),
+            position,
+        )),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+/// A member of the module table defined by a top-level statement, like `function M.f() end`
+/// or `M.x = 1`
+struct ModuleMember {
+    stat: LuaStat,
+    var: LuaIndexExpr,
+    name: String,
+}
+
+impl ModuleMember {
+    fn find(semantic_model: &SemanticModel, position: Position) -> Option<Self> {
+        let document = semantic_model.get_document();
+        let offset = document.get_offset(position.line as usize, position.character as usize)?;
+        let root = semantic_model.get_root();
+        let token = root.syntax().token_at_offset(offset).right_biased()?;
+        let stat = token.parent_ancestors().find_map(LuaStat::cast)?;
+        if stat.syntax().parent()? != *root.get_block()?.syntax() {
+            return None;
+        }
+
+        let var = match &stat {
+            LuaStat::FuncStat(func_stat) => match func_stat.get_func_name()? {
+                LuaVarExpr::IndexExpr(index_expr) => index_expr,
+                _ => return None,
+            },
+            LuaStat::AssignStat(assign_stat) => {
+                let (vars, exprs) = assign_stat.get_var_and_expr_list();
+                match (vars.as_slice(), exprs.len()) {
+                    ([LuaVarExpr::IndexExpr(index_expr)], 1) => index_expr.clone(),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        if !var.get_range().contains(offset) && var.get_range().end() != offset {
+            return None;
+        }
+        let LuaIndexKey::Name(name_token) = var.get_index_key()? else {
+            return None;
+        };
+        let LuaExpr::NameExpr(prefix) = var.get_prefix_expr()? else {
+            return None;
+        };
+        if find_local_decl(semantic_model, &prefix)? != get_module_table(semantic_model)?.0 {
+            return None;
+        }
+
+        Some(Self {
+            stat,
+            name: name_token.get_name_text().to_string(),
+            var,
+        })
+    }
+
+    /// The statement with its doc comments
+    fn get_range(&self) -> TextRange {
+        let start = match self.stat.get_left_comment() {
+            Some(comment) => comment.get_position(),
+            None => self.stat.get_position(),
+        };
+        TextRange::new(start, self.stat.get_range().end())
+    }
+}
+
+/// The local returned by the module, like `M` of `return M`, and its name
+fn get_module_table(semantic_model: &SemanticModel) -> Option<(LuaDeclId, String)> {
+    let block = semantic_model.get_root().get_block()?;
+    let LuaStat::ReturnStat(return_stat) = block.get_stats().last()? else {
+        return None;
+    };
+    let mut exprs = return_stat.get_expr_list();
+    let LuaExpr::NameExpr(name_expr) = exprs.next()? else {
+        return None;
+    };
+    if exprs.next().is_some() {
+        return None;
+    }
+    let decl_id = find_local_decl(semantic_model, &name_expr)?;
+    Some((decl_id, name_expr.get_name_text()?))
+}
+
+fn find_local_decl(semantic_model: &SemanticModel, name_expr: &LuaNameExpr) -> Option<LuaDeclId> {
+    match semantic_model.find_decl(
+        name_expr.syntax().clone().into(),
+        SemanticDeclLevel::NoTrace,
+    )? {
+        LuaSemanticDeclId::LuaDecl(decl_id) => Some(decl_id),
+        _ => None,
+    }
+}
+
+/// The file required by `require("a.b")`
+fn get_required_file(semantic_model: &SemanticModel, call_expr: &LuaCallExpr) -> Option<FileId> {
+    if !call_expr.is_require() {
+        return None;
+    }
+    let arg = call_expr.get_args_list()?.get_args().next()?;
+    let LuaExpr::LiteralExpr(literal) = arg else {
+        return None;
+    };
+    let LuaLiteralToken::String(path) = literal.get_literal()? else {
+        return None;
+    };
+    let emmyrc = semantic_model.get_emmyrc();
+    let path = path
+        .get_value()
+        .replace(&emmyrc.completion.auto_require_separator, ".");
+    semantic_model
+        .get_db()
+        .get_module_index()
+        .find_module(&path)
+        .map(|module_info| module_info.file_id)
+}
+
+/// The top-level `local x = require("...")` statements of the file with the required file
+fn get_require_stats(semantic_model: &SemanticModel) -> Vec<(LuaLocalStat, LuaDeclId, FileId)> {
+    let Some(block) = semantic_model.get_root().get_block() else {
+        return Vec::new();
+    };
+    block
+        .get_stats()
+        .filter_map(|stat| {
+            let LuaStat::LocalStat(local_stat) = stat else {
+                return None;
+            };
+            let mut names = local_stat.get_local_name_list();
+            let name = names.next()?;
+            let mut values = local_stat.get_value_exprs();
+            let LuaExpr::CallExpr(call_expr) = values.next()? else {
+                return None;
+            };
+            if names.next().is_some() || values.next().is_some() {
+                return None;
+            }
+            let file_id = get_required_file(semantic_model, &call_expr)?;
+            let decl_id = LuaDeclId::new(semantic_model.get_file_id(), name.get_position());
+            Some((local_stat, decl_id, file_id))
+        })
+        .collect()
+}
+
+/// The modules of the main workspace the member defined at `position` can move to, sorted by
+/// path
+pub fn get_move_targets(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+) -> Vec<String> {
+    let compilation = &analysis.compilation;
+    let Some(semantic_model) = compilation.get_semantic_model(file_id) else {
+        return Vec::new();
+    };
+    let Some(member) = ModuleMember::find(&semantic_model, position) else {
+        return Vec::new();
+    };
+    let module_index = compilation.get_db().get_module_index();
+    let mut targets = module_index
+        .get_module_infos()
+        .into_iter()
+        .filter(|info| info.file_id != file_id && module_index.is_main(&info.file_id))
+        .filter(|info| {
+            compilation
+                .get_semantic_model(info.file_id)
+                .and_then(|target_model| {
+                    let (_, target_table) = get_module_table(&target_model)?;
+                    Some(!defines_member(&target_model, &target_table, &member.name))
+                })
+                .unwrap_or(false)
+        })
+        .map(|info| info.full_module_name.clone())
+        .collect::<Vec<_>>();
+    targets.sort();
+    targets
+}
+
+/// What every file edited by the move needs to know
+struct MoveContext {
+    source_file: FileId,
+    target_file: FileId,
+    module_decl: LuaDeclId,
+    target_table: String,
+    /// The name of the local holding the target module in a file that requires it
+    target_local_name: String,
+    /// `require("a.b")` for the target module
+    require_text: String,
+    re_export: bool,
+}
+
+/// Move the module member defined at `position` before the `return` of `target_module`, the
+/// accesses to it through `require` of the old module are changed to the new one
+#[allow(clippy::mutable_key_type)]
+pub fn move_symbol(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+    target_module: &str,
+    re_export: bool,
+) -> Option<WorkspaceEdit> {
+    let compilation = &analysis.compilation;
+    let semantic_model = compilation.get_semantic_model(file_id)?;
+    let document = semantic_model.get_document();
+    let member = ModuleMember::find(&semantic_model, position)?;
+    let (module_decl, module_table) = get_module_table(&semantic_model)?;
+
+    let module_index = compilation.get_db().get_module_index();
+    let target_info = module_index.find_module(target_module)?;
+    let target_file = target_info.file_id;
+    if target_file == file_id || !module_index.is_main(&target_file) {
+        return None;
+    }
+    let target_model = compilation.get_semantic_model(target_file)?;
+    let (_, target_table) = get_module_table(&target_model)?;
+    if defines_member(&target_model, &target_table, &member.name) {
+        return None;
+    }
+
+    // the code moves alone, it can not read the locals of the old module
+    let member_range = member.get_range();
+    let prefix_range = member.var.get_prefix_expr()?.get_range();
+    for name_expr in member
+        .stat
+        .syntax()
+        .descendants()
+        .filter_map(LuaNameExpr::cast)
+    {
+        if name_expr.get_range() == prefix_range {
+            continue;
+        }
+        if let Some(decl_id) = find_local_decl(&semantic_model, &name_expr)
+            && !member_range.contains(decl_id.position)
+        {
+            return None;
+        }
+    }
+
+    let emmyrc = semantic_model.get_emmyrc();
+    let separator = &emmyrc.completion.auto_require_separator;
+    let target_path = match separator.as_str() {
+        "." | "" => target_info.full_module_name.clone(),
+        _ => target_info.full_module_name.replace(".", separator),
+    };
+    let context = MoveContext {
+        source_file: file_id,
+        target_file,
+        module_decl,
+        target_local_name: module_name_convert(
+            target_info,
+            emmyrc.completion.auto_require_naming_convention,
+        ),
+        require_text: format!(
+            "{}(\"{}\")",
+            emmyrc.completion.auto_require_function, target_path
+        ),
+        target_table: target_table.clone(),
+        re_export,
+    };
+
+    let mut changes: HashMap<Uri, Vec<TextEdit>> = HashMap::new();
+
+    // the accesses of every file, the old module among them for the definition
+    let mut accesses = search_member_accesses(&semantic_model, compilation, &member)?;
+    accesses.entry(file_id).or_default();
+    for (access_file_id, file_accesses) in accesses {
+        let access_model = compilation.get_semantic_model(access_file_id)?;
+        let (mut edits, target_local) = build_access_edits(&access_model, &context, file_accesses)?;
+        if access_file_id == file_id {
+            // the definition leaves with its docs, a re-export may take its place
+            let edit = match target_local {
+                Some(target_local) if re_export => TextEdit {
+                    range: document.to_lsp_range(member_range)?,
+                    new_text: format!(
+                        "{}.{} = {}.{}",
+                        module_table, member.name, target_local, member.name
+                    ),
+                },
+                _ => TextEdit {
+                    range: document
+                        .to_lsp_range(get_member_removal_range(&semantic_model, &member.stat)?)?,
+                    new_text: String::new(),
+                },
+            };
+            edits.push(edit);
+        }
+        if !edits.is_empty() {
+            changes
+                .entry(access_model.get_document().get_uri())
+                .or_default()
+                .extend(edits);
+        }
+    }
+
+    // the definition goes before the `return` of the target module
+    let target_document = target_model.get_document();
+    let return_stat = target_model.get_root().get_block()?.get_stats().last()?;
+    let insert_offset = return_stat.get_position();
+    let mut new_text = String::new();
+    new_text.push_str(
+        document.get_text_slice(TextRange::new(member_range.start(), prefix_range.start())),
+    );
+    new_text.push_str(&target_table);
+    new_text
+        .push_str(document.get_text_slice(TextRange::new(prefix_range.end(), member_range.end())));
+    new_text.push_str("\n\n");
+    new_text.push_str(&get_indent(&target_document, insert_offset).unwrap_or_default());
+    let insert_range =
+        target_document.to_lsp_range(TextRange::new(insert_offset, insert_offset))?;
+    changes
+        .entry(target_document.get_uri())
+        .or_default()
+        .push(TextEdit {
+            range: insert_range,
+            new_text,
+        });
+
+    Some(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    })
+}
+
+/// The lines of the statement, with one of the blank lines around it
+fn get_member_removal_range(semantic_model: &SemanticModel, stat: &LuaStat) -> Option<TextRange> {
+    let document = semantic_model.get_document();
+    let range = get_stat_removal_range(semantic_model, stat)?;
+    let is_blank_line = |line: usize| {
+        document
+            .get_line_range(line)
+            .is_some_and(|line_range| document.get_text_slice(line_range).trim().is_empty())
+    };
+    let start_line = document.get_line(range.start())?;
+    let end_line = document.get_line(range.end())?;
+    if start_line > 0
+        && is_blank_line(start_line - 1)
+        && is_blank_line(end_line)
+        && document.get_line_range(end_line)?.start() == range.end()
+    {
+        return Some(TextRange::new(
+            range.start(),
+            document.get_line_range(end_line)?.end(),
+        ));
+    }
+
+    Some(range)
+}
+
+fn defines_member(semantic_model: &SemanticModel, table: &str, name: &str) -> bool {
+    let Some(block) = semantic_model.get_root().get_block() else {
+        return false;
+    };
+    block.get_stats().any(|stat| {
+        let var = match stat {
+            LuaStat::FuncStat(func_stat) => func_stat.get_func_name(),
+            LuaStat::AssignStat(assign_stat) => assign_stat.get_var_and_expr_list().0.first().cloned(),
+            _ => None,
+        };
+        let Some(LuaVarExpr::IndexExpr(index_expr)) = var else {
+            return false;
+        };
+        matches!(index_expr.get_prefix_expr(), Some(LuaExpr::NameExpr(prefix)) if prefix.get_name_text().as_deref() == Some(table))
+            && matches!(index_expr.get_index_key(), Some(LuaIndexKey::Name(key)) if key.get_name_text() == name)
+    })
+}
+
+/// The `m.name` of every file that reads the member, its definition aside
+fn search_member_accesses(
+    semantic_model: &SemanticModel,
+    compilation: &LuaCompilation,
+    member: &ModuleMember,
+) -> Option<HashMap<FileId, Vec<LuaIndexExpr>>> {
+    let LuaSemanticDeclId::Member(member_id) = semantic_model.find_decl(
+        member.var.get_index_name_token()?.into(),
+        SemanticDeclLevel::NoTrace,
+    )?
+    else {
+        return None;
+    };
+    let db = semantic_model.get_db();
+    let key = db.get_member_index().get_member(&member_id)?.get_key();
+    let semantic_id = LuaSemanticDeclId::Member(member_id);
+    let member_range = member.get_range();
+
+    let mut accesses: HashMap<FileId, Vec<LuaIndexExpr>> = HashMap::new();
+    let mut semantic_cache = HashMap::new();
+    for syntax_id in db
+        .get_reference_index()
+        .get_index_references(key)
+        .unwrap_or_default()
+    {
+        let file_id = syntax_id.file_id;
+        let access_model = match semantic_cache.entry(file_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(compilation.get_semantic_model(file_id)?),
+        };
+        let node = syntax_id
+            .value
+            .to_node_from_root(access_model.get_root().syntax())?;
+        let Some(index_expr) = LuaIndexExpr::cast(node.clone()) else {
+            continue;
+        };
+        if file_id == semantic_model.get_file_id()
+            && member_range.contains_range(index_expr.get_range())
+        {
+            continue;
+        }
+        if access_model.is_reference_to(node, semantic_id.clone(), SemanticDeclLevel::default()) {
+            accesses.entry(file_id).or_default().push(index_expr);
+        }
+    }
+
+    Some(accesses)
+}
+
+/// The edits of the accesses in one file and the local holding the target module there, the
+/// old module is required under the new path when the moved member was all the file read of it
+fn build_access_edits(
+    semantic_model: &SemanticModel,
+    context: &MoveContext,
+    accesses: Vec<LuaIndexExpr>,
+) -> Option<(Vec<TextEdit>, Option<String>)> {
+    let file_id = semantic_model.get_file_id();
+    let document = semantic_model.get_document();
+    let is_source = file_id == context.source_file;
+    let require_stats = get_require_stats(semantic_model);
+
+    // the locals holding the old module, with the accesses changed through them
+    let mut old_locals: HashMap<LuaDeclId, usize> = require_stats
+        .iter()
+        .filter(|(_, _, required)| *required == context.source_file)
+        .map(|(_, decl_id, _)| (*decl_id, 0))
+        .collect();
+    if is_source {
+        old_locals.insert(context.module_decl, 0);
+    }
+    let mut target_local = if file_id == context.target_file {
+        Some(context.target_table.clone())
+    } else {
+        require_stats
+            .iter()
+            .find(|(_, _, required)| *required == context.target_file)
+            .and_then(|(stat, _, _)| {
+                Some(
+                    stat.get_local_name_list()
+                        .next()?
+                        .get_name_token()?
+                        .get_name_text()
+                        .to_string(),
+                )
+            })
+    };
+
+    let mut edits = Vec::new();
+    let mut name_ranges = Vec::new();
+    for access in accesses {
+        match access.get_prefix_expr()? {
+            LuaExpr::NameExpr(name_expr) => {
+                match find_local_decl(semantic_model, &name_expr)
+                    .and_then(|decl_id| old_locals.get_mut(&decl_id))
+                {
+                    Some(count) => {
+                        *count += 1;
+                        name_ranges.push(name_expr.get_range());
+                    }
+                    // the re-export keeps the other accesses working
+                    None if context.re_export => {}
+                    None => return None,
+                }
+            }
+            LuaExpr::CallExpr(call_expr)
+                if get_required_file(semantic_model, &call_expr) == Some(context.source_file) =>
+            {
+                edits.push(TextEdit {
+                    range: document.to_lsp_range(call_expr.get_range())?,
+                    new_text: context.require_text.clone(),
+                });
+            }
+            _ if context.re_export => {}
+            _ => return None,
+        }
+    }
+
+    let is_unused = |decl_id: &LuaDeclId| {
+        let count = semantic_model
+            .get_db()
+            .get_reference_index()
+            .get_decl_references(&file_id, decl_id)
+            .map(|references| {
+                references
+                    .cells
+                    .iter()
+                    .filter(|cell| cell.range.start() != decl_id.position)
+                    .count()
+            })
+            .unwrap_or_default();
+        !is_source
+            && old_locals
+                .get(decl_id)
+                .is_some_and(|changed| *changed == count)
+    };
+    let mut replaced = None;
+    if target_local.is_none() && (!name_ranges.is_empty() || (is_source && context.re_export)) {
+        let unused_require = require_stats
+            .iter()
+            .find(|(_, decl_id, required)| *required == context.source_file && is_unused(decl_id));
+        let name = match unused_require {
+            Some((stat, decl_id, _)) => {
+                let old_name = stat.get_local_name_list().next()?.get_name_token()?;
+                let name = if old_name.get_name_text() == context.target_local_name {
+                    context.target_local_name.clone()
+                } else {
+                    get_unique_name(semantic_model, &context.target_local_name)
+                };
+                edits.push(TextEdit {
+                    range: document.to_lsp_range(stat.get_range())?,
+                    new_text: format!("local {} = {}", name, context.require_text),
+                });
+                replaced = Some(*decl_id);
+                name
+            }
+            None => {
+                let name = get_unique_name(semantic_model, &context.target_local_name);
+                let line = match require_stats.last() {
+                    Some((stat, _, _)) => document.get_line(stat.get_range().end())? + 1,
+                    None => 0,
+                };
+                let position = Position::new(line as u32, 0);
+                edits.push(TextEdit {
+                    range: lsp_types::Range::new(position, position),
+                    new_text: format!("local {} = {}\n", name, context.require_text),
+                });
+                name
+            }
+        };
+        target_local = Some(name);
+    }
+
+    // the requires of the old module nothing reads anymore
+    for (stat, decl_id, required) in &require_stats {
+        if *required == context.source_file && Some(*decl_id) != replaced && is_unused(decl_id) {
+            edits.push(TextEdit {
+                range: document.to_lsp_range(get_stat_removal_range(
+                    semantic_model,
+                    &LuaStat::LocalStat(stat.clone()),
+                )?)?,
+                new_text: String::new(),
+            });
+        }
+    }
+
+    if let Some(target_local) = &target_local {
+        for range in name_ranges {
+            edits.push(TextEdit {
+                range: document.to_lsp_range(range)?,
+                new_text: target_local.clone(),
+            });
+        }
+    }
+
+    Some((edits, target_local))
+}
@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{SemanticModel, organize_requires};
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, TextEdit, WorkspaceEdit};
+
+pub fn build_organize_requires(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let text_edits = organize_requires(semantic_model)?
+        .into_iter()
+        .map(|(range, new_text)| {
+            Some(TextEdit {
+                range: document.to_lsp_range(range)?,
+                new_text,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Organize requires").to_string(),
+        kind: Some(CodeActionKind::SOURCE_ORGANIZE_IMPORTS),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), text_edits)])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
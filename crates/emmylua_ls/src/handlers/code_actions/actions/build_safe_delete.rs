@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{
+    EmmyLuaAnalysis, FileId, LuaSemanticDeclId, SemanticDeclLevel, SemanticModel, uri_to_file_path,
+};
+use emmylua_parser::{
+    LuaAssignStat, LuaAst, LuaAstNode, LuaAstToken, LuaComment, LuaDocFieldKey, LuaDocTag, LuaStat,
+    LuaSyntaxToken, LuaTokenKind, LuaVarExpr,
+};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Location, Position, TextEdit, WorkspaceEdit,
+};
+use rowan::{TextRange, TokenAtOffset};
+
+use crate::handlers::{
+    command::{FunctionDefinition, make_safe_delete_command},
+    references::{search_decl_references, search_member_references, search_type_decl_references},
+};
+
+use super::build_inline_code::get_stat_removal_range;
+
+/// The references listed in the reason of the disabled action
+const MAX_LISTED_REFERENCES: usize = 5;
+
+/// The command carries the symbol at the cursor, its references are searched when it runs
+pub fn build_safe_delete(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    position: Position,
+) -> Option<()> {
+    let target = DeleteTarget::find(semantic_model, position)?;
+    let title = t!("Safe delete `%{name}`", name = target.name);
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: title.to_string(),
+        kind: Some(CodeActionKind::REFACTOR),
+        command: Some(make_safe_delete_command(
+            title.as_ref(),
+            semantic_model.get_file_id(),
+            position,
+        )),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+/// Delete the function, field or class defined at `position`, the error lists the references
+/// left when something still uses it
+#[allow(clippy::mutable_key_type)]
+pub fn safe_delete(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+) -> Option<Result<WorkspaceEdit, String>> {
+    let compilation = &analysis.compilation;
+    let semantic_model = compilation.get_semantic_model(file_id)?;
+    let target = DeleteTarget::find(&semantic_model, position)?;
+    let document = semantic_model.get_document();
+    let uri = document.get_uri();
+
+    let mut locations = Vec::new();
+    for semantic_id in &target.semantic_ids {
+        match semantic_id.clone() {
+            LuaSemanticDeclId::LuaDecl(decl_id) => {
+                search_decl_references(&semantic_model, compilation, decl_id, &mut locations);
+            }
+            LuaSemanticDeclId::Member(member_id) => {
+                search_member_references(&semantic_model, compilation, member_id, &mut locations);
+            }
+            LuaSemanticDeclId::TypeDecl(type_decl_id) => {
+                search_type_decl_references(&semantic_model, type_decl_id, &mut locations);
+            }
+            _ => return None,
+        }
+    }
+    // the references inside the deleted code go with it
+    let mut references: Vec<Location> = Vec::new();
+    for location in locations {
+        let is_deleted = location.uri == uri
+            && document
+                .to_rowan_range(location.range)
+                .is_some_and(|range| target.ranges.iter().any(|r| r.contains_range(range)));
+        if !is_deleted && !references.contains(&location) {
+            references.push(location);
+        }
+    }
+    if !references.is_empty() {
+        return Some(Err(t!(
+            "Symbol `%{name}` is still used at %{locations}",
+            name = target.name,
+            locations = format_locations(&references)
+        )
+        .to_string()));
+    }
+
+    let edits = target
+        .ranges
+        .iter()
+        .map(|range| {
+            Some(TextEdit {
+                range: document.to_lsp_range(*range)?,
+                new_text: String::new(),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(Ok(WorkspaceEdit {
+        changes: Some(HashMap::from([(uri, edits)])),
+        ..Default::default()
+    }))
+}
+
+/// `file.lua:line` of the first references
+pub fn format_locations(locations: &[Location]) -> String {
+    let mut listed = locations
+        .iter()
+        .take(MAX_LISTED_REFERENCES)
+        .map(|location| {
+            let file_name = uri_to_file_path(&location.uri)
+                .and_then(|path| Some(path.file_name()?.to_string_lossy().to_string()))
+                .unwrap_or_else(|| location.uri.as_str().to_string());
+            format!("{}:{}", file_name, location.range.start.line + 1)
+        })
+        .collect::<Vec<_>>();
+    if locations.len() > MAX_LISTED_REFERENCES {
+        listed.push("...".to_string());
+    }
+    listed.join(", ")
+}
+
+/// What the deletion removes, and the declarations whose references must be gone
+struct DeleteTarget {
+    name: String,
+    semantic_ids: Vec<LuaSemanticDeclId>,
+    ranges: Vec<TextRange>,
+}
+
+impl DeleteTarget {
+    fn find(semantic_model: &SemanticModel, position: Position) -> Option<Self> {
+        let document = semantic_model.get_document();
+        let offset = document.get_offset(position.line as usize, position.character as usize)?;
+        let root = semantic_model.get_root();
+        let token = match root.syntax().token_at_offset(offset) {
+            TokenAtOffset::Single(token) => token,
+            TokenAtOffset::Between(left, right) => {
+                if left.kind() == LuaTokenKind::TkName.into() {
+                    left
+                } else {
+                    right
+                }
+            }
+            TokenAtOffset::None => return None,
+        };
+        if token.kind() != LuaTokenKind::TkName.into() {
+            return None;
+        }
+
+        if let Some(tag) = token.parent_ancestors().find_map(LuaDocTag::cast) {
+            return Self::find_doc(semantic_model, tag, &token);
+        }
+
+        if let Some(definition) = FunctionDefinition::find(semantic_model, position) {
+            let semantic_id = semantic_model.find_decl(
+                definition.name_token.clone().into(),
+                SemanticDeclLevel::NoTrace,
+            )?;
+            return Some(Self {
+                name: definition.name_token.text().to_string(),
+                semantic_ids: vec![semantic_id],
+                ranges: vec![get_stat_removal_range(semantic_model, &definition.stat)?],
+            });
+        }
+
+        // `M.x = value`
+        let assign_stat = token.parent_ancestors().find_map(LuaAssignStat::cast)?;
+        let (vars, exprs) = assign_stat.get_var_and_expr_list();
+        let ([LuaVarExpr::IndexExpr(index_expr)], 1) = (vars.as_slice(), exprs.len()) else {
+            return None;
+        };
+        if index_expr.get_index_name_token()? != token {
+            return None;
+        }
+        let semantic_id =
+            semantic_model.find_decl(token.clone().into(), SemanticDeclLevel::NoTrace)?;
+        Some(Self {
+            name: token.text().to_string(),
+            semantic_ids: vec![semantic_id],
+            ranges: vec![get_stat_removal_range(
+                semantic_model,
+                &LuaStat::AssignStat(assign_stat),
+            )?],
+        })
+    }
+
+    /// `---@class` with its comment and the statement it describes, or a `---@field` line
+    fn find_doc(
+        semantic_model: &SemanticModel,
+        tag: LuaDocTag,
+        token: &LuaSyntaxToken,
+    ) -> Option<Self> {
+        let document = semantic_model.get_document();
+        let semantic_id =
+            semantic_model.find_decl(token.clone().into(), SemanticDeclLevel::NoTrace)?;
+        match tag {
+            LuaDocTag::Class(class) => {
+                if class.get_name_token()?.syntax() != token {
+                    return None;
+                }
+                let comment = class.get_parent::<LuaComment>()?;
+                let mut semantic_ids = vec![semantic_id];
+                let owner =
+                    match comment.get_owner() {
+                        Some(LuaAst::LuaLocalStat(local_stat)) => {
+                            let mut names = local_stat.get_local_name_list();
+                            let name = names.next()?;
+                            if names.next().is_some() {
+                                return None;
+                            }
+                            semantic_ids.push(semantic_model.find_decl(
+                                name.syntax().clone().into(),
+                                SemanticDeclLevel::NoTrace,
+                            )?);
+                            Some(LuaStat::LocalStat(local_stat))
+                        }
+                        Some(LuaAst::LuaAssignStat(assign_stat)) => {
+                            let (vars, _) = assign_stat.get_var_and_expr_list();
+                            let [LuaVarExpr::NameExpr(name_expr)] = vars.as_slice() else {
+                                return None;
+                            };
+                            semantic_ids.push(semantic_model.find_decl(
+                                name_expr.syntax().clone().into(),
+                                SemanticDeclLevel::NoTrace,
+                            )?);
+                            Some(LuaStat::AssignStat(assign_stat))
+                        }
+                        _ => None,
+                    };
+                let range = match owner {
+                    Some(stat) => get_stat_removal_range(semantic_model, &stat)?,
+                    None => {
+                        let comment_range = comment.get_range();
+                        let start_line = document.get_line(comment_range.start())?;
+                        let end_line = document.get_line(comment_range.end())?;
+                        TextRange::new(
+                            document.get_line_range(start_line)?.start(),
+                            document.get_line_range(end_line)?.end(),
+                        )
+                    }
+                };
+                Some(Self {
+                    name: token.text().to_string(),
+                    semantic_ids,
+                    ranges: vec![range],
+                })
+            }
+            LuaDocTag::Field(field) => {
+                let LuaDocFieldKey::Name(name_token) = field.get_field_key()? else {
+                    return None;
+                };
+                if name_token.syntax() != token {
+                    return None;
+                }
+                let line = document.get_line(field.get_position())?;
+                Some(Self {
+                    name: token.text().to_string(),
+                    semantic_ids: vec![semantic_id],
+                    ranges: vec![document.get_line_range(line)?],
+                })
+            }
+            _ => None,
+        }
+    }
+}
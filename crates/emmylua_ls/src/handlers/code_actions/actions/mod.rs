@@ -1,5 +1,21 @@
+mod build_change_signature;
+mod build_convert_method;
 mod build_disable_code;
+mod build_extract_code;
 mod build_fix_code;
+mod build_generate_annotations;
+mod build_inline_code;
+mod build_move_symbol;
+mod build_organize_requires;
+mod build_safe_delete;
 
+pub use build_change_signature::*;
+pub use build_convert_method::*;
 pub use build_disable_code::*;
+pub use build_extract_code::*;
 pub use build_fix_code::*;
+pub use build_generate_annotations::*;
+pub use build_inline_code::*;
+pub use build_move_symbol::*;
+pub use build_organize_requires::*;
+pub use build_safe_delete::*;
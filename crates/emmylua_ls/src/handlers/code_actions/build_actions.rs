@@ -1,56 +1,87 @@
 use std::str::FromStr;
 
-use emmylua_code_analysis::{DiagnosticCode, FileId, SemanticModel};
+use emmylua_code_analysis::{DiagnosticCode, FileId, LuaCompilation, SemanticModel};
 use lsp_types::{
     CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionResponse, Diagnostic,
-    NumberOrString, Range, WorkspaceEdit,
+    NumberOrString, Range, TextEdit, WorkspaceEdit,
 };
+use serde_json::json;
 
 use super::actions::{
-    build_add_doc_tag, build_disable_file_changes, build_disable_next_line_changes,
+    build_add_doc_tag, build_add_global, build_add_missing_cases, build_change_signature,
+    build_convert_method_definition, build_disable_file_changes, build_disable_next_line_changes,
+    build_extract_function, build_extract_local, build_fill_missing_fields,
+    build_generate_annotations, build_implement_members, build_inline_function,
+    build_inline_variable, build_move_symbol, build_organize_requires, build_replace_similar_name,
+    build_require_global, build_safe_delete, build_use_local_alias,
 };
 use crate::handlers::{
     code_actions::actions::build_need_check_nil,
-    command::{DisableAction, make_disable_code_command},
+    command::{DisableAction, make_disable_code_command, make_fix_all_command},
 };
 
+/// The kind of the action applying every fix that is safe to run unattended
+pub const FIX_ALL_KIND: CodeActionKind = CodeActionKind::new("source.fixAll.emmylua");
+
 pub fn build_actions(
     semantic_model: &SemanticModel,
+    compilation: &LuaCompilation,
+    range: Range,
     diagnostics: Vec<Diagnostic>,
+    only: Option<&[CodeActionKind]>,
 ) -> Option<CodeActionResponse> {
     let mut actions = Vec::new();
     let file_id = semantic_model.get_file_id();
+    let diagnostics = if is_kind_requested(only, &CodeActionKind::QUICKFIX) {
+        diagnostics
+    } else {
+        Vec::new()
+    };
     for diagnostic in diagnostics {
-        if diagnostic.source.is_none() {
+        let Some(diagnostic_code) = get_diagnostic_code(&diagnostic) else {
             continue;
-        }
+        };
 
-        let source = diagnostic.source.unwrap();
-        if source != "EmmyLua" {
-            continue;
-        }
+        add_fix_code_action(
+            semantic_model,
+            &mut actions,
+            diagnostic_code,
+            file_id,
+            diagnostic.range,
+            &diagnostic.data,
+        );
+        add_disable_code_action(
+            semantic_model,
+            &mut actions,
+            diagnostic_code,
+            file_id,
+            diagnostic.range,
+        );
+    }
 
-        if let Some(code) = diagnostic.code
-            && let NumberOrString::String(action_string) = code
-            && let Ok(diagnostic_code) = DiagnosticCode::from_str(&action_string)
-        {
-            add_fix_code_action(
-                semantic_model,
-                &mut actions,
-                diagnostic_code,
-                file_id,
-                diagnostic.range,
-                &diagnostic.data,
-            );
-            add_disable_code_action(
-                semantic_model,
-                &mut actions,
-                diagnostic_code,
-                file_id,
-                diagnostic.range,
-            );
-        }
+    if is_kind_requested(only, &FIX_ALL_KIND) && actions.iter().any(is_fix_all_action) {
+        let title = t!("Fix all auto-fixable problems");
+        actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+            title: title.to_string(),
+            kind: Some(FIX_ALL_KIND),
+            command: Some(make_fix_all_command(
+                title.as_ref(),
+                Some(semantic_model.get_document().get_uri()),
+            )),
+            ..Default::default()
+        }));
+    }
+
+    add_refactor_actions(semantic_model, compilation, &mut actions, range, only);
+    if is_kind_requested(only, &CodeActionKind::SOURCE_ORGANIZE_IMPORTS) {
+        build_organize_requires(semantic_model, &mut actions);
     }
+    actions.retain(|action| match action {
+        CodeActionOrCommand::CodeAction(CodeAction {
+            kind: Some(kind), ..
+        }) => is_kind_requested(only, kind),
+        _ => true,
+    });
 
     if actions.is_empty() {
         return None;
@@ -59,6 +90,143 @@ pub fn build_actions(
     Some(actions)
 }
 
+/// The edits of the first safe fix of each diagnostic, a fix that overlaps one taken before is
+/// left for the next run
+pub fn build_fix_all_edits(
+    semantic_model: &SemanticModel,
+    diagnostics: Vec<Diagnostic>,
+) -> Vec<TextEdit> {
+    let file_id = semantic_model.get_file_id();
+    let uri = semantic_model.get_document().get_uri();
+    let mut edits: Vec<TextEdit> = Vec::new();
+    for diagnostic in diagnostics {
+        let Some(diagnostic_code) = get_diagnostic_code(&diagnostic) else {
+            continue;
+        };
+
+        let mut actions = Vec::new();
+        add_fix_code_action(
+            semantic_model,
+            &mut actions,
+            diagnostic_code,
+            file_id,
+            diagnostic.range,
+            &diagnostic.data,
+        );
+        let Some(fix_edits) = actions.into_iter().find_map(|action| match action {
+            CodeActionOrCommand::CodeAction(action) if is_fix_all_code_action(&action) => {
+                let mut changes = action.edit?.changes?;
+                if changes.len() != 1 {
+                    return None;
+                }
+                changes.remove(&uri)
+            }
+            _ => None,
+        }) else {
+            continue;
+        };
+
+        if fix_edits.iter().any(|fix_edit| {
+            edits
+                .iter()
+                .any(|edit| is_overlap(&edit.range, &fix_edit.range))
+        }) {
+            continue;
+        }
+        edits.extend(fix_edits);
+    }
+
+    edits.sort_by_key(|edit| edit.range.start);
+    edits
+}
+
+fn get_diagnostic_code(diagnostic: &Diagnostic) -> Option<DiagnosticCode> {
+    if diagnostic.source.as_deref() != Some("EmmyLua") {
+        return None;
+    }
+
+    match &diagnostic.code {
+        Some(NumberOrString::String(action_string)) => DiagnosticCode::from_str(action_string).ok(),
+        _ => None,
+    }
+}
+
+/// The key of `CodeAction::data` marking a quickfix as safe for fix-all
+const FIX_ALL_SAFE_KEY: &str = "fixAllSafe";
+
+/// Let fix-all apply the quickfix unattended. Only a fix that keeps the behaviour of the code may
+/// be marked, not one inserting placeholders, stubs or guessed names.
+pub fn mark_fix_all_safe(mut action: CodeAction) -> CodeAction {
+    action.data = Some(json!({ FIX_ALL_SAFE_KEY: true }));
+    action
+}
+
+fn is_fix_all_code_action(action: &CodeAction) -> bool {
+    action.edit.is_some()
+        && action
+            .data
+            .as_ref()
+            .and_then(|data| data.get(FIX_ALL_SAFE_KEY))
+            .is_some_and(|safe| safe.as_bool() == Some(true))
+}
+
+fn is_fix_all_action(action: &CodeActionOrCommand) -> bool {
+    matches!(action, CodeActionOrCommand::CodeAction(action) if is_fix_all_code_action(action))
+}
+
+/// Two insertions at the same place overlap too, their order would be arbitrary
+fn is_overlap(a: &Range, b: &Range) -> bool {
+    a.start == b.start || (a.start < b.end && b.start < a.end)
+}
+
+/// Whether the client asks for actions of `kind`, a requested kind takes in its sub kinds
+fn is_kind_requested(only: Option<&[CodeActionKind]>, kind: &CodeActionKind) -> bool {
+    match only {
+        Some(only) if !only.is_empty() => only.iter().any(|requested| {
+            kind.as_str()
+                .strip_prefix(requested.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        }),
+        _ => true,
+    }
+}
+
+fn add_refactor_actions(
+    semantic_model: &SemanticModel,
+    compilation: &LuaCompilation,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+    only: Option<&[CodeActionKind]>,
+) -> Option<()> {
+    if is_kind_requested(only, &CodeActionKind::REFACTOR_INLINE) {
+        build_inline_variable(semantic_model, actions, range.start);
+        build_inline_function(semantic_model, actions, range.start);
+    }
+    if is_kind_requested(only, &CodeActionKind::REFACTOR_REWRITE) {
+        build_convert_method_definition(semantic_model, compilation, actions, range.start);
+    }
+    let is_refactor_requested = is_kind_requested(only, &CodeActionKind::REFACTOR);
+    if is_refactor_requested {
+        build_change_signature(semantic_model, actions, range.start);
+    }
+    if is_kind_requested(only, &CodeActionKind::REFACTOR_REWRITE)
+        || is_kind_requested(only, &CodeActionKind::SOURCE)
+    {
+        build_generate_annotations(semantic_model, compilation, actions, range.start);
+    }
+    if is_refactor_requested {
+        build_move_symbol(semantic_model, actions, range.start);
+        build_safe_delete(semantic_model, actions, range.start);
+    }
+    if range.start == range.end || !is_kind_requested(only, &CodeActionKind::REFACTOR_EXTRACT) {
+        return Some(());
+    }
+
+    build_extract_local(semantic_model, actions, range);
+    build_extract_function(semantic_model, actions, range);
+    Some(())
+}
+
 #[allow(unused_variables)]
 fn add_fix_code_action(
     semantic_model: &SemanticModel,
@@ -71,6 +239,26 @@ fn add_fix_code_action(
     match diagnostic_code {
         DiagnosticCode::NeedCheckNil => build_need_check_nil(semantic_model, actions, range, data),
         DiagnosticCode::UnknownDocTag => build_add_doc_tag(semantic_model, actions, range, data),
+        DiagnosticCode::MissingFields => {
+            build_fill_missing_fields(semantic_model, actions, range, data)
+        }
+        DiagnosticCode::UnimplementedMember => {
+            build_implement_members(semantic_model, actions, range, data)
+        }
+        DiagnosticCode::UndefinedGlobal => {
+            build_replace_similar_name(semantic_model, actions, range, data);
+            build_require_global(semantic_model, actions, range);
+            build_add_global(semantic_model, actions, range)
+        }
+        DiagnosticCode::NonExhaustiveDispatch => {
+            build_add_missing_cases(semantic_model, actions, range, data)
+        }
+        DiagnosticCode::UndefinedField | DiagnosticCode::InjectField => {
+            build_replace_similar_name(semantic_model, actions, range, data)
+        }
+        DiagnosticCode::PreferredLocalAlias => {
+            build_use_local_alias(semantic_model, actions, range, data)
+        }
         _ => Some(()),
     }
 }
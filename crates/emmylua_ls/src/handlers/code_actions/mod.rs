@@ -1,12 +1,18 @@
 mod actions;
 mod build_actions;
 
-use build_actions::build_actions;
+pub use actions::{
+    format_locations, generate_definition_annotations, get_move_targets, move_symbol, safe_delete,
+};
+
+use build_actions::{FIX_ALL_KIND, build_actions, build_fix_all_edits};
 use emmylua_code_analysis::{EmmyLuaAnalysis, FileId};
 use lsp_types::{
-    ClientCapabilities, CodeActionParams, CodeActionProviderCapability, CodeActionResponse,
-    Diagnostic, ServerCapabilities,
+    ClientCapabilities, CodeAction, CodeActionKind, CodeActionOptions, CodeActionOrCommand,
+    CodeActionParams, CodeActionProviderCapability, CodeActionResponse, Diagnostic, Range,
+    ServerCapabilities, TextEdit, WorkspaceEdit,
 };
+use std::collections::HashMap;
 use tokio_util::sync::CancellationToken;
 
 use crate::context::ServerContextSnapshot;
@@ -23,23 +29,84 @@ pub async fn on_code_action_handler(
     let diagnostics = params.context.diagnostics;
     let analysis = context.analysis().read().await;
     let file_id = analysis.get_file_id(&uri)?;
-    code_action(&analysis, file_id, diagnostics)
+    // a client running the fixes on save asks for them alone and needs the edit right away
+    if let Some(only) = &params.context.only
+        && !only.is_empty()
+        && only.iter().all(|kind| {
+            kind.as_str()
+                .starts_with(CodeActionKind::SOURCE_FIX_ALL.as_str())
+                && FIX_ALL_KIND.as_str().starts_with(kind.as_str())
+        })
+    {
+        return fix_all_action(&analysis, file_id);
+    }
+    code_action(
+        &analysis,
+        file_id,
+        params.range,
+        diagnostics,
+        params.context.only.as_deref(),
+    )
 }
 
+/// The actions for the range, only those of the kinds in `only` when it is given
 pub fn code_action(
     analysis: &EmmyLuaAnalysis,
     file_id: FileId,
+    range: Range,
     diagnostics: Vec<Diagnostic>,
+    only: Option<&[CodeActionKind]>,
 ) -> Option<CodeActionResponse> {
     let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
 
-    build_actions(&semantic_model, diagnostics)
+    build_actions(
+        &semantic_model,
+        &analysis.compilation,
+        range,
+        diagnostics,
+        only,
+    )
+}
+
+/// The edits of every safe fix for the diagnostics of the whole file
+pub fn fix_all_edits(analysis: &EmmyLuaAnalysis, file_id: FileId) -> Option<Vec<TextEdit>> {
+    let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
+    let diagnostics = analysis.diagnose_file(file_id, CancellationToken::new())?;
+    let edits = build_fix_all_edits(&semantic_model, diagnostics);
+    if edits.is_empty() { None } else { Some(edits) }
+}
+
+#[allow(clippy::mutable_key_type)]
+fn fix_all_action(analysis: &EmmyLuaAnalysis, file_id: FileId) -> Option<CodeActionResponse> {
+    let edits = fix_all_edits(analysis, file_id)?;
+    let uri = analysis.get_uri(file_id)?;
+    Some(vec![CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Fix all auto-fixable problems").to_string(),
+        kind: Some(FIX_ALL_KIND),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri, edits)])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })])
 }
 
 pub struct CodeActionsCapabilities;
 
 impl RegisterCapabilities for CodeActionsCapabilities {
     fn register_capabilities(server_capabilities: &mut ServerCapabilities, _: &ClientCapabilities) {
-        server_capabilities.code_action_provider = Some(CodeActionProviderCapability::Simple(true));
+        server_capabilities.code_action_provider =
+            Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                code_action_kinds: Some(vec![
+                    CodeActionKind::QUICKFIX,
+                    CodeActionKind::REFACTOR,
+                    CodeActionKind::REFACTOR_EXTRACT,
+                    CodeActionKind::REFACTOR_INLINE,
+                    CodeActionKind::REFACTOR_REWRITE,
+                    CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                    FIX_ALL_KIND,
+                ]),
+                ..Default::default()
+            }));
     }
 }
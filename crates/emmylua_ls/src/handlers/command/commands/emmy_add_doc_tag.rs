@@ -16,7 +16,7 @@ impl CommandSpec for AddDocTagCommand {
 
     async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()> {
         let tag_name: String = serde_json::from_value(args.first()?.clone()).ok()?;
-        add_doc_tag(context.workspace_manager(), tag_name).await;
+        push_emmyrc_list(context.workspace_manager(), "doc", "knownTags", tag_name).await;
         Some(())
     }
 }
@@ -31,7 +31,13 @@ pub fn make_auto_doc_tag_command(title: &str, tag_name: &str) -> Command {
     }
 }
 
-async fn add_doc_tag(workspace_manager: &RwLock<WorkspaceManager>, tag_name: String) -> Option<()> {
+/// Append `value` to the `section.key` list of the main workspace `.emmyrc.json`
+pub(super) async fn push_emmyrc_list(
+    workspace_manager: &RwLock<WorkspaceManager>,
+    section: &str,
+    key: &str,
+    value: String,
+) -> Option<()> {
     let workspace_manager = workspace_manager.read().await;
     let main_workspace = workspace_manager.workspace_folders.first()?;
     let emmyrc_path = main_workspace.join(".emmyrc.json");
@@ -40,13 +46,13 @@ async fn add_doc_tag(workspace_manager: &RwLock<WorkspaceManager>, tag_name: Str
 
     emmyrc
         .as_object_mut()?
-        .entry("doc")
+        .entry(section)
         .or_insert_with(|| Value::Object(Default::default()))
         .as_object_mut()?
-        .entry("knownTags")
+        .entry(key)
         .or_insert_with(|| Value::Array(Default::default()))
         .as_array_mut()?
-        .push(Value::String(tag_name));
+        .push(Value::String(value));
 
     let emmyrc_json = serde_json::to_string_pretty(&emmyrc).ok()?;
     if let Ok(mut file) = OpenOptions::new()
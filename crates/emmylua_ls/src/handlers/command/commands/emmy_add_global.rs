@@ -0,0 +1,28 @@
+use lsp_types::Command;
+use serde_json::Value;
+
+use crate::context::ServerContextSnapshot;
+
+use super::{CommandSpec, emmy_add_doc_tag::push_emmyrc_list};
+
+pub struct AddGlobalCommand;
+
+impl CommandSpec for AddGlobalCommand {
+    const COMMAND: &str = "emmy.add.global";
+
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()> {
+        let name: String = serde_json::from_value(args.first()?.clone()).ok()?;
+        push_emmyrc_list(context.workspace_manager(), "diagnostics", "globals", name).await;
+        Some(())
+    }
+}
+
+pub fn make_add_global_command(title: &str, name: &str) -> Command {
+    let args = vec![serde_json::to_value(name).unwrap()];
+
+    Command {
+        title: title.to_string(),
+        command: AddGlobalCommand::COMMAND.to_string(),
+        arguments: Some(args),
+    }
+}
@@ -0,0 +1,642 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use emmylua_code_analysis::{
+    EmmyLuaAnalysis, FileId, LuaCompilation, LuaDeclId, LuaDocument, LuaSemanticDeclId,
+    SemanticDeclLevel, SemanticModel,
+};
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaCallExpr, LuaClosureExpr, LuaCommentOwner, LuaDocTag, LuaExpr,
+    LuaLiteralToken, LuaParamName, LuaStat, LuaSyntaxToken, LuaTokenKind, LuaVarExpr,
+};
+use lsp_types::{
+    ApplyWorkspaceEditParams, Command, Location, MessageType, Position, ShowMessageParams,
+    TextEdit, Uri, WorkspaceEdit,
+};
+use rowan::{NodeOrToken, TextRange, TextSize, TokenAtOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    context::ServerContextSnapshot,
+    handlers::{
+        code_actions::format_locations,
+        references::{search_decl_references, search_member_references},
+    },
+    util::time_cancel_token,
+};
+
+use super::CommandSpec;
+
+/// A parameter of the new signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureParam {
+    pub name: String,
+    /// The index of the parameter in the current signature, `None` for a new parameter
+    #[serde(default)]
+    pub old_index: Option<usize>,
+    /// The argument given by the existing calls to a new parameter, `nil` when empty
+    #[serde(default)]
+    pub default_value: Option<String>,
+}
+
+pub struct ChangeSignatureCommand;
+
+impl CommandSpec for ChangeSignatureCommand {
+    const COMMAND: &str = "emmy.change.signature";
+
+    /// The arguments are the file, the position of the function and the new parameters
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()> {
+        let file_id: FileId = serde_json::from_value(args.first()?.clone()).ok()?;
+        let position: Position = serde_json::from_value(args.get(1)?.clone()).ok()?;
+        let params: Option<Vec<SignatureParam>> = args
+            .get(2)
+            .and_then(|value| serde_json::from_value(value.clone()).ok());
+
+        let analysis = context.analysis().read().await;
+        let result =
+            params.and_then(|params| change_signature(&analysis, file_id, position, &params));
+        drop(analysis);
+        let edit = match result {
+            Some(Ok(edit)) => edit,
+            Some(Err(reason)) => {
+                context.client().show_message(ShowMessageParams {
+                    typ: MessageType::WARNING,
+                    message: reason,
+                });
+                return None;
+            }
+            None => {
+                context.client().show_message(ShowMessageParams {
+                    typ: MessageType::WARNING,
+                    message: t!("Can not change the signature of this function").to_string(),
+                });
+                return None;
+            }
+        };
+
+        let cancel_token = time_cancel_token(Duration::from_secs(5));
+        let apply_edit_params = ApplyWorkspaceEditParams {
+            label: Some(t!("Change signature").to_string()),
+            edit,
+        };
+        let context_clone = context.clone();
+        tokio::spawn(async move {
+            let res = context_clone
+                .client()
+                .apply_edit(apply_edit_params, cancel_token)
+                .await;
+            if let Some(res) = res
+                && !res.applied
+            {
+                log::error!("Failed to apply edit: {:?}", res.failure_reason);
+            }
+        });
+
+        Some(())
+    }
+}
+
+pub fn make_change_signature_command(
+    title: &str,
+    file_id: FileId,
+    position: Position,
+    params: Vec<SignatureParam>,
+) -> Command {
+    let args = vec![
+        serde_json::to_value(file_id).unwrap(),
+        serde_json::to_value(position).unwrap(),
+        serde_json::to_value(params).unwrap(),
+    ];
+
+    Command {
+        title: title.to_string(),
+        command: ChangeSignatureCommand::COMMAND.to_string(),
+        arguments: Some(args),
+    }
+}
+
+/// The function defined by the statement at the cursor
+pub struct FunctionDefinition {
+    pub name_token: LuaSyntaxToken,
+    pub stat: LuaStat,
+    pub closure: LuaClosureExpr,
+    /// `function M:f()`, whose `self` is not in the parameter list
+    pub is_colon: bool,
+}
+
+impl FunctionDefinition {
+    pub fn find(semantic_model: &SemanticModel, position: Position) -> Option<Self> {
+        let document = semantic_model.get_document();
+        let offset = document.get_offset(position.line as usize, position.character as usize)?;
+        let root = semantic_model.get_root();
+        let name_token = match root.syntax().token_at_offset(offset) {
+            TokenAtOffset::Single(token) => token,
+            TokenAtOffset::Between(left, right) => {
+                if left.kind() == LuaTokenKind::TkName.into() {
+                    left
+                } else {
+                    right
+                }
+            }
+            TokenAtOffset::None => return None,
+        };
+        if name_token.kind() != LuaTokenKind::TkName.into() {
+            return None;
+        }
+
+        let stat = name_token.parent_ancestors().find_map(LuaStat::cast)?;
+        let definition = Self::from_stat(stat)?;
+        let name_range = match &definition.stat {
+            LuaStat::FuncStat(func_stat) => func_stat.get_func_name()?.get_range(),
+            _ => definition.name_token.text_range(),
+        };
+        if !name_range.contains_range(name_token.text_range()) {
+            return None;
+        }
+
+        Some(definition)
+    }
+
+    /// The function of a `function` or `local function` statement
+    pub fn from_stat(stat: LuaStat) -> Option<Self> {
+        let (name_token, closure, is_colon) = match &stat {
+            LuaStat::FuncStat(func_stat) => match func_stat.get_func_name()? {
+                LuaVarExpr::IndexExpr(index_expr) => (
+                    index_expr.get_index_name_token()?,
+                    func_stat.get_closure()?,
+                    index_expr.get_index_token()?.is_colon(),
+                ),
+                LuaVarExpr::NameExpr(name_expr) => (
+                    name_expr.get_name_token()?.syntax().clone(),
+                    func_stat.get_closure()?,
+                    false,
+                ),
+            },
+            LuaStat::LocalFuncStat(local_func_stat) => (
+                local_func_stat
+                    .get_local_name()?
+                    .get_name_token()?
+                    .syntax()
+                    .clone(),
+                local_func_stat.get_closure()?,
+                false,
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            name_token,
+            stat,
+            closure,
+            is_colon,
+        })
+    }
+
+    pub fn get_params(&self) -> Vec<LuaParamName> {
+        self.closure
+            .get_params_list()
+            .map(|params_list| {
+                params_list
+                    .get_params()
+                    .filter(|param| !param.is_dots())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The references to the function, its calls among them
+    pub fn search_references(
+        &self,
+        semantic_model: &SemanticModel,
+        compilation: &LuaCompilation,
+    ) -> Option<Vec<Location>> {
+        let mut locations = Vec::new();
+        match semantic_model
+            .find_decl(self.name_token.clone().into(), SemanticDeclLevel::NoTrace)?
+        {
+            LuaSemanticDeclId::LuaDecl(decl_id) => {
+                search_decl_references(semantic_model, compilation, decl_id, &mut locations);
+            }
+            LuaSemanticDeclId::Member(member_id) => {
+                search_member_references(semantic_model, compilation, member_id, &mut locations);
+            }
+            _ => return None,
+        }
+        Some(locations)
+    }
+
+    pub fn is_vararg(&self) -> bool {
+        self.closure
+            .get_params_list()
+            .is_some_and(|params_list| params_list.get_params().any(|param| param.is_dots()))
+    }
+}
+
+/// The edits of the definition, its `---@param` docs and every call of the function, nothing is
+/// changed when a call can not be changed and the error is the reason given to the user
+#[allow(clippy::mutable_key_type)]
+pub fn change_signature(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+    new_params: &[SignatureParam],
+) -> Option<Result<WorkspaceEdit, String>> {
+    let compilation = &analysis.compilation;
+    let semantic_model = compilation.get_semantic_model(file_id)?;
+    let document = semantic_model.get_document();
+    let definition = FunctionDefinition::find(&semantic_model, position)?;
+    let old_params = definition.get_params();
+    let old_names = old_params
+        .iter()
+        .map(|param| Some(param.get_name_token()?.get_name_text().to_string()))
+        .collect::<Option<Vec<_>>>()?;
+    if !is_valid_change(&old_names, new_params) {
+        return None;
+    }
+
+    let mut changes: HashMap<Uri, Vec<TextEdit>> = HashMap::new();
+    let uri = document.get_uri();
+    let edits = changes.entry(uri).or_default();
+
+    // the parameter list
+    let params_list = definition.closure.get_params_list()?;
+    let mut names = new_params
+        .iter()
+        .map(|param| param.name.clone())
+        .collect::<Vec<_>>();
+    if definition.is_vararg() {
+        names.push("...".to_string());
+    }
+    let inner_range = TextRange::new(
+        params_list.get_position() + TextSize::from(1),
+        params_list.get_range().end() - TextSize::from(1),
+    );
+    edits.push(TextEdit {
+        range: document.to_lsp_range(inner_range)?,
+        new_text: names.join(", "),
+    });
+
+    // the parameters read in the body, a removed one must be unused
+    let kept = new_params
+        .iter()
+        .filter_map(|param| Some((param.old_index?, param.name.as_str())))
+        .collect::<HashMap<_, _>>();
+    let reference_index = semantic_model.get_db().get_reference_index();
+    for (index, param) in old_params.iter().enumerate() {
+        let decl_id = LuaDeclId::new(file_id, param.get_position());
+        let cells = reference_index
+            .get_decl_references(&file_id, &decl_id)
+            .map(|references| references.cells.as_slice())
+            .unwrap_or_default();
+        match kept.get(&index) {
+            Some(name) if *name != old_names[index] => {
+                for cell in cells {
+                    edits.push(TextEdit {
+                        range: document.to_lsp_range(cell.range)?,
+                        new_text: name.to_string(),
+                    });
+                }
+            }
+            Some(_) => {}
+            None if !cells.is_empty() => return None,
+            None => {}
+        }
+    }
+
+    edits.extend(build_doc_edits(
+        &document,
+        &definition,
+        &old_names,
+        new_params,
+    )?);
+
+    // the calls, the name of the definition is the only reference that is not one
+    let definition_range = match &definition.stat {
+        LuaStat::FuncStat(func_stat) => func_stat.get_func_name()?.get_range(),
+        _ => definition.name_token.text_range(),
+    };
+    let locations = definition.search_references(&semantic_model, compilation)?;
+    let mut visited = HashSet::new();
+    for location in locations {
+        if !visited.insert((location.uri.clone(), location.range))
+            || (location.uri == document.get_uri()
+                && document
+                    .to_rowan_range(location.range)
+                    .is_some_and(|range| definition_range.contains_range(range)))
+        {
+            continue;
+        }
+        match build_call_edit(
+            compilation,
+            &location,
+            &definition,
+            old_names.len(),
+            new_params,
+        ) {
+            Ok(Some((uri, edit))) => changes.entry(uri).or_default().push(edit),
+            Ok(None) => {}
+            Err(reason) => return Some(Err(reason)),
+        }
+    }
+
+    Some(Ok(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    }))
+}
+
+fn is_valid_change(old_names: &[String], new_params: &[SignatureParam]) -> bool {
+    let mut names = HashSet::new();
+    let mut old_indexes = HashSet::new();
+    new_params.iter().all(|param| {
+        let is_valid_name = {
+            let mut chars = param.name.chars();
+            chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        is_valid_name
+            && names.insert(param.name.as_str())
+            && param
+                .old_index
+                .is_none_or(|index| index < old_names.len() && old_indexes.insert(index))
+    })
+}
+
+/// Rewrite the `---@param` lines in the new order, when the function documents its parameters
+fn build_doc_edits(
+    document: &LuaDocument,
+    definition: &FunctionDefinition,
+    old_names: &[String],
+    new_params: &[SignatureParam],
+) -> Option<Vec<TextEdit>> {
+    let Some(comment) = definition.stat.get_left_comment() else {
+        return Some(Vec::new());
+    };
+    let param_tags = comment
+        .get_doc_tags()
+        .filter_map(|tag| match tag {
+            LuaDocTag::Param(param) => Some(param),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if param_tags.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut tag_lines = Vec::new();
+    for tag in &param_tags {
+        let name_token = tag.get_name_token()?;
+        let line_range = document.get_line_range(document.get_line(tag.get_position())?)?;
+        tag_lines.push((
+            name_token.get_name_text().to_string(),
+            line_range,
+            name_token.get_range(),
+        ));
+    }
+    let first_line_text = document.get_text_slice(tag_lines.first()?.1);
+    let indent = &first_line_text[..first_line_text.len() - first_line_text.trim_start().len()];
+
+    let mut new_text = String::new();
+    for param in new_params {
+        let old_tag = param.old_index.and_then(|index| {
+            let old_name = old_names.get(index)?;
+            tag_lines.iter().find(|(name, _, _)| name == old_name)
+        });
+        match old_tag {
+            Some((_, line_range, name_range)) => {
+                new_text.push_str(
+                    document.get_text_slice(TextRange::new(line_range.start(), name_range.start())),
+                );
+                new_text.push_str(&param.name);
+                new_text.push_str(
+                    document.get_text_slice(TextRange::new(name_range.end(), line_range.end())),
+                );
+            }
+            None => new_text.push_str(&format!(
+                "{}---@param {} {}",
+                indent,
+                param.name,
+                infer_default_value_type(param.default_value.as_deref())
+            )),
+        }
+        if !new_text.ends_with('\n') {
+            new_text.push('\n');
+        }
+    }
+    // the docs of other names, like `self`, stay
+    for (name, line_range, _) in &tag_lines {
+        if !old_names.contains(name) {
+            new_text.push_str(document.get_text_slice(*line_range));
+        }
+    }
+
+    // the new lines take the place of the first one
+    let mut edits = Vec::new();
+    for (i, (_, line_range, _)) in tag_lines.iter().enumerate() {
+        edits.push(TextEdit {
+            range: document.to_lsp_range(*line_range)?,
+            new_text: if i == 0 {
+                std::mem::take(&mut new_text)
+            } else {
+                String::new()
+            },
+        });
+    }
+    Some(edits)
+}
+
+fn infer_default_value_type(default_value: Option<&str>) -> &'static str {
+    let Some(value) = default_value.map(str::trim) else {
+        return "any";
+    };
+    if value.parse::<i64>().is_ok() {
+        "integer"
+    } else if value.parse::<f64>().is_ok() {
+        "number"
+    } else if value == "true" || value == "false" {
+        "boolean"
+    } else if value.starts_with('"') || value.starts_with('\'') || value.starts_with("[[") {
+        "string"
+    } else if value.starts_with('{') {
+        "table"
+    } else {
+        "any"
+    }
+}
+
+/// The call whose callee is the reference at `location`
+pub(super) fn find_location_call<'a>(
+    compilation: &'a LuaCompilation,
+    location: &Location,
+) -> Option<(SemanticModel<'a>, LuaCallExpr)> {
+    let file_id = compilation.get_db().get_vfs().get_file_id(&location.uri)?;
+    let semantic_model = compilation.get_semantic_model(file_id)?;
+    let document = semantic_model.get_document();
+    let range = document.to_rowan_range(location.range)?;
+    let root = semantic_model.get_root();
+    let node = match root.syntax().covering_element(range) {
+        NodeOrToken::Node(node) => node,
+        NodeOrToken::Token(token) => token.parent()?,
+    };
+    let call_expr = node
+        .ancestors()
+        .filter_map(LuaCallExpr::cast)
+        .find(|call_expr| {
+            call_expr
+                .get_prefix_expr()
+                .is_some_and(|prefix_expr| prefix_expr.get_range() == range)
+        })?;
+    Some((semantic_model, call_expr))
+}
+
+/// The new argument list of the call whose callee is at `location`, `None` when the call stays
+/// as it is and an error when it can not be changed
+fn build_call_edit(
+    compilation: &LuaCompilation,
+    location: &Location,
+    definition: &FunctionDefinition,
+    old_count: usize,
+    new_params: &[SignatureParam],
+) -> Result<Option<(Uri, TextEdit)>, String> {
+    let location_text = format_locations(std::slice::from_ref(location));
+    let cannot_change = || {
+        t!(
+            "Can not change the call at %{location}",
+            location = location_text
+        )
+        .to_string()
+    };
+    let Some((semantic_model, call_expr)) = find_location_call(compilation, location) else {
+        return Err(t!(
+            "Function `%{name}` is used without being called at %{location}",
+            name = definition.name_token.text(),
+            location = location_text
+        )
+        .to_string());
+    };
+    let document = semantic_model.get_document();
+    let args_list = call_expr.get_args_list().ok_or_else(cannot_change)?;
+    let arg_exprs = args_list.get_args().collect::<Vec<_>>();
+    let args = arg_exprs
+        .iter()
+        .map(|arg| document.get_text_slice(arg.get_range()).to_string())
+        .collect::<Vec<_>>();
+
+    // `M.f(obj, x)` passes the `self` of `function M:f(x)` first, and `obj:f(x)` passes the
+    // first parameter of `function M.f(self, x)` as the receiver
+    let implicit_first = !definition.is_colon && call_expr.is_colon_call();
+    if implicit_first && new_params.first().and_then(|param| param.old_index) != Some(0) {
+        return Err(cannot_change());
+    }
+    let has_receiver = definition.is_colon && !call_expr.is_colon_call();
+    if has_receiver && args.is_empty() {
+        return Err(cannot_change());
+    }
+    let first_arg = usize::from(has_receiver);
+
+    // each new argument with the index of the argument it was, `None` for a new one
+    let mut new_args = Vec::new();
+    for param in new_params.iter().skip(usize::from(implicit_first)) {
+        let arg = match param.old_index {
+            Some(index) => {
+                let index = index
+                    .checked_sub(usize::from(implicit_first))
+                    .ok_or_else(cannot_change)?
+                    + first_arg;
+                args.get(index).map(|arg| (arg.clone(), Some(index)))
+            }
+            None => Some((
+                param
+                    .default_value
+                    .clone()
+                    .filter(|value| !value.trim().is_empty())
+                    .unwrap_or_else(|| "nil".to_string()),
+                None,
+            )),
+        };
+        new_args.push(arg);
+    }
+    while matches!(new_args.last(), Some(None)) {
+        new_args.pop();
+    }
+    let mut new_args = new_args
+        .into_iter()
+        .map(|arg| arg.unwrap_or_else(|| ("nil".to_string(), None)))
+        .collect::<Vec<_>>();
+    // the arguments after the parameters, for `...`
+    let param_count = old_count - usize::from(implicit_first);
+    new_args.extend(
+        args.iter()
+            .enumerate()
+            .skip(first_arg + param_count)
+            .map(|(index, arg)| (arg.clone(), Some(index))),
+    );
+    if has_receiver {
+        new_args.insert(0, (args[0].clone(), Some(0)));
+    }
+
+    let new_texts = new_args
+        .iter()
+        .map(|(text, _)| text.as_str())
+        .collect::<Vec<_>>();
+    if new_texts == args && !args_list.is_single_arg_no_parens() {
+        return Ok(None);
+    }
+
+    // all the values of the last call or `...` fill the parameters from its place
+    let last_index = arg_exprs.len().checked_sub(1);
+    if let Some(last_index) = last_index
+        && is_multi_value_expr(&arg_exprs[last_index])
+        && (new_args.len() != arg_exprs.len() || new_args[last_index].1 != Some(last_index))
+    {
+        return Err(t!(
+            "The last argument of the call at %{location} may give several values",
+            location = location_text
+        )
+        .to_string());
+    }
+    // the arguments that may have side effects are still all evaluated in the same order
+    let moved_indexes = new_args
+        .iter()
+        .filter_map(|(_, index)| *index)
+        .filter(|index| !is_plain_expr(&arg_exprs[*index]))
+        .collect::<Vec<_>>();
+    let kept_count = arg_exprs.iter().filter(|arg| !is_plain_expr(arg)).count();
+    if moved_indexes.len() != kept_count || !moved_indexes.is_sorted() {
+        return Err(t!(
+            "The call at %{location} has arguments with side effects that would be moved or removed",
+            location = location_text
+        )
+        .to_string());
+    }
+
+    let range = document
+        .to_lsp_range(args_list.get_range())
+        .ok_or_else(cannot_change)?;
+    let edit = TextEdit {
+        range,
+        new_text: format!("({})", new_texts.join(", ")),
+    };
+    Ok(Some((document.get_uri(), edit)))
+}
+
+fn is_multi_value_expr(expr: &LuaExpr) -> bool {
+    match expr {
+        LuaExpr::CallExpr(_) => true,
+        LuaExpr::LiteralExpr(literal_expr) => {
+            matches!(literal_expr.get_literal(), Some(LuaLiteralToken::Dots(_)))
+        }
+        _ => false,
+    }
+}
+
+/// A name or a literal, whose evaluation has no side effects
+fn is_plain_expr(expr: &LuaExpr) -> bool {
+    matches!(expr, LuaExpr::NameExpr(_) | LuaExpr::LiteralExpr(_))
+}
@@ -0,0 +1,85 @@
+use std::{collections::HashMap, time::Duration};
+
+use lsp_types::{ApplyWorkspaceEditParams, Command, Uri, WorkspaceEdit};
+use serde_json::Value;
+
+use crate::{
+    context::ServerContextSnapshot, handlers::code_actions::fix_all_edits, util::time_cancel_token,
+};
+
+use super::CommandSpec;
+
+pub struct FixAllCommand;
+
+impl CommandSpec for FixAllCommand {
+    const COMMAND: &str = "emmy.fix.all";
+
+    /// Fix the document given as argument, or every file of the main workspace without one,
+    /// in a single edit
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()> {
+        let uri: Option<Uri> = match args.first() {
+            Some(value) => serde_json::from_value(value.clone()).ok()?,
+            None => None,
+        };
+
+        let analysis = context.analysis().read().await;
+        let file_ids = match &uri {
+            Some(uri) => vec![analysis.get_file_id(uri)?],
+            None => analysis
+                .compilation
+                .get_db()
+                .get_module_index()
+                .get_main_workspace_file_ids(),
+        };
+        #[allow(clippy::mutable_key_type)]
+        let mut changes = HashMap::new();
+        for file_id in file_ids {
+            if let Some(uri) = analysis.get_uri(file_id)
+                && let Some(edits) = fix_all_edits(&analysis, file_id)
+            {
+                changes.insert(uri, edits);
+            }
+        }
+        drop(analysis);
+        if changes.is_empty() {
+            return Some(());
+        }
+
+        let cancel_token = time_cancel_token(Duration::from_secs(5));
+        let apply_edit_params = ApplyWorkspaceEditParams {
+            label: Some(t!("Fix all auto-fixable problems").to_string()),
+            edit: WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            },
+        };
+
+        let context_clone = context.clone();
+        tokio::spawn(async move {
+            let res = context_clone
+                .client()
+                .apply_edit(apply_edit_params, cancel_token)
+                .await;
+            if let Some(res) = res
+                && !res.applied
+            {
+                log::error!("Failed to apply edit: {:?}", res.failure_reason);
+            }
+        });
+
+        Some(())
+    }
+}
+
+pub fn make_fix_all_command(title: &str, uri: Option<Uri>) -> Command {
+    let args = match uri {
+        Some(uri) => vec![serde_json::to_value(uri).unwrap()],
+        None => vec![],
+    };
+
+    Command {
+        title: title.to_string(),
+        command: FixAllCommand::COMMAND.to_string(),
+        arguments: Some(args),
+    }
+}
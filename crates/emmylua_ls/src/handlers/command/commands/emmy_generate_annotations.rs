@@ -0,0 +1,698 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
+    time::Duration,
+};
+
+use emmylua_code_analysis::{
+    DbIndex, EmmyLuaAnalysis, FileId, LuaCompilation, LuaSemanticDeclId, LuaSignatureId, LuaType,
+    RenderLevel, SemanticDeclLevel, SemanticModel, SignatureReturnStatus, VariadicType,
+    humanize_type,
+};
+use emmylua_parser::{
+    LuaAssignStat, LuaAstNode, LuaCallExpr, LuaClosureExpr, LuaCommentOwner, LuaDocTag, LuaExpr,
+    LuaFuncStat, LuaIndexExpr, LuaLocalStat, LuaStat, LuaTableExpr, LuaVarExpr,
+};
+use lsp_types::{ApplyWorkspaceEditParams, Command, Position, Range, TextEdit, Uri, WorkspaceEdit};
+use rowan::TextRange;
+use serde_json::Value;
+
+use crate::{
+    context::ServerContextSnapshot, handlers::code_actions::generate_definition_annotations,
+    util::time_cancel_token,
+};
+
+use super::{
+    CommandSpec,
+    emmy_change_signature::{FunctionDefinition, find_location_call},
+};
+
+pub struct GenerateAnnotationsCommand;
+
+impl CommandSpec for GenerateAnnotationsCommand {
+    const COMMAND: &str = "emmy.generate.annotations";
+
+    /// Annotate the definition at the position given with the document, the whole document
+    /// without a position, or every file of the main workspace without arguments, in a single
+    /// edit
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()> {
+        let uri: Option<Uri> = match args.first() {
+            Some(value) => serde_json::from_value(value.clone()).ok()?,
+            None => None,
+        };
+        let position: Option<Position> = match args.get(1) {
+            Some(value) => serde_json::from_value(value.clone()).ok()?,
+            None => None,
+        };
+
+        let analysis = context.analysis().read().await;
+        let changes = match (&uri, position) {
+            (Some(uri), Some(position)) => {
+                let file_id = analysis.get_file_id(uri)?;
+                let edit = generate_definition_annotations(&analysis, file_id, position)?;
+                HashMap::from([(uri.clone(), vec![edit])])
+            }
+            (Some(uri), None) => generate_annotations(&analysis, &[analysis.get_file_id(uri)?]),
+            (None, _) => generate_annotations(
+                &analysis,
+                &analysis
+                    .compilation
+                    .get_db()
+                    .get_module_index()
+                    .get_main_workspace_file_ids(),
+            ),
+        };
+        drop(analysis);
+        if changes.is_empty() {
+            return Some(());
+        }
+
+        let cancel_token = time_cancel_token(Duration::from_secs(5));
+        let apply_edit_params = ApplyWorkspaceEditParams {
+            label: Some(t!("Generate annotations").to_string()),
+            edit: WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            },
+        };
+
+        let context_clone = context.clone();
+        tokio::spawn(async move {
+            let res = context_clone
+                .client()
+                .apply_edit(apply_edit_params, cancel_token)
+                .await;
+            if let Some(res) = res
+                && !res.applied
+            {
+                log::error!("Failed to apply edit: {:?}", res.failure_reason);
+            }
+        });
+
+        Some(())
+    }
+}
+
+pub fn make_generate_annotations_command(
+    title: &str,
+    uri: Option<Uri>,
+    position: Option<Position>,
+) -> Command {
+    let mut args = Vec::new();
+    if let Some(uri) = uri {
+        args.push(serde_json::to_value(uri).unwrap());
+        if let Some(position) = position {
+            args.push(serde_json::to_value(position).unwrap());
+        }
+    }
+
+    Command {
+        title: title.to_string(),
+        command: GenerateAnnotationsCommand::COMMAND.to_string(),
+        arguments: Some(args),
+    }
+}
+
+/// The missing docs of every function and table class of the files, a class name is declared
+/// by one file only
+#[allow(clippy::mutable_key_type)]
+pub fn generate_annotations(
+    analysis: &EmmyLuaAnalysis,
+    file_ids: &[FileId],
+) -> HashMap<Uri, Vec<TextEdit>> {
+    let mut class_names = HashSet::new();
+    let mut changes = HashMap::new();
+    for file_id in file_ids {
+        if let Some(uri) = analysis.get_uri(*file_id)
+            && let Some(edits) = generate_file_annotations(analysis, *file_id, &mut class_names)
+        {
+            changes.insert(uri, edits);
+        }
+    }
+    changes
+}
+
+fn generate_file_annotations(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    class_names: &mut HashSet<String>,
+) -> Option<Vec<TextEdit>> {
+    let compilation = &analysis.compilation;
+    let semantic_model = compilation.get_semantic_model(file_id)?;
+    let mut edits = Vec::new();
+    for stat in semantic_model.get_root().descendants::<LuaStat>() {
+        let edit = match &stat {
+            LuaStat::FuncStat(_) | LuaStat::LocalFuncStat(_) => FunctionDefinition::from_stat(stat)
+                .and_then(|definition| {
+                    build_function_annotations(&semantic_model, compilation, &definition, true)
+                }),
+            LuaStat::LocalStat(_) | LuaStat::AssignStat(_) => {
+                build_class_annotations(&semantic_model, &stat, class_names)
+            }
+            _ => None,
+        };
+        edits.extend(edit);
+    }
+
+    if edits.is_empty() { None } else { Some(edits) }
+}
+
+/// The `---@param` and `---@return` lines the function misses, the parameter types come from
+/// its calls and the return types from its body. Without `infer_arg_types` the calls are not
+/// searched and the parameters are `any`, the lines are the same.
+pub fn build_function_annotations(
+    semantic_model: &SemanticModel,
+    compilation: &LuaCompilation,
+    definition: &FunctionDefinition,
+    infer_arg_types: bool,
+) -> Option<TextEdit> {
+    let comment = definition.stat.get_left_comment();
+    let mut doc_params = HashSet::new();
+    let mut has_doc_return = false;
+    for tag in comment.iter().flat_map(|comment| comment.get_doc_tags()) {
+        match tag {
+            LuaDocTag::Param(param) => {
+                if let Some(name) = param.get_name_token() {
+                    doc_params.insert(name.get_name_text().to_string());
+                } else if param.is_vararg() {
+                    doc_params.insert("...".to_string());
+                }
+            }
+            LuaDocTag::Return(_) => has_doc_return = true,
+            _ => {}
+        }
+    }
+
+    let db = semantic_model.get_db();
+    let mut lines = Vec::new();
+    let params = definition.get_params();
+    let mut arg_types = vec![DocTypes::default(); params.len()];
+    if infer_arg_types
+        && params.iter().any(|param| {
+            param
+                .get_name_token()
+                .is_some_and(|name| !doc_params.contains(name.get_name_text()))
+        })
+    {
+        collect_arg_types(semantic_model, compilation, definition, &mut arg_types);
+    }
+    for (param, types) in params.iter().zip(arg_types) {
+        let name = param.get_name_token()?.get_name_text().to_string();
+        if doc_params.contains(&name) || name == "_" {
+            continue;
+        }
+        let types = match get_self_type(semantic_model, definition, &name) {
+            Some(self_type) => DocTypes::from_type(db, &self_type),
+            None => types,
+        };
+        let (type_text, nullable) = types.to_text();
+        lines.push(format!(
+            "---@param {}{} {}",
+            name,
+            if nullable { "?" } else { "" },
+            type_text
+        ));
+    }
+    if definition.is_vararg() && !doc_params.contains("...") {
+        lines.push("---@param ... any".to_string());
+    }
+
+    if !has_doc_return {
+        let signature_id =
+            LuaSignatureId::from_closure(semantic_model.get_file_id(), &definition.closure);
+        if let Some(signature) = db.get_signature_index().get(&signature_id)
+            && signature.resolve_return == SignatureReturnStatus::InferResolve
+        {
+            let returns = signature
+                .return_docs
+                .iter()
+                .flat_map(|return_doc| match &return_doc.type_ref {
+                    LuaType::Variadic(variadic) => match variadic.deref() {
+                        VariadicType::Multi(types) => types.clone(),
+                        VariadicType::Base(_) => vec![return_doc.type_ref.clone()],
+                    },
+                    typ => vec![typ.clone()],
+                })
+                .map(|typ| DocTypes::from_type(db, &typ).to_text())
+                .collect::<Vec<_>>();
+            // a function returning nothing has no `---@return`
+            if returns.iter().any(|(type_text, _)| type_text != "nil") {
+                for (type_text, nullable) in returns {
+                    lines.push(format!(
+                        "---@return {}{}",
+                        type_text,
+                        if nullable { "?" } else { "" }
+                    ));
+                }
+            }
+        }
+    }
+
+    // a global function needs a comment even when it has nothing to declare
+    if lines.is_empty() && comment.is_none() && is_global_function(semantic_model, definition) {
+        lines.push("---".to_string());
+    }
+    if lines.is_empty() {
+        return None;
+    }
+
+    build_insert_edit(semantic_model, &definition.stat, lines)
+}
+
+/// The `---@class` block of `local T = {}` with the fields its methods assign to `self` and its
+/// constructors assign to the object they give the metatable, `class_names` holds the names the
+/// edits made before this one declare
+pub fn build_class_annotations(
+    semantic_model: &SemanticModel,
+    stat: &LuaStat,
+    class_names: &mut HashSet<String>,
+) -> Option<TextEdit> {
+    let (name_node, value) = match stat {
+        LuaStat::LocalStat(local_stat) => (
+            local_stat.get_local_name_list().next()?.syntax().clone(),
+            local_stat.get_value_exprs().next()?,
+        ),
+        LuaStat::AssignStat(assign_stat) => {
+            let (vars, values) = assign_stat.get_var_and_expr_list();
+            let LuaVarExpr::NameExpr(name_expr) = vars.first()? else {
+                return None;
+            };
+            (name_expr.syntax().clone(), values.first()?.clone())
+        }
+        _ => return None,
+    };
+    if !matches!(value, LuaExpr::TableExpr(_)) {
+        return None;
+    }
+    let has_class = stat.get_left_comment().is_some_and(|comment| {
+        comment
+            .get_doc_tags()
+            .any(|tag| matches!(tag, LuaDocTag::Class(_)))
+    });
+    if has_class {
+        return None;
+    }
+    let name = get_class_name(semantic_model, &name_node.text().to_string())?;
+    if class_names.contains(&name)
+        || semantic_model
+            .get_db()
+            .get_type_index()
+            .find_type_decl(semantic_model.get_file_id(), &name)
+            .is_some()
+    {
+        return None;
+    }
+    let owner = semantic_model.find_decl(name_node.into(), SemanticDeclLevel::NoTrace)?;
+
+    let mut fields = FieldCollector::default();
+    for func_stat in semantic_model.get_root().descendants::<LuaFuncStat>() {
+        let Some(LuaVarExpr::IndexExpr(func_name)) = func_stat.get_func_name() else {
+            continue;
+        };
+        let Some(closure) = func_stat.get_closure() else {
+            continue;
+        };
+        if !is_reference_to(semantic_model, func_name.get_prefix_expr(), &owner) {
+            continue;
+        }
+        let is_method = func_name
+            .get_index_token()
+            .is_some_and(|token| token.is_colon())
+            || closure
+                .get_params_list()
+                .and_then(|params| params.get_params().next())
+                .and_then(|param| param.get_name_token())
+                .is_some_and(|name| name.get_name_text() == "self");
+        if is_method {
+            collect_assigned_fields(semantic_model, &closure, "self", &mut fields);
+        }
+        for call_expr in closure.descendants::<LuaCallExpr>() {
+            collect_constructor_fields(semantic_model, &call_expr, &owner, is_method, &mut fields);
+        }
+    }
+    if fields.fields.is_empty() {
+        return None;
+    }
+
+    let mut lines = vec![format!("---@class {}", name)];
+    for (field_name, types) in fields.fields {
+        let (type_text, nullable) = types.to_text();
+        lines.push(format!(
+            "---@field {}{} {}",
+            field_name,
+            if nullable { "?" } else { "" },
+            type_text
+        ));
+    }
+    let edit = build_insert_edit(semantic_model, stat, lines)?;
+    class_names.insert(name);
+    Some(edit)
+}
+
+/// The names modules commonly give the table they return, they say nothing about the class
+const MODULE_TABLE_NAMES: &[&str] = &["M", "_M", "m", "mod", "module"];
+
+/// The variable name, or the module path for the table of a module
+fn get_class_name(semantic_model: &SemanticModel, name: &str) -> Option<String> {
+    if !MODULE_TABLE_NAMES.contains(&name) {
+        return Some(name.to_string());
+    }
+    let module = semantic_model
+        .get_db()
+        .get_module_index()
+        .get_module(semantic_model.get_file_id())?;
+    Some(module.full_module_name.clone())
+}
+
+/// The types a parameter or field receives, in the order they are met
+#[derive(Debug, Default, Clone)]
+struct DocTypes {
+    names: Vec<String>,
+    nullable: bool,
+}
+
+impl DocTypes {
+    fn from_type(db: &DbIndex, typ: &LuaType) -> Self {
+        let mut types = Self::default();
+        types.add(db, typ);
+        types
+    }
+
+    fn add(&mut self, db: &DbIndex, typ: &LuaType) {
+        let name = match typ {
+            LuaType::Unknown | LuaType::Any => "any".to_string(),
+            LuaType::Nil => {
+                self.nullable = true;
+                return;
+            }
+            LuaType::Union(union) => {
+                for typ in union.into_vec() {
+                    self.add(db, &typ);
+                }
+                return;
+            }
+            LuaType::IntegerConst(_) | LuaType::DocIntegerConst(_) => "integer".to_string(),
+            LuaType::FloatConst(_) => "number".to_string(),
+            LuaType::StringConst(_) | LuaType::DocStringConst(_) => "string".to_string(),
+            LuaType::BooleanConst(_) | LuaType::DocBooleanConst(_) => "boolean".to_string(),
+            LuaType::TableConst(_) => "table".to_string(),
+            LuaType::Signature(_) | LuaType::DocFunction(_) => "function".to_string(),
+            _ => humanize_type(db, typ, RenderLevel::Simple),
+        };
+        if !self.names.contains(&name) {
+            self.names.push(name);
+        }
+    }
+
+    /// The doc type and whether it may be nil, `any` covers everything else
+    fn to_text(&self) -> (String, bool) {
+        if self.names.iter().any(|name| name == "any") {
+            return ("any".to_string(), false);
+        }
+        match self.names.len() {
+            0 if self.nullable => ("nil".to_string(), false),
+            0 => ("any".to_string(), false),
+            _ => (self.names.join("|"), self.nullable),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FieldCollector {
+    fields: Vec<(String, DocTypes)>,
+}
+
+impl FieldCollector {
+    fn add(&mut self, db: &DbIndex, name: String, typ: &LuaType) {
+        if name.starts_with("__") {
+            return;
+        }
+        match self.fields.iter_mut().find(|(field, _)| *field == name) {
+            Some((_, types)) => types.add(db, typ),
+            None => self.fields.push((name, DocTypes::from_type(db, typ))),
+        }
+    }
+}
+
+fn collect_arg_types(
+    semantic_model: &SemanticModel,
+    compilation: &LuaCompilation,
+    definition: &FunctionDefinition,
+    arg_types: &mut [DocTypes],
+) -> Option<()> {
+    let locations = definition.search_references(semantic_model, compilation)?;
+    for location in locations {
+        let Some((call_model, call_expr)) = find_location_call(compilation, &location) else {
+            continue;
+        };
+        let db = call_model.get_db();
+        let Some(args_list) = call_expr.get_args_list() else {
+            continue;
+        };
+        let mut args = args_list
+            .get_args()
+            .map(|arg| call_model.infer_expr(arg).unwrap_or(LuaType::Unknown))
+            .collect::<Vec<_>>();
+        if definition.is_colon && !call_expr.is_colon_call() {
+            if args.is_empty() {
+                continue;
+            }
+            args.remove(0);
+        } else if !definition.is_colon && call_expr.is_colon_call() {
+            let receiver = match call_expr.get_prefix_expr() {
+                Some(LuaExpr::IndexExpr(index_expr)) => index_expr
+                    .get_prefix_expr()
+                    .and_then(|prefix| call_model.infer_expr(prefix).ok())
+                    .unwrap_or(LuaType::Unknown),
+                _ => LuaType::Unknown,
+            };
+            args.insert(0, receiver);
+        }
+
+        for (i, types) in arg_types.iter_mut().enumerate() {
+            match args.get(i) {
+                Some(typ) => types.add(db, typ),
+                None => types.nullable = true,
+            }
+        }
+    }
+
+    Some(())
+}
+
+/// The owner type of `function T.f(self)`
+fn get_self_type(
+    semantic_model: &SemanticModel,
+    definition: &FunctionDefinition,
+    name: &str,
+) -> Option<LuaType> {
+    if name != "self" {
+        return None;
+    }
+    let LuaStat::FuncStat(func_stat) = &definition.stat else {
+        return None;
+    };
+    let LuaVarExpr::IndexExpr(index_expr) = func_stat.get_func_name()? else {
+        return None;
+    };
+    match semantic_model
+        .infer_expr(index_expr.get_prefix_expr()?)
+        .ok()?
+    {
+        typ @ (LuaType::Def(_) | LuaType::Ref(_)) => Some(typ),
+        _ => None,
+    }
+}
+
+fn is_global_function(semantic_model: &SemanticModel, definition: &FunctionDefinition) -> bool {
+    let Some(LuaSemanticDeclId::LuaDecl(decl_id)) = semantic_model.find_decl(
+        definition.name_token.clone().into(),
+        SemanticDeclLevel::NoTrace,
+    ) else {
+        return false;
+    };
+    semantic_model
+        .get_db()
+        .get_decl_index()
+        .get_decl(&decl_id)
+        .is_some_and(|decl| decl.is_global())
+}
+
+fn is_reference_to(
+    semantic_model: &SemanticModel,
+    expr: Option<LuaExpr>,
+    owner: &LuaSemanticDeclId,
+) -> bool {
+    let Some(LuaExpr::NameExpr(name_expr)) = expr else {
+        return false;
+    };
+    semantic_model.find_decl(
+        name_expr.syntax().clone().into(),
+        SemanticDeclLevel::NoTrace,
+    ) == Some(owner.clone())
+}
+
+/// The `name.field = value` assignments of the closure body, outside nested functions
+fn collect_assigned_fields(
+    semantic_model: &SemanticModel,
+    closure: &LuaClosureExpr,
+    name: &str,
+    fields: &mut FieldCollector,
+) {
+    let db = semantic_model.get_db();
+    for assign_stat in closure.descendants::<LuaAssignStat>() {
+        if assign_stat.ancestors::<LuaClosureExpr>().next().as_ref() != Some(closure) {
+            continue;
+        }
+        let (vars, values) = assign_stat.get_var_and_expr_list();
+        for (i, var) in vars.iter().enumerate() {
+            let LuaVarExpr::IndexExpr(index_expr) = var else {
+                continue;
+            };
+            let Some(field_name) = get_field_name(index_expr, name) else {
+                continue;
+            };
+            let typ = values
+                .get(i)
+                .and_then(|value| semantic_model.infer_expr(value.clone()).ok())
+                .unwrap_or(LuaType::Unknown);
+            fields.add(db, field_name, &typ);
+        }
+    }
+}
+
+/// `prefix.field` with a name key
+fn get_field_name(index_expr: &LuaIndexExpr, prefix: &str) -> Option<String> {
+    let LuaExpr::NameExpr(prefix_expr) = index_expr.get_prefix_expr()? else {
+        return None;
+    };
+    if prefix_expr.get_name_text()? != prefix || !index_expr.get_index_token()?.is_dot() {
+        return None;
+    }
+    Some(index_expr.get_index_name_token()?.text().to_string())
+}
+
+/// The fields of the object a constructor passes to `setmetatable(obj, T)`, also with
+/// `{ __index = T }` as the metatable, or `self` in a method
+fn collect_constructor_fields(
+    semantic_model: &SemanticModel,
+    call_expr: &LuaCallExpr,
+    owner: &LuaSemanticDeclId,
+    is_method: bool,
+    fields: &mut FieldCollector,
+) -> Option<()> {
+    let LuaExpr::NameExpr(callee) = call_expr.get_prefix_expr()? else {
+        return None;
+    };
+    if callee.get_name_text()? != "setmetatable" {
+        return None;
+    }
+    let mut args = call_expr.get_args_list()?.get_args();
+    let object = args.next()?;
+    let metatable = args.next()?;
+    let is_owner_metatable = |expr: &LuaExpr| match expr {
+        LuaExpr::NameExpr(name_expr) if is_method && name_expr.get_name_text()? == "self" => {
+            Some(true)
+        }
+        _ => Some(is_reference_to(semantic_model, Some(expr.clone()), owner)),
+    };
+    let is_owner = match &metatable {
+        LuaExpr::TableExpr(table_expr) => table_expr.get_fields().any(|field| {
+            field
+                .get_field_key()
+                .is_some_and(|key| key.get_path_part() == "__index")
+                && field
+                    .get_value_expr()
+                    .and_then(|value| is_owner_metatable(&value))
+                    .unwrap_or(false)
+        }),
+        _ => is_owner_metatable(&metatable).unwrap_or(false),
+    };
+    if !is_owner {
+        return None;
+    }
+
+    let closure = call_expr.ancestors::<LuaClosureExpr>().next()?;
+    let object_name = match &object {
+        LuaExpr::TableExpr(table_expr) => {
+            collect_table_fields(semantic_model, table_expr, fields);
+            // `local obj = setmetatable({}, T)`
+            let local_stat = call_expr.get_parent::<LuaLocalStat>()?;
+            local_stat
+                .get_local_name_by_value(LuaExpr::CallExpr(call_expr.clone()))?
+                .get_name_token()?
+                .get_name_text()
+                .to_string()
+        }
+        LuaExpr::NameExpr(name_expr) => {
+            let name = name_expr.get_name_text()?;
+            // `local obj = {}` given the metatable later
+            for local_stat in closure.descendants::<LuaLocalStat>() {
+                for (local_name, value) in local_stat
+                    .get_local_name_list()
+                    .zip(local_stat.get_value_exprs())
+                {
+                    if let LuaExpr::TableExpr(table_expr) = value
+                        && local_name
+                            .get_name_token()
+                            .is_some_and(|token| token.get_name_text() == name)
+                    {
+                        collect_table_fields(semantic_model, &table_expr, fields);
+                    }
+                }
+            }
+            name
+        }
+        _ => return None,
+    };
+    collect_assigned_fields(semantic_model, &closure, &object_name, fields);
+
+    Some(())
+}
+
+fn collect_table_fields(
+    semantic_model: &SemanticModel,
+    table_expr: &LuaTableExpr,
+    fields: &mut FieldCollector,
+) {
+    let db = semantic_model.get_db();
+    for field in table_expr.get_fields() {
+        let Some(key) = field.get_field_key() else {
+            continue;
+        };
+        if key.get_name().is_none() {
+            continue;
+        }
+        let typ = field
+            .get_value_expr()
+            .and_then(|value| semantic_model.infer_expr(value).ok())
+            .unwrap_or(LuaType::Unknown);
+        fields.add(db, key.get_path_part(), &typ);
+    }
+}
+
+/// The lines go right above the statement, after the comment it already has
+fn build_insert_edit(
+    semantic_model: &SemanticModel,
+    stat: &LuaStat,
+    lines: Vec<String>,
+) -> Option<TextEdit> {
+    let document = semantic_model.get_document();
+    let line = document.get_line(stat.get_position())?;
+    let line_start = document.get_line_range(line)?.start();
+    let indent = document
+        .get_text_slice(TextRange::new(line_start, stat.get_position()))
+        .to_string();
+    if !indent.chars().all(|c| c == ' ' || c == '\t') {
+        return None;
+    }
+    let position = document.to_lsp_position(line_start)?;
+    let new_text = lines
+        .iter()
+        .map(|line| format!("{}{}\n", indent, line))
+        .collect::<String>();
+    Some(TextEdit {
+        range: Range::new(position, position),
+        new_text,
+    })
+}
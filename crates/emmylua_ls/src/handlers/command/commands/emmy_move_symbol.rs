@@ -0,0 +1,143 @@
+use std::{collections::HashMap, time::Duration};
+
+use emmylua_code_analysis::FileId;
+use lsp_types::{
+    ApplyWorkspaceEditParams, Command, MessageActionItem, MessageType, Position, ShowMessageParams,
+    ShowMessageRequestParams,
+};
+use serde_json::Value;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    context::ServerContextSnapshot,
+    handlers::code_actions::{get_move_targets, move_symbol},
+    util::time_cancel_token,
+};
+
+use super::CommandSpec;
+
+pub struct MoveSymbolCommand;
+
+impl CommandSpec for MoveSymbolCommand {
+    const COMMAND: &str = "emmy.move.symbol";
+
+    /// The arguments are the file, the position of the member, the target module path and
+    /// whether to keep a re-export, the user is asked for the missing ones
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()> {
+        let file_id: FileId = serde_json::from_value(args.first()?.clone()).ok()?;
+        let position: Position = serde_json::from_value(args.get(1)?.clone()).ok()?;
+        let target_module: Option<String> = match args.get(2) {
+            Some(value) => serde_json::from_value(value.clone()).ok()?,
+            None => None,
+        };
+        let re_export: Option<bool> = match args.get(3) {
+            Some(value) => serde_json::from_value(value.clone()).ok()?,
+            None => None,
+        };
+
+        let client = context.client();
+        let target_module = match target_module {
+            Some(target_module) => target_module,
+            None => {
+                let analysis = context.analysis().read().await;
+                let targets = get_move_targets(&analysis, file_id, position);
+                drop(analysis);
+                if targets.is_empty() {
+                    client.show_message(ShowMessageParams {
+                        typ: MessageType::WARNING,
+                        message: t!("There is no module to move this symbol to").to_string(),
+                    });
+                    return None;
+                }
+                let show_message_params = ShowMessageRequestParams {
+                    typ: MessageType::INFO,
+                    message: t!("Which module do you want to move the symbol to?").to_string(),
+                    actions: Some(
+                        targets
+                            .into_iter()
+                            .map(|target| MessageActionItem {
+                                title: target,
+                                properties: HashMap::new(),
+                            })
+                            .collect(),
+                    ),
+                };
+                client
+                    .show_message_request(show_message_params, CancellationToken::new())
+                    .await?
+                    .title
+            }
+        };
+        let re_export = match re_export {
+            Some(re_export) => re_export,
+            None => {
+                let show_message_params = ShowMessageRequestParams {
+                    typ: MessageType::INFO,
+                    message: t!("Do you want to keep a re-export in the old module?").to_string(),
+                    actions: Some(vec![
+                        MessageActionItem {
+                            title: t!("Keep").to_string(),
+                            properties: HashMap::new(),
+                        },
+                        MessageActionItem {
+                            title: t!("Remove").to_string(),
+                            properties: HashMap::new(),
+                        },
+                    ]),
+                };
+                let selected_action = client
+                    .show_message_request(show_message_params, CancellationToken::new())
+                    .await?;
+                selected_action.title == t!("Keep")
+            }
+        };
+
+        let analysis = context.analysis().read().await;
+        let Some(edit) = move_symbol(&analysis, file_id, position, &target_module, re_export)
+        else {
+            client.show_message(ShowMessageParams {
+                typ: MessageType::WARNING,
+                message: t!(
+                    "Can not move this symbol to module `%{module}`",
+                    module = target_module
+                )
+                .to_string(),
+            });
+            return None;
+        };
+        drop(analysis);
+
+        let cancel_token = time_cancel_token(Duration::from_secs(5));
+        let apply_edit_params = ApplyWorkspaceEditParams {
+            label: Some(t!("Move symbol").to_string()),
+            edit,
+        };
+        let context_clone = context.clone();
+        tokio::spawn(async move {
+            let res = context_clone
+                .client()
+                .apply_edit(apply_edit_params, cancel_token)
+                .await;
+            if let Some(res) = res
+                && !res.applied
+            {
+                log::error!("Failed to apply edit: {:?}", res.failure_reason);
+            }
+        });
+
+        Some(())
+    }
+}
+
+pub fn make_move_symbol_command(title: &str, file_id: FileId, position: Position) -> Command {
+    let args = vec![
+        serde_json::to_value(file_id).unwrap(),
+        serde_json::to_value(position).unwrap(),
+    ];
+
+    Command {
+        title: title.to_string(),
+        command: MoveSymbolCommand::COMMAND.to_string(),
+        arguments: Some(args),
+    }
+}
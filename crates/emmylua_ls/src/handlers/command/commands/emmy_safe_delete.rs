@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use emmylua_code_analysis::FileId;
+use lsp_types::{ApplyWorkspaceEditParams, Command, MessageType, Position, ShowMessageParams};
+use serde_json::Value;
+
+use crate::{
+    context::ServerContextSnapshot, handlers::code_actions::safe_delete, util::time_cancel_token,
+};
+
+use super::CommandSpec;
+
+pub struct SafeDeleteCommand;
+
+impl CommandSpec for SafeDeleteCommand {
+    const COMMAND: &str = "emmy.safe.delete";
+
+    /// The arguments are the file and the position of the symbol, the user is told where it is
+    /// still used instead of deleting it
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()> {
+        let file_id: FileId = serde_json::from_value(args.first()?.clone()).ok()?;
+        let position: Position = serde_json::from_value(args.get(1)?.clone()).ok()?;
+
+        let analysis = context.analysis().read().await;
+        let result = safe_delete(&analysis, file_id, position);
+        drop(analysis);
+        let edit = match result {
+            Some(Ok(edit)) => edit,
+            Some(Err(reason)) => {
+                context.client().show_message(ShowMessageParams {
+                    typ: MessageType::WARNING,
+                    message: reason,
+                });
+                return None;
+            }
+            None => return None,
+        };
+
+        let cancel_token = time_cancel_token(Duration::from_secs(5));
+        let apply_edit_params = ApplyWorkspaceEditParams {
+            label: Some(t!("Safe delete").to_string()),
+            edit,
+        };
+        let context_clone = context.clone();
+        tokio::spawn(async move {
+            let res = context_clone
+                .client()
+                .apply_edit(apply_edit_params, cancel_token)
+                .await;
+            if let Some(res) = res
+                && !res.applied
+            {
+                log::error!("Failed to apply edit: {:?}", res.failure_reason);
+            }
+        });
+
+        Some(())
+    }
+}
+
+pub fn make_safe_delete_command(title: &str, file_id: FileId, position: Position) -> Command {
+    let args = vec![
+        serde_json::to_value(file_id).unwrap(),
+        serde_json::to_value(position).unwrap(),
+    ];
+
+    Command {
+        title: title.to_string(),
+        command: SafeDeleteCommand::COMMAND.to_string(),
+        arguments: Some(args),
+    }
+}
@@ -1,21 +1,46 @@
 use std::sync::LazyLock;
 
 use emmy_add_doc_tag::AddDocTagCommand;
+use emmy_add_global::AddGlobalCommand;
 use emmy_auto_require::AutoRequireCommand;
+use emmy_change_signature::ChangeSignatureCommand;
 use emmy_disable_code::DisableCodeCommand;
+use emmy_fix_all::FixAllCommand;
 use emmy_fix_format::FixFormatCommand;
+use emmy_generate_annotations::GenerateAnnotationsCommand;
+use emmy_move_symbol::MoveSymbolCommand;
+use emmy_safe_delete::SafeDeleteCommand;
 use serde_json::Value;
 
 use crate::context::ServerContextSnapshot;
 
 mod emmy_add_doc_tag;
+mod emmy_add_global;
 mod emmy_auto_require;
+mod emmy_change_signature;
 mod emmy_disable_code;
+mod emmy_fix_all;
 mod emmy_fix_format;
+mod emmy_generate_annotations;
+mod emmy_move_symbol;
+mod emmy_safe_delete;
 
 pub use emmy_add_doc_tag::make_auto_doc_tag_command;
+pub use emmy_add_global::make_add_global_command;
 pub use emmy_auto_require::make_auto_require;
+#[allow(unused)]
+pub use emmy_change_signature::{
+    FunctionDefinition, SignatureParam, change_signature, make_change_signature_command,
+};
 pub use emmy_disable_code::{DisableAction, make_disable_code_command};
+pub use emmy_fix_all::make_fix_all_command;
+#[allow(unused)]
+pub use emmy_generate_annotations::{
+    build_class_annotations, build_function_annotations, generate_annotations,
+    make_generate_annotations_command,
+};
+pub use emmy_move_symbol::make_move_symbol_command;
+pub use emmy_safe_delete::make_safe_delete_command;
 
 pub trait CommandSpec {
     const COMMAND: &str;
@@ -29,6 +54,12 @@ static COMMANDS: LazyLock<Vec<String>> = LazyLock::new(|| {
         DisableCodeCommand::COMMAND.to_string(),
         FixFormatCommand::COMMAND.to_string(),
         AddDocTagCommand::COMMAND.to_string(),
+        ChangeSignatureCommand::COMMAND.to_string(),
+        AddGlobalCommand::COMMAND.to_string(),
+        FixAllCommand::COMMAND.to_string(),
+        GenerateAnnotationsCommand::COMMAND.to_string(),
+        MoveSymbolCommand::COMMAND.to_string(),
+        SafeDeleteCommand::COMMAND.to_string(),
     ]
 });
 
@@ -46,6 +77,14 @@ pub async fn dispatch_command(
         DisableCodeCommand::COMMAND => DisableCodeCommand::handle(context, args).await,
         FixFormatCommand::COMMAND => FixFormatCommand::handle(context, args).await,
         AddDocTagCommand::COMMAND => AddDocTagCommand::handle(context, args).await,
+        ChangeSignatureCommand::COMMAND => ChangeSignatureCommand::handle(context, args).await,
+        AddGlobalCommand::COMMAND => AddGlobalCommand::handle(context, args).await,
+        FixAllCommand::COMMAND => FixAllCommand::handle(context, args).await,
+        GenerateAnnotationsCommand::COMMAND => {
+            GenerateAnnotationsCommand::handle(context, args).await
+        }
+        MoveSymbolCommand::COMMAND => MoveSymbolCommand::handle(context, args).await,
+        SafeDeleteCommand::COMMAND => SafeDeleteCommand::handle(context, args).await,
         _ => Some(()),
     }
 }
@@ -0,0 +1,11 @@
+use emmylua_code_analysis::{LuaLabel, SemanticModel};
+use lsp_types::GotoDefinitionResponse;
+
+pub fn goto_label(
+    semantic_model: &SemanticModel,
+    label: &LuaLabel,
+) -> Option<GotoDefinitionResponse> {
+    let document = semantic_model.get_document();
+    let location = document.to_lsp_location(label.get_range())?;
+    Some(GotoDefinitionResponse::Scalar(location))
+}
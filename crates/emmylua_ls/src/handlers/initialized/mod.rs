@@ -13,14 +13,14 @@ use crate::{
     },
     handlers::{
         initialized::collect_files::calculate_include_and_exclude,
-        text_document::register_files_watch,
+        text_document::register_files_watch, type_hierarchy::register_type_hierarchy,
     },
     logger::init_logger,
 };
 pub use client_config::{ClientConfig, get_client_config};
 use codestyle::load_editorconfig;
 use collect_files::collect_files;
-use emmylua_code_analysis::{EmmyLuaAnalysis, Emmyrc, uri_to_file_path};
+use emmylua_code_analysis::{EmmyLuaAnalysis, Emmyrc, LuaIndexCache, uri_to_file_path};
 use lsp_types::InitializeParams;
 use tokio::sync::RwLock;
 
@@ -75,7 +75,13 @@ pub async fn initialized_handler(
     load_editorconfig(workspace_folders.clone());
 
     // init std lib
-    init_std_lib(context.analysis(), &cmd_args, emmyrc.clone()).await;
+    init_std_lib(
+        context.analysis(),
+        &cmd_args,
+        &workspace_folders,
+        emmyrc.clone(),
+    )
+    .await;
 
     init_analysis(
         context.analysis(),
@@ -96,6 +102,7 @@ pub async fn initialized_handler(
         log::info!("workspace manager initialized");
     }
     register_files_watch(context.clone(), &params.capabilities).await;
+    register_type_hierarchy(context.client(), &params.capabilities);
     Some(())
 }
 
@@ -125,7 +132,6 @@ pub async fn init_analysis(
         Some("Loading workspace files".to_string()),
     );
 
-    let mut workspace_folders = workspace_folders;
     for workspace_root in &workspace_folders {
         log::info!("add workspace root: {:?}", workspace_root);
         mut_analysis.add_main_workspace(workspace_root.clone());
@@ -138,8 +144,17 @@ pub async fn init_analysis(
 
     for lib in &emmyrc.workspace.library {
         log::info!("add library: {:?}", lib);
-        mut_analysis.add_library_workspace(PathBuf::from_str(lib).unwrap());
-        workspace_folders.push(PathBuf::from_str(lib).unwrap());
+        status_bar.update_progress_task(
+            ProgressTask::LoadWorkspace,
+            None,
+            Some(format!("Indexing library {}", lib)),
+        );
+        let lib_root = PathBuf::from_str(lib).unwrap();
+        let files = collect_files(&vec![lib_root.clone()], &emmyrc)
+            .into_iter()
+            .map(|file| file.into_tuple())
+            .collect();
+        mut_analysis.add_library_workspace(lib_root, files);
     }
 
     status_bar.update_progress_task(
@@ -209,6 +224,7 @@ pub fn get_workspace_folders(params: &InitializeParams) -> Vec<PathBuf> {
 pub async fn init_std_lib(
     analysis: &RwLock<EmmyLuaAnalysis>,
     cmd_args: &CmdArgs,
+    workspace_folders: &[PathBuf],
     emmyrc: Arc<Emmyrc>,
 ) {
     log::info!(
@@ -216,6 +232,7 @@ pub async fn init_std_lib(
         cmd_args.resources_path
     );
     let mut analysis = analysis.write().await;
+    analysis.set_index_cache(LuaIndexCache::default_dir(workspace_folders).map(LuaIndexCache::new));
     if cmd_args.load_stdlib.0 {
         // double update config
         analysis.update_config(emmyrc);
@@ -2,9 +2,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use emmylua_code_analysis::{
-    AsyncState, FileId, InferGuard, LuaFunctionType, LuaMember, LuaMemberId, LuaMemberKey,
-    LuaMemberOwner, LuaOperatorId, LuaOperatorMetaMethod, LuaSemanticDeclId, LuaType, LuaTypeDecl,
-    SemanticModel,
+    AsyncState, FileId, LuaFunctionType, LuaMember, LuaMemberKey, LuaMemberOwner, LuaOperatorId,
+    LuaOperatorMetaMethod, LuaSemanticDeclId, LuaType, LuaTypeDecl, SemanticModel,
 };
 use emmylua_parser::{
     LuaAst, LuaAstNode, LuaCallExpr, LuaExpr, LuaFuncStat, LuaIndexExpr, LuaIndexKey,
@@ -373,82 +372,51 @@ fn build_func_stat_override_hint(
         let prefix_expr = index_expr.get_prefix_expr()?;
         let prefix_type = semantic_model.infer_expr(prefix_expr).ok()?;
         if let LuaType::Def(id) = prefix_type {
-            let supers = semantic_model
-                .get_db()
-                .get_type_index()
-                .get_super_types(&id)?;
-
             let index_key = index_expr.get_index_key()?;
             let member_key: LuaMemberKey = semantic_model.get_member_key(&index_key)?;
-            let guard = InferGuard::new();
-            for super_type in supers {
-                if let Some(member_id) =
-                    get_super_member_id(semantic_model, super_type, &member_key, &guard)
-                {
-                    let member = semantic_model
-                        .get_db()
-                        .get_member_index()
-                        .get_member(&member_id)?;
-
-                    let document = semantic_model.get_document();
-                    let last_paren_pos = func_stat
-                        .get_closure()?
-                        .get_params_list()?
-                        .get_range()
-                        .end();
-                    let last_paren_lsp_pos = document.to_lsp_position(last_paren_pos)?;
-
-                    let file_id = member.get_file_id();
-                    let syntax_id = member.get_syntax_id();
-                    let lsp_location =
-                        get_override_lsp_location(semantic_model, file_id, syntax_id)?;
-                    let hint = InlayHint {
-                        kind: Some(InlayHintKind::TYPE),
-                        label: InlayHintLabel::LabelParts(vec![InlayHintLabelPart {
-                            value: "override".to_string(),
-                            location: Some(lsp_location),
-                            ..Default::default()
-                        }]),
-                        position: last_paren_lsp_pos,
-                        text_edits: None,
-                        tooltip: None,
-                        padding_left: Some(true),
-                        padding_right: None,
-                        data: None,
-                    };
-                    result.push(hint);
-                    break;
-                }
-            }
+            let LuaSemanticDeclId::Member(member_id) = semantic_model
+                .find_super_member(&id, &member_key)?
+                .property_owner_id?
+            else {
+                return None;
+            };
+            let member = semantic_model
+                .get_db()
+                .get_member_index()
+                .get_member(&member_id)?;
+
+            let document = semantic_model.get_document();
+            let last_paren_pos = func_stat
+                .get_closure()?
+                .get_params_list()?
+                .get_range()
+                .end();
+            let last_paren_lsp_pos = document.to_lsp_position(last_paren_pos)?;
+
+            let file_id = member.get_file_id();
+            let syntax_id = member.get_syntax_id();
+            let lsp_location = get_override_lsp_location(semantic_model, file_id, syntax_id)?;
+            let hint = InlayHint {
+                kind: Some(InlayHintKind::TYPE),
+                label: InlayHintLabel::LabelParts(vec![InlayHintLabelPart {
+                    value: "override".to_string(),
+                    location: Some(lsp_location),
+                    ..Default::default()
+                }]),
+                position: last_paren_lsp_pos,
+                text_edits: None,
+                tooltip: None,
+                padding_left: Some(true),
+                padding_right: None,
+                data: None,
+            };
+            result.push(hint);
         }
     }
 
     Some(())
 }
 
-fn get_super_member_id(
-    semantic_model: &SemanticModel,
-    super_type: LuaType,
-    member_key: &LuaMemberKey,
-    infer_guard: &InferGuard,
-) -> Option<LuaMemberId> {
-    let super_type_id = match &super_type {
-        LuaType::Ref(id) => id,
-        LuaType::Generic(generic) => generic.get_base_type_id_ref(),
-        _ => return None,
-    };
-    infer_guard.check(super_type_id).ok()?;
-    let member_map = semantic_model.get_member_info_map(&super_type)?;
-
-    if let Some(member_infos) = member_map.get(member_key) {
-        let first_property = member_infos.first()?.property_owner_id.clone()?;
-        if let LuaSemanticDeclId::Member(member_id) = first_property {
-            return Some(member_id);
-        }
-    }
-    None
-}
-
 fn get_override_lsp_location(
     semantic_model: &SemanticModel,
     file_id: FileId,
@@ -29,6 +29,8 @@ mod response_handler;
 mod semantic_token;
 mod signature_helper;
 mod text_document;
+mod type_definition;
+mod type_hierarchy;
 mod workspace;
 mod workspace_symbol;
 
@@ -81,6 +83,7 @@ capabilities!(modules: {
     inlay_hint => InlayHintCapabilities,
     definition => DefinitionCapabilities,
     implementation => ImplementationCapabilities,
+    type_definition => TypeDefinitionCapabilities,
     references => ReferencesCapabilities,
     rename => RenameCapabilities,
     code_lens => CodeLensCapabilities,
@@ -1,8 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
 use emmylua_code_analysis::{
-    DeclReferenceCell, LuaCompilation, LuaDeclId, LuaMemberId, LuaMemberKey, LuaSemanticDeclId,
-    LuaTypeDeclId, SemanticDeclLevel, SemanticModel,
+    DeclReferenceCell, LuaCompilation, LuaDeclId, LuaLabel, LuaMemberId, LuaMemberKey,
+    LuaSemanticDeclId, LuaTypeDeclId, SemanticDeclLevel, SemanticModel,
 };
 use emmylua_parser::{
     LuaAssignStat, LuaAst, LuaAstNode, LuaAstToken, LuaNameToken, LuaStringToken, LuaSyntaxNode,
@@ -16,7 +16,9 @@ pub fn search_references(
     token: LuaSyntaxToken,
 ) -> Option<Vec<Location>> {
     let mut result = Vec::new();
-    if let Some(semantic_decl) =
+    if let Some(label) = semantic_model.find_token_label(&token) {
+        search_label_references(semantic_model, label, &mut result);
+    } else if let Some(semantic_decl) =
         semantic_model.find_decl(token.clone().into(), SemanticDeclLevel::default())
     {
         match semantic_decl {
@@ -92,6 +94,21 @@ pub fn search_decl_references(
     Some(())
 }
 
+pub fn search_label_references(
+    semantic_model: &SemanticModel,
+    label: &LuaLabel,
+    result: &mut Vec<Location>,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    result.push(document.to_lsp_location(label.get_range())?);
+    for goto_stat in semantic_model.get_label_gotos(label) {
+        let name_token = goto_stat.get_label_name_token()?;
+        result.push(document.to_lsp_location(name_token.get_range())?);
+    }
+
+    Some(())
+}
+
 pub fn search_member_references(
     semantic_model: &SemanticModel,
     compilation: &LuaCompilation,
@@ -228,7 +245,7 @@ fn fuzzy_search_references(
     Some(())
 }
 
-fn search_type_decl_references(
+pub fn search_type_decl_references(
     semantic_model: &SemanticModel,
     type_decl_id: LuaTypeDeclId,
     result: &mut Vec<Location>,
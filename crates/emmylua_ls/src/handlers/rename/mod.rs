@@ -1,4 +1,5 @@
 mod rename_decl;
+mod rename_label;
 mod rename_member;
 mod rename_type;
 
@@ -11,9 +12,10 @@ use emmylua_parser::{
 };
 use lsp_types::{
     ClientCapabilities, OneOf, PrepareRenameResponse, RenameOptions, RenameParams,
-    ServerCapabilities, TextDocumentPositionParams, WorkspaceEdit,
+    ServerCapabilities, TextDocumentPositionParams, Uri, WorkspaceEdit,
 };
 use rename_decl::rename_decl_references;
+use rename_label::rename_label_references;
 use rename_member::rename_member_references;
 use rename_type::rename_type_references;
 use rowan::TokenAtOffset;
@@ -123,6 +125,11 @@ fn rename_references(
     new_name: String,
 ) -> Option<WorkspaceEdit> {
     let mut result = HashMap::new();
+    if let Some(label) = semantic_model.find_token_label(&token) {
+        rename_label_references(semantic_model, label, new_name, &mut result);
+        return build_workspace_edit(semantic_model, result);
+    }
+
     let semantic_decl = match get_target_node(token.clone()) {
         Some(node) => semantic_model.find_decl(node.into(), SemanticDeclLevel::NoTrace),
         None => semantic_model.find_decl(token.into(), SemanticDeclLevel::NoTrace),
@@ -147,6 +154,14 @@ fn rename_references(
         _ => {}
     }
 
+    build_workspace_edit(semantic_model, result)
+}
+
+#[allow(clippy::mutable_key_type)]
+fn build_workspace_edit(
+    semantic_model: &SemanticModel,
+    result: HashMap<Uri, HashMap<lsp_types::Range, String>>,
+) -> Option<WorkspaceEdit> {
     let changes = result
         .into_iter()
         .filter(|(uri, _)| {
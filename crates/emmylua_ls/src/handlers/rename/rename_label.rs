@@ -0,0 +1,27 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{LuaLabel, SemanticModel};
+use emmylua_parser::LuaAstToken;
+use lsp_types::Uri;
+
+#[allow(clippy::mutable_key_type)]
+pub fn rename_label_references(
+    semantic_model: &SemanticModel,
+    label: &LuaLabel,
+    new_name: String,
+    result: &mut HashMap<Uri, HashMap<lsp_types::Range, String>>,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let uri = document.get_uri();
+    let edits = result.entry(uri).or_default();
+    edits.insert(document.to_lsp_range(label.get_range())?, new_name.clone());
+    for goto_stat in semantic_model.get_label_gotos(label) {
+        let name_token = goto_stat.get_label_name_token()?;
+        edits.insert(
+            document.to_lsp_range(name_token.get_range())?,
+            new_name.clone(),
+        );
+    }
+
+    Some(())
+}
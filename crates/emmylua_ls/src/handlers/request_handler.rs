@@ -7,11 +7,12 @@ use lsp_types::request::{
     CodeActionRequest, CodeLensRequest, CodeLensResolve, ColorPresentationRequest, Completion,
     DocumentColor, DocumentDiagnosticRequest, DocumentHighlightRequest, DocumentLinkRequest,
     DocumentLinkResolve, DocumentSymbolRequest, ExecuteCommand, FoldingRangeRequest, Formatting,
-    GotoDefinition, GotoImplementation, HoverRequest, InlayHintRequest, InlayHintResolveRequest,
-    InlineValueRequest, OnTypeFormatting, PrepareRenameRequest, RangeFormatting, References,
-    Rename, Request as LspRequest, ResolveCompletionItem, SelectionRangeRequest,
-    SemanticTokensFullRequest, SignatureHelpRequest, WorkspaceDiagnosticRequest,
-    WorkspaceSymbolRequest,
+    GotoDefinition, GotoImplementation, GotoTypeDefinition, HoverRequest, InlayHintRequest,
+    InlayHintResolveRequest, InlineValueRequest, OnTypeFormatting, PrepareRenameRequest,
+    RangeFormatting, References, Rename, Request as LspRequest, ResolveCompletionItem,
+    SelectionRangeRequest, SemanticTokensFullDeltaRequest, SemanticTokensFullRequest,
+    SemanticTokensRangeRequest, SignatureHelpRequest, TypeHierarchyPrepare, TypeHierarchySubtypes,
+    TypeHierarchySupertypes, WorkspaceDiagnosticRequest, WorkspaceSymbolRequest,
 };
 
 use crate::{
@@ -46,8 +47,14 @@ use super::{
     inline_values::on_inline_values_handler,
     references::on_references_handler,
     rename::{on_prepare_rename_handler, on_rename_handler},
-    semantic_token::on_semantic_token_handler,
+    semantic_token::{
+        on_semantic_token_delta_handler, on_semantic_token_handler, on_semantic_token_range_handler,
+    },
     signature_helper::on_signature_helper_handler,
+    type_definition::on_type_definition_handler,
+    type_hierarchy::{
+        on_prepare_type_hierarchy_handler, on_subtypes_handler, on_supertypes_handler,
+    },
     workspace_symbol::on_workspace_symbol_handler,
 };
 
@@ -101,6 +108,7 @@ pub async fn on_request_handler(
         InlayHintResolveRequest => on_resolve_inlay_hint,
         GotoDefinition => on_goto_definition_handler,
         GotoImplementation => on_implementation_handler,
+        GotoTypeDefinition => on_type_definition_handler,
         References => on_references_handler,
         Rename => on_rename_handler,
         PrepareRenameRequest => on_prepare_rename_handler,
@@ -109,6 +117,8 @@ pub async fn on_request_handler(
         SignatureHelpRequest => on_signature_helper_handler,
         DocumentHighlightRequest => on_document_highlight_handler,
         SemanticTokensFullRequest => on_semantic_token_handler,
+        SemanticTokensFullDeltaRequest => on_semantic_token_delta_handler,
+        SemanticTokensRangeRequest => on_semantic_token_range_handler,
         ExecuteCommand => on_execute_command_handler,
         CodeActionRequest => on_code_action_handler,
         InlineValueRequest => on_inline_values_handler,
@@ -119,6 +129,9 @@ pub async fn on_request_handler(
         CallHierarchyPrepare => on_prepare_call_hierarchy_handler,
         CallHierarchyIncomingCalls => on_incoming_calls_handler,
         CallHierarchyOutgoingCalls => on_outgoing_calls_handler,
+        TypeHierarchyPrepare => on_prepare_type_hierarchy_handler,
+        TypeHierarchySupertypes => on_supertypes_handler,
+        TypeHierarchySubtypes => on_subtypes_handler,
         DocumentDiagnosticRequest => on_pull_document_diagnostic,
         WorkspaceDiagnosticRequest => on_pull_workspace_diagnostic,
     });
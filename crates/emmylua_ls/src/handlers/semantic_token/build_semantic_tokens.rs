@@ -17,13 +17,14 @@ use emmylua_parser::{
 };
 use emmylua_parser_desc::{CodeBlockHighlightKind, DescItem, DescItemKind};
 use lsp_types::{SemanticToken, SemanticTokenModifier, SemanticTokenType};
-use rowan::{NodeOrToken, TextRange, TextSize};
+use rowan::{NodeOrToken, TextRange, TextSize, WalkEvent};
 
 pub fn build_semantic_tokens(
     semantic_model: &SemanticModel,
     support_muliline_token: bool,
     client_id: ClientId,
     emmyrc: &Emmyrc,
+    range: Option<TextRange>,
 ) -> Option<Vec<SemanticToken>> {
     let root = semantic_model.get_root();
     let document = semantic_model.get_document();
@@ -33,8 +34,33 @@ pub fn build_semantic_tokens(
         SEMANTIC_TOKEN_TYPES.to_vec(),
         SEMANTIC_TOKEN_MODIFIERS.to_vec(),
     );
+    if let Some(range) = range {
+        let lsp_range = document.to_lsp_range(range)?;
+        // a range ending at the start of a line does not include that line
+        let end_line = if lsp_range.end.character == 0 && lsp_range.end.line > lsp_range.start.line
+        {
+            lsp_range.end.line - 1
+        } else {
+            lsp_range.end.line
+        };
+        builder.set_line_range(lsp_range.start.line, end_line);
+    }
+
+    let mut preorder = root.syntax().preorder_with_tokens();
+    while let Some(event) = preorder.next() {
+        let WalkEvent::Enter(node_or_token) = event else {
+            continue;
+        };
+        // skip the subtrees outside of the requested range
+        if let Some(range) = range
+            && node_or_token.text_range().intersect(range).is_none()
+        {
+            if node_or_token.as_node().is_some() {
+                preorder.skip_subtree();
+            }
+            continue;
+        }
 
-    for node_or_token in root.syntax().descendants_with_tokens() {
         match node_or_token {
             NodeOrToken::Node(node) => {
                 build_node_semantic_token(semantic_model, &mut builder, node, emmyrc);
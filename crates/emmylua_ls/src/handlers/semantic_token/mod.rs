@@ -7,10 +7,13 @@ use crate::context::{ClientId, ServerContextSnapshot};
 use build_semantic_tokens::build_semantic_tokens;
 use emmylua_code_analysis::{EmmyLuaAnalysis, FileId};
 use lsp_types::{
-    ClientCapabilities, SemanticTokens, SemanticTokensFullOptions, SemanticTokensLegend,
-    SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult,
+    ClientCapabilities, Range, SemanticToken, SemanticTokens, SemanticTokensDelta,
+    SemanticTokensDeltaParams, SemanticTokensEdit, SemanticTokensFullDeltaResult,
+    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, SemanticTokensParams,
+    SemanticTokensRangeParams, SemanticTokensRangeResult, SemanticTokensResult,
     SemanticTokensServerCapabilities, ServerCapabilities,
 };
+use rowan::{TextRange, TextSize};
 #[allow(unused)]
 pub use semantic_token_builder::{
     CustomSemanticTokenType, SEMANTIC_TOKEN_MODIFIERS, SEMANTIC_TOKEN_TYPES,
@@ -32,11 +35,84 @@ pub async fn on_semantic_token_handler(
     let client_id = workspace_manager.client_config.client_id;
     let _ = workspace_manager;
 
-    semantic_token(
+    let result = semantic_token(
         &analysis,
         file_id,
         context.lsp_features().supports_multiline_tokens(),
         client_id,
+    )?;
+    let SemanticTokensResult::Tokens(mut tokens) = result else {
+        return Some(result);
+    };
+    tokens.result_id = Some(
+        context
+            .semantic_tokens_cache()
+            .save(uri, tokens.data.clone()),
+    );
+
+    Some(SemanticTokensResult::Tokens(tokens))
+}
+
+pub async fn on_semantic_token_delta_handler(
+    context: ServerContextSnapshot,
+    params: SemanticTokensDeltaParams,
+    _: CancellationToken,
+) -> Option<SemanticTokensFullDeltaResult> {
+    let uri = params.text_document.uri;
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+
+    let workspace_manager = context.workspace_manager().read().await;
+    let client_id = workspace_manager.client_config.client_id;
+    let _ = workspace_manager;
+
+    let SemanticTokensResult::Tokens(tokens) = semantic_token(
+        &analysis,
+        file_id,
+        context.lsp_features().supports_multiline_tokens(),
+        client_id,
+    )?
+    else {
+        return None;
+    };
+
+    let cache = context.semantic_tokens_cache();
+    let previous_tokens = cache.take(&uri, &params.previous_result_id);
+    let result_id = Some(cache.save(uri, tokens.data.clone()));
+    match previous_tokens {
+        Some(previous_tokens) => Some(SemanticTokensFullDeltaResult::TokensDelta(
+            SemanticTokensDelta {
+                result_id,
+                edits: semantic_tokens_edits(&previous_tokens, &tokens.data),
+            },
+        )),
+        // the client has an older result, send all tokens
+        None => Some(SemanticTokensFullDeltaResult::Tokens(SemanticTokens {
+            result_id,
+            data: tokens.data,
+        })),
+    }
+}
+
+pub async fn on_semantic_token_range_handler(
+    context: ServerContextSnapshot,
+    params: SemanticTokensRangeParams,
+    _: CancellationToken,
+) -> Option<SemanticTokensRangeResult> {
+    let uri = params.text_document.uri;
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+
+    let workspace_manager = context.workspace_manager().read().await;
+    let client_id = workspace_manager.client_config.client_id;
+    let _ = workspace_manager;
+
+    semantic_token_range(
+        &analysis,
+        file_id,
+        params.range,
+        context.lsp_features().supports_multiline_tokens(),
+        client_id,
     )
 }
 
@@ -57,6 +133,7 @@ pub fn semantic_token(
         supports_multiline_tokens,
         client_id,
         emmyrc,
+        None,
     )?;
 
     Some(SemanticTokensResult::Tokens(SemanticTokens {
@@ -65,6 +142,73 @@ pub fn semantic_token(
     }))
 }
 
+pub fn semantic_token_range(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    range: Range,
+    supports_multiline_tokens: bool,
+    client_id: ClientId,
+) -> Option<SemanticTokensRangeResult> {
+    let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
+    let emmyrc = semantic_model.get_emmyrc();
+    if !emmyrc.semantic_tokens.enable {
+        return None;
+    }
+
+    // the client may send a range that ends after the last line
+    let document = semantic_model.get_document();
+    let text_len = TextSize::of(document.get_text());
+    let start = document
+        .get_offset(range.start.line as usize, range.start.character as usize)
+        .unwrap_or(text_len);
+    let end = document
+        .get_offset(range.end.line as usize, range.end.character as usize)
+        .unwrap_or(text_len);
+    let result = build_semantic_tokens(
+        &semantic_model,
+        supports_multiline_tokens,
+        client_id,
+        emmyrc,
+        Some(TextRange::new(start, end.max(start))),
+    )?;
+
+    Some(SemanticTokensRangeResult::Tokens(SemanticTokens {
+        result_id: None,
+        data: result,
+    }))
+}
+
+/// A single edit replacing the tokens between the common prefix and suffix of both arrays,
+/// `start` and `delete_count` count integers, five per token
+pub fn semantic_tokens_edits(
+    previous: &[SemanticToken],
+    current: &[SemanticToken],
+) -> Vec<SemanticTokensEdit> {
+    let prefix_len = previous
+        .iter()
+        .zip(current)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix_len = previous[prefix_len..]
+        .iter()
+        .rev()
+        .zip(current[prefix_len..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let deleted = previous.len() - prefix_len - suffix_len;
+    let inserted = &current[prefix_len..current.len() - suffix_len];
+    if deleted == 0 && inserted.is_empty() {
+        return vec![];
+    }
+
+    vec![SemanticTokensEdit {
+        start: (prefix_len * 5) as u32,
+        delete_count: (deleted * 5) as u32,
+        data: Some(inserted.to_vec()),
+    }]
+}
+
 pub struct SemanticTokenCapabilities;
 
 impl RegisterCapabilities for SemanticTokenCapabilities {
@@ -78,7 +222,8 @@ impl RegisterCapabilities for SemanticTokenCapabilities {
                     token_modifiers: SEMANTIC_TOKEN_MODIFIERS.to_vec(),
                     token_types: SEMANTIC_TOKEN_TYPES.to_vec(),
                 },
-                full: Some(SemanticTokensFullOptions::Bool(true)),
+                full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                range: Some(true),
                 ..Default::default()
             }),
         );
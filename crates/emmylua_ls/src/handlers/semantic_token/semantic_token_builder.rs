@@ -77,6 +77,7 @@ pub struct SemanticBuilder<'a> {
     modifier_to_id: HashMap<SemanticTokenModifier, u32>,
     data: HashMap<TextSize, SemanticTokenData>,
     string_special_range: HashSet<TextRange>,
+    line_range: Option<(u32, u32)>,
 }
 
 impl<'a> SemanticBuilder<'a> {
@@ -102,9 +103,15 @@ impl<'a> SemanticBuilder<'a> {
             modifier_to_id,
             data: HashMap::new(),
             string_special_range: HashSet::new(),
+            line_range: None,
         }
     }
 
+    /// Only the tokens on the lines from `start_line` to `end_line` are built
+    pub fn set_line_range(&mut self, start_line: u32, end_line: u32) {
+        self.line_range = Some((start_line, end_line));
+    }
+
     fn push_data(&mut self, range: TextRange, text: &str, typ: u32, modifiers: u32) -> Option<()> {
         let position = range.start();
         if self.data.contains_key(&position) {
@@ -256,6 +263,10 @@ impl<'a> SemanticBuilder<'a> {
             }
         }
 
+        if let Some((start_line, end_line)) = self.line_range {
+            data.retain(|token_data| token_data.line >= start_line && token_data.line <= end_line);
+        }
+
         data.sort_by(|a, b| {
             let line1 = a.line;
             let line2 = b.line;
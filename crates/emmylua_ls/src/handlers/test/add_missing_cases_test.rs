@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_add_missing_enum_cases() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def(
+            r#"
+                ---@enum Color
+                Color = { Red = 1, Green = 2, Blue = 3 }
+            "#,
+        );
+        check!(ws.check_quick_fix(
+            r#"
+                ---@param c Color
+                local function paint(c)
+                    if c == Color.Red then
+                        return "red"
+                    else
+                        error("unknown color")
+                    end
+                end
+            "#,
+            "Add missing cases",
+            r#"
+                ---@param c Color
+                local function paint(c)
+                    if c == Color.Red then
+                        return "red"
+                    elseif c == Color.Green then
+                        error("not implemented")
+                    elseif c == Color.Blue then
+                        error("not implemented")
+                    else
+                        error("unknown color")
+                    end
+                end
+            "#,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_add_missing_literal_cases() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_quick_fix(
+            r#"
+                ---@param mode "r"|"w"|"a"
+                local function open(mode)
+                    ---@[exhaustive]
+                    if mode == "r" then
+                        return 1
+                    end
+                end
+            "#,
+            "Add missing cases",
+            r#"
+                ---@param mode "r"|"w"|"a"
+                local function open(mode)
+                    ---@[exhaustive]
+                    if mode == "r" then
+                        return 1
+                    elseif mode == "w" then
+                        error("not implemented")
+                    elseif mode == "a" then
+                        error("not implemented")
+                    end
+                end
+            "#,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_add_missing_cases_single_line() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def(
+            r#"
+                ---@enum E
+                E = { A = 1, B = 2 }
+            "#,
+        );
+        check!(ws.check_quick_fix(
+            r#"
+                ---@param x E
+                local function run(x)
+                    if x == E.A then a() else error() end
+                end
+            "#,
+            "Add missing cases",
+            r#"
+                ---@param x E
+                local function run(x)
+                    if x == E.A then a()
+                    elseif x == E.B then
+                        error("not implemented")
+                    else error() end
+                end
+            "#,
+        ));
+        Ok(())
+    }
+}
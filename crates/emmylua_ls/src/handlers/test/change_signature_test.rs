@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::{
+        command::SignatureParam,
+        test_lib::{ProviderVirtualWorkspace, check},
+    };
+    use googletest::prelude::*;
+
+    fn param(name: &str, old_index: Option<usize>, default_value: Option<&str>) -> SignatureParam {
+        SignatureParam {
+            name: name.to_string(),
+            old_index,
+            default_value: default_value.map(|value| value.to_string()),
+        }
+    }
+
+    #[gtest]
+    fn test_reorder_rename_add() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_change_signature(
+            r#"
+                ---@param a integer
+                ---@param b string
+                local function f<??>oo(a, b)
+                    print(a, b)
+                end
+                foo(1, "x")
+                foo(2)
+            "#,
+            vec![
+                param("name", Some(1), None),
+                param("a", Some(0), None),
+                param("flag", None, Some("true")),
+            ],
+            Some(
+                r#"
+                ---@param name string
+                ---@param a integer
+                ---@param flag boolean
+                local function foo(name, a, flag)
+                    print(a, name)
+                end
+                foo("x", 1, true)
+                foo(nil, 2, true)
+            "#
+            ),
+            vec![],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_remove_param() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_change_signature(
+            r#"
+                function lo<??>g(level, message, ...)
+                    print(message, ...)
+                end
+                log(1, "a", "b")
+            "#,
+            vec![param("message", Some(1), None)],
+            Some(
+                r#"
+                function log(message, ...)
+                    print(message, ...)
+                end
+                log("a", "b")
+            "#
+            ),
+            vec![],
+        ));
+        // the body still reads the parameter
+        check!(ws.check_change_signature(
+            r#"
+                local function <??>add(a, b)
+                    return a + b
+                end
+            "#,
+            vec![param("a", Some(0), None)],
+            None,
+            vec![],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_method_calls_in_workspace() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let other = ws.def_file(
+            "other.lua",
+            r#"
+                ---@type Point
+                local p
+                p:move(1, 2)
+                Point.move(p, 3, 4)
+            "#,
+        );
+        check!(ws.check_change_signature(
+            r#"
+                ---@class Point
+                Point = {}
+
+                function Point:mo<??>ve(x, y)
+                    return x + y
+                end
+            "#,
+            vec![param("y", Some(1), None), param("x", Some(0), None)],
+            Some(
+                r#"
+                ---@class Point
+                Point = {}
+
+                function Point:move(y, x)
+                    return x + y
+                end
+            "#
+            ),
+            vec![(
+                other,
+                r#"
+                ---@type Point
+                local p
+                p:move(2, 1)
+                Point.move(p, 4, 3)
+            "#
+            )],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_calls_not_changed() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_change_signature_error(
+            r#"
+                local function f<??>oo(a, b)
+                end
+                local g = foo
+                foo(1, 2)
+            "#,
+            vec![param("b", Some(1), None), param("a", Some(0), None)],
+            "Function `foo` is used without being called at virtual_0.lua:4",
+        ));
+        // the receiver of `obj:f()` stays the first parameter
+        check!(ws.check_change_signature_error(
+            r#"
+                local M = {}
+                function M.f<??>(self, x)
+                end
+                M:f(1)
+            "#,
+            vec![param("x", Some(1), None), param("self", Some(0), None)],
+            "Can not change the call at virtual_1.lua:5",
+        ));
+        check!(ws.check_change_signature_error(
+            r#"
+                local function f<??>oo(a, b)
+                    return b
+                end
+                foo(table.unpack({ 1, 2 }))
+            "#,
+            vec![param("b", Some(1), None)],
+            "The last argument of the call at virtual_2.lua:5 may give several values",
+        ));
+        check!(ws.check_change_signature_error(
+            r#"
+                local function f<??>oo(a, b)
+                end
+                local function bar(...)
+                    foo(1, ...)
+                end
+            "#,
+            vec![param("b", Some(1), None), param("a", Some(0), None)],
+            "The last argument of the call at virtual_3.lua:5 may give several values",
+        ));
+        check!(ws.check_change_signature_error(
+            r#"
+                local function f<??>oo(a, b)
+                end
+                local t = {}
+                foo(t.a, t.b)
+            "#,
+            vec![param("b", Some(1), None), param("a", Some(0), None)],
+            "The call at virtual_4.lua:5 has arguments with side effects that would be moved or removed",
+        ));
+        check!(ws.check_change_signature_error(
+            r#"
+                local function f<??>oo(a, b)
+                    return b
+                end
+                local t = {}
+                foo(t.a, 1)
+            "#,
+            vec![param("b", Some(1), None)],
+            "The call at virtual_5.lua:6 has arguments with side effects that would be moved or removed",
+        ));
+        // the last call still fills the parameters from its place
+        check!(ws.check_change_signature(
+            r#"
+                local function f<??>oo(a, b, c)
+                    return a, b, c
+                end
+                foo(1, 2, bar())
+            "#,
+            vec![
+                param("b", Some(1), None),
+                param("a", Some(0), None),
+                param("c", Some(2), None),
+            ],
+            Some(
+                r#"
+                local function foo(b, a, c)
+                    return a, b, c
+                end
+                foo(2, 1, bar())
+            "#
+            ),
+            vec![],
+        ));
+        Ok(())
+    }
+}
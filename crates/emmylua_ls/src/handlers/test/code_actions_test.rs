@@ -3,6 +3,7 @@ mod tests {
     use crate::handlers::test_lib::{ProviderVirtualWorkspace, VirtualCodeAction, check};
     use emmylua_code_analysis::{DiagnosticCode, Emmyrc};
     use googletest::prelude::*;
+    use lsp_types::CodeActionKind;
 
     #[gtest]
     fn test_1() -> Result<()> {
@@ -107,4 +108,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[gtest]
+    fn test_requested_kinds() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let code = r#"
+                local function he<??>lper(n)
+                    return n
+                end
+            "#;
+        check!(ws.check_requested_actions(
+            code,
+            vec![CodeActionKind::REFACTOR_REWRITE],
+            vec!["Generate annotations"],
+        ));
+        check!(ws.check_requested_actions(
+            code,
+            vec![CodeActionKind::REFACTOR],
+            vec!["Generate annotations", "Safe delete `helper`",],
+        ));
+        check!(ws.check_requested_actions(code, vec![CodeActionKind::QUICKFIX], vec![]));
+
+        // the client asks for the new parameters
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.code_action.change_signature = true;
+        ws.analysis.update_config(emmyrc.into());
+        check!(ws.check_requested_actions(
+            code,
+            vec![CodeActionKind::REFACTOR],
+            vec![
+                "Change signature",
+                "Generate annotations",
+                "Safe delete `helper`",
+            ],
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_dot_to_colon() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                ---@class Counter
+                local Counter = {}
+
+                ---@param self Counter
+                ---@param n integer
+                function Counter.a<??>dd(self, n)
+                    self.value = self.value + n
+                end
+
+                local c = Counter
+                c.add(c, 1)
+                Counter.add(c, 2)
+            "#,
+            "Convert to `Counter:add`",
+            Some(
+                r#"
+                ---@class Counter
+                local Counter = {}
+
+                ---@param n integer
+                function Counter:add(n)
+                    self.value = self.value + n
+                end
+
+                local c = Counter
+                c:add(1)
+                Counter.add(c, 2)
+            "#
+            ),
+        ));
+        // only a first parameter named `self` becomes the implicit one
+        check!(ws.check_refactor_action(
+            r#"
+                local M = {}
+                function M.g<??>et(obj)
+                    return obj
+                end
+            "#,
+            "Convert to `M:get`",
+            None,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_colon_to_dot() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                ---@class Counter
+                local Counter = {}
+
+                ---@param n integer
+                function Counter:a<??>dd(n)
+                    self.value = self.value + n
+                end
+
+                ---@return integer
+                function Counter:get()
+                    return self.value
+                end
+
+                Counter:add(1)
+                make():add(2)
+            "#,
+            "Convert to `Counter.add`",
+            Some(
+                r#"
+                ---@class Counter
+                local Counter = {}
+
+                ---@param self Counter
+                ---@param n integer
+                function Counter.add(self, n)
+                    self.value = self.value + n
+                end
+
+                ---@return integer
+                function Counter:get()
+                    return self.value
+                end
+
+                Counter.add(Counter, 1)
+                make():add(2)
+            "#
+            ),
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local M = {}
+                function M:re<??>set()
+                end
+                M:reset()
+            "#,
+            "Convert to `M.reset`",
+            Some(
+                r#"
+                local M = {}
+                function M.reset(self)
+                end
+                M.reset(M)
+            "#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_workspace_calls() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let other = ws.def_file(
+            "other.lua",
+            r#"
+                ---@type Stack
+                local stack
+                stack.push(stack, 1)
+            "#,
+        );
+        check!(ws.check_workspace_refactor_action(
+            r#"
+                ---@class Stack
+                local Stack = {}
+
+                function Stack.pu<??>sh(self, value)
+                    self[#self + 1] = value
+                end
+            "#,
+            "Convert to `Stack:push`",
+            r#"
+                ---@class Stack
+                local Stack = {}
+
+                function Stack:push(value)
+                    self[#self + 1] = value
+                end
+            "#,
+            vec![(
+                other,
+                r#"
+                ---@type Stack
+                local stack
+                stack:push(1)
+            "#
+            )],
+        ));
+        Ok(())
+    }
+}
@@ -554,4 +554,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[gtest]
+    fn test_goto_label() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_definition(
+            r#"
+                for i = 1, 10 do
+                    if i == 5 then
+                        goto con<??>tinue
+                    end
+                    ::continue::
+                end
+            "#,
+            vec![Expected {
+                file: "".to_string(),
+                line: 5
+            }]
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,247 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    const EXTRACT_LOCAL: &str = "Extract to local variable";
+    const EXTRACT_FUNCTION: &str = "Extract to function";
+
+    #[gtest]
+    fn test_extract_local() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local a = 1
+                print(<??>a + 2<??>)
+            "#,
+            EXTRACT_LOCAL,
+            Some(
+                r#"
+                local a = 1
+                local value = a + 2
+                print(value)
+            "#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_extract_local_name() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local value = {}
+                ---@type string
+                local s = " " .. <??>value:getName()<??>
+            "#,
+            EXTRACT_LOCAL,
+            Some(
+                r#"
+                local value = {}
+                local name = value:getName()
+                ---@type string
+                local s = " " .. name
+            "#
+            ),
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local name = 1
+                local t = { <??>get_name()<??>, 2 }
+            "#,
+            EXTRACT_LOCAL,
+            Some(
+                r#"
+                local name = 1
+                local name1 = get_name()
+                local t = { name1, 2 }
+            "#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_extract_local_not_allowed() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        // all the values of the last call or `...` in a list are used
+        check!(ws.check_refactor_action(
+            r#"
+                local t = { <??>get_name()<??> }
+            "#,
+            EXTRACT_LOCAL,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local function f(...)
+                    print(1, <??>...<??>)
+                end
+            "#,
+            EXTRACT_LOCAL,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local function f()
+                    return 1, <??>f()<??>
+                end
+            "#,
+            EXTRACT_LOCAL,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local i = 0
+                while <??>i < 10<??> do
+                    i = i + 1
+                end
+            "#,
+            EXTRACT_LOCAL,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                <??>print(1)<??>
+            "#,
+            EXTRACT_LOCAL,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local t = {}
+                <??>t.a<??> = 1
+            "#,
+            EXTRACT_LOCAL,
+            None,
+        ));
+        // the right operand of `and` and `or` is not always evaluated
+        check!(ws.check_refactor_action(
+            r#"
+                local t = nil
+                print(t and <??>t.a<??>)
+            "#,
+            EXTRACT_LOCAL,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local t = nil
+                print(t or get(<??>t.a + 1<??>))
+            "#,
+            EXTRACT_LOCAL,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local t = nil
+                print(<??>t.a<??> and t.b)
+            "#,
+            EXTRACT_LOCAL,
+            Some(
+                r#"
+                local t = nil
+                local a = t.a
+                print(a and t.b)
+            "#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_extract_function_params() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                ---@param items integer[]
+                local function sum(items)
+                    local total = 0
+                    <??>for _, v in ipairs(items) do
+                        total = total + v
+                    end<??>
+                    return total
+                end
+            "#,
+            EXTRACT_FUNCTION,
+            Some(
+                r#"
+                ---@param items integer[]
+                ---@param total integer
+                ---@return integer
+                local function extracted_function(items, total)
+                    for _, v in ipairs(items) do
+                        total = total + v
+                    end
+                    return total
+                end
+
+                ---@param items integer[]
+                local function sum(items)
+                    local total = 0
+                    total = extracted_function(items, total)
+                    return total
+                end
+            "#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_extract_function_returns() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local a = 1
+                <??>local b = a + 1
+                local c = "n" .. b<??>
+                print(c)
+            "#,
+            EXTRACT_FUNCTION,
+            Some(
+                r#"
+                local a = 1
+                ---@return string
+                local function extracted_function()
+                    local b = a + 1
+                    local c = "n" .. b
+                    return c
+                end
+
+                local c = extracted_function()
+                print(c)
+            "#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_extract_function_not_allowed() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local function f(a)
+                    <??>if a then
+                        return 1
+                    end<??>
+                    return 2
+                end
+            "#,
+            EXTRACT_FUNCTION,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local a = 1
+                local b = <??>a + 1
+                print(b)<??>
+            "#,
+            EXTRACT_FUNCTION,
+            None,
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_placeholders() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def(
+            r#"
+                ---@class Vec2
+                ---@field x number
+                ---@field y number
+
+                ---@class Player
+                ---@field name string
+                ---@field level integer
+                ---@field alive boolean
+                ---@field items table
+                ---@field position Vec2
+                ---@field title? string
+            "#,
+        );
+        check!(ws.check_quick_fix(
+            r#"
+                ---@type Player
+                local player = {}
+            "#,
+            "Fill missing fields",
+            r#"
+                ---@type Player
+                local player = { alive = false, items = {}, level = 0, name = "", position = { x = 0, y = 0 } }
+            "#,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_keep_layout() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def(
+            r#"
+                ---@class Config
+                ---@field host string
+                ---@field port integer
+                ---@field debug boolean
+            "#,
+        );
+        check!(ws.check_quick_fix(
+            r#"
+                ---@type Config
+                local a = {
+                    host = "localhost",
+                }
+            "#,
+            "Fill missing fields",
+            r#"
+                ---@type Config
+                local a = {
+                    host = "localhost",
+                    debug = false,
+                    port = 0,
+                }
+            "#,
+        ));
+        check!(ws.check_quick_fix(
+            r#"
+                ---@type Config
+                local b = {
+                  host = "localhost"; -- the server
+                }
+            "#,
+            "Fill missing fields",
+            r#"
+                ---@type Config
+                local b = {
+                  host = "localhost"; -- the server
+                  debug = false;
+                  port = 0;
+                }
+            "#,
+        ));
+        check!(ws.check_quick_fix(
+            r#"
+                ---@type Config
+                local c = { host = "localhost" }
+            "#,
+            "Fill missing fields",
+            r#"
+                ---@type Config
+                local c = { host = "localhost", debug = false, port = 0 }
+            "#,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_recursive_class() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def(
+            r#"
+                ---@class TreeNode
+                ---@field value integer
+                ---@field parent TreeNode
+            "#,
+        );
+        check!(ws.check_quick_fix(
+            r#"
+                ---@type TreeNode
+                local node = {
+                }
+            "#,
+            "Fill missing fields",
+            r#"
+                ---@type TreeNode
+                local node = {
+                    parent = { parent = {}, value = 0 },
+                    value = 0,
+                }
+            "#,
+        ));
+        Ok(())
+    }
+}
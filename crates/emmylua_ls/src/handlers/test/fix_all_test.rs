@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_fix_all() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new_with_init_std_lib();
+        check!(ws.check_fix_all(
+            r#"
+                local format = string.format
+                local rep = string.rep
+                print(string.format("%d", 1), string.rep("a", 2))
+            "#,
+            Some(
+                r#"
+                local format = string.format
+                local rep = string.rep
+                print(format("%d", 1), rep("a", 2))
+            "#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_skip_shadowed_alias() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new_with_init_std_lib();
+        check!(ws.check_fix_all(
+            r#"
+                local format = string.format
+                do
+                    local format = "%d"
+                    print(string.format(format, 1))
+                end
+            "#,
+            None,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_skip_unsafe() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new_with_init_std_lib();
+        ws.def(
+            r#"
+                ---@class Point
+                ---@field x number
+                ---@field y number
+
+                ---@enum Color
+                Color = { Red = 1, Green = 2 }
+            "#,
+        );
+        // placeholders, stubs, guessed names and the `---@cast` insertion are left to the user
+        check!(ws.check_fix_all(
+            r#"
+                ---@type Point
+                local p = {}
+                prnt(p.x)
+
+                ---@type string?
+                local s
+                print(s:len())
+
+                ---@param c Color
+                local function f(c)
+                    if c == Color.Red then
+                    else
+                        error("unknown color")
+                    end
+                end
+            "#,
+            None,
+        ));
+        Ok(())
+    }
+}
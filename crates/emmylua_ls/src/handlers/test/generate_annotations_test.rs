@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_function() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new_with_init_std_lib();
+        check!(ws.check_generate_definition_annotations(
+            r#"
+                local function re<??>peat_text(text, count)
+                    return string.rep(text, count), #text
+                end
+                repeat_text("a", 3)
+                repeat_text("b")
+            "#,
+            r#"
+                ---@param text string
+                ---@param count? integer
+                ---@return string
+                ---@return integer
+                local function repeat_text(text, count)
+                    return string.rep(text, count), #text
+                end
+                repeat_text("a", 3)
+                repeat_text("b")
+            "#,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_class() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local Cou<??>nter = {}
+                Counter.__index = Counter
+
+                function Counter.new(name)
+                    local counter = setmetatable({ name = name }, Counter)
+                    counter.value = 0
+                    return counter
+                end
+
+                function Counter:reset()
+                    self.value = 0
+                    self.resets = true
+                end
+            "#,
+            "Generate annotations",
+            Some(
+                r#"
+                ---@class Counter
+                ---@field name any
+                ---@field value integer
+                ---@field resets boolean
+                local Counter = {}
+                Counter.__index = Counter
+
+                function Counter.new(name)
+                    local counter = setmetatable({ name = name }, Counter)
+                    counter.value = 0
+                    return counter
+                end
+
+                function Counter:reset()
+                    self.value = 0
+                    self.resets = true
+                end
+            "#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_file() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_generate_annotations(
+            r#"
+                ---Adds two numbers
+                ---@param a number
+                function add(a, b)
+                    return a + b
+                end
+
+                function reset()
+                end
+
+                add(1.5, 2)
+            "#,
+            r#"
+                ---Adds two numbers
+                ---@param a number
+                ---@param b integer
+                ---@return number
+                function add(a, b)
+                    return a + b
+                end
+
+                ---
+                function reset()
+                end
+
+                add(1.5, 2)
+            "#,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_module_class() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_generate_workspace_annotations(
+            vec![(
+                "counter.lua",
+                r#"
+                local M = {}
+                M.__index = M
+
+                function M:reset()
+                    self.value = 0
+                end
+
+                return M
+            "#,
+            )],
+            vec![(
+                "counter.lua",
+                r#"
+                ---@class counter
+                ---@field value integer
+                local M = {}
+                M.__index = M
+
+                function M:reset()
+                    self.value = 0
+                end
+
+                return M
+            "#,
+            )],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_class_declared_once() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let code = r#"
+                local Point = {}
+
+                function Point:move(x)
+                    self.x = x
+                end
+            "#;
+        check!(ws.check_generate_workspace_annotations(
+            vec![("a.lua", code), ("b.lua", code)],
+            vec![
+                (
+                    "a.lua",
+                    r#"
+                ---@class Point
+                ---@field x any
+                local Point = {}
+
+                ---@param x any
+                function Point:move(x)
+                    self.x = x
+                end
+            "#,
+                ),
+                (
+                    "b.lua",
+                    r#"
+                local Point = {}
+
+                ---@param x any
+                function Point:move(x)
+                    self.x = x
+                end
+            "#,
+                ),
+            ],
+        ));
+        Ok(())
+    }
+}
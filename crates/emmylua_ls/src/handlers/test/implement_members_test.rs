@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_implement_members() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def(
+            r#"
+                ---@class Shape
+                ---@field area fun(self: Shape): number
+                ---@field move fun(self: Shape, dx: number, dy: number)
+                ---@field bounds fun(self: Shape): number, number
+            "#,
+        );
+        check!(ws.check_quick_fix(
+            r#"
+                ---@class Circle: Shape
+                local Circle = {}
+
+                function Circle:area()
+                    return 0
+                end
+            "#,
+            "Implement missing methods",
+            r#"
+                ---@class Circle: Shape
+                local Circle = {}
+
+                function Circle:area()
+                    return 0
+                end
+
+                ---@param dx number
+                ---@param dy number
+                function Circle:move(dx, dy)
+                    error("not implemented")
+                end
+
+                ---@return number
+                ---@return number
+                function Circle:bounds()
+                    error("not implemented")
+                end
+            "#,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_implement_inherited_members() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def(
+            r#"
+                ---@class Reader
+                ---@field read fun(self: Reader, n: integer): string?
+
+                ---@class Stream: Reader
+                ---@field close fun(self: Stream)
+            "#,
+        );
+        check!(ws.check_quick_fix(
+            r#"
+                local M = {}
+
+                ---@class FileStream: Stream
+                M.FileStream = {}
+            "#,
+            "Implement missing methods",
+            r#"
+                local M = {}
+
+                ---@class FileStream: Stream
+                M.FileStream = {}
+
+                function M.FileStream:close()
+                    error("not implemented")
+                end
+
+                ---@param n integer
+                ---@return string?
+                function M.FileStream:read(n)
+                    error("not implemented")
+                end
+            "#,
+        ));
+        Ok(())
+    }
+}
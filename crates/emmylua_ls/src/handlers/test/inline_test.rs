@@ -0,0 +1,313 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    const INLINE_VARIABLE: &str = "Inline variable";
+    const INLINE_FUNCTION: &str = "Inline function";
+
+    #[gtest]
+    fn test_inline_variable() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local a = 1
+                local su<??>m = a + 2
+                print(sum, sum * 3)
+            "#,
+            INLINE_VARIABLE,
+            Some(
+                r#"
+                local a = 1
+                print(a + 2, (a + 2) * 3)
+            "#
+            ),
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                ---@type string
+                local name = "world"
+                print("hello " .. na<??>me, name:upper())
+            "#,
+            INLINE_VARIABLE,
+            Some(
+                r#"
+                print("hello " .. "world", ("world"):upper())
+            "#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_inline_variable_call() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local val<??>ue = get()
+                print(value)
+            "#,
+            INLINE_VARIABLE,
+            Some(
+                r#"
+                print((get()))
+            "#
+            ),
+        ));
+        // the call would run once for each use
+        check!(ws.check_refactor_action(
+            r#"
+                local val<??>ue = get()
+                print(value, value)
+            "#,
+            INLINE_VARIABLE,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local val<??>ue = {}
+                for i = 1, 3 do
+                    print(value)
+                end
+            "#,
+            INLINE_VARIABLE,
+            None,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_inline_variable_order() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local t = { a = 1 }
+                local val<??>ue = t.a
+                print(value)
+            "#,
+            INLINE_VARIABLE,
+            Some(
+                r#"
+                local t = { a = 1 }
+                print(t.a)
+            "#
+            ),
+        ));
+        // the call would run after `g()` instead of before it
+        check!(ws.check_refactor_action(
+            r#"
+                local val<??>ue = f()
+                g()
+                print(value)
+            "#,
+            INLINE_VARIABLE,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local val<??>ue = f()
+                print(g(), value)
+            "#,
+            INLINE_VARIABLE,
+            None,
+        ));
+        // the field would be read after it is changed
+        check!(ws.check_refactor_action(
+            r#"
+                local t = { a = 1 }
+                local val<??>ue = t.a
+                t.a = 5
+                print(value)
+            "#,
+            INLINE_VARIABLE,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local t = { a = 1 }
+                local val<??>ue = t.a
+                if t then
+                    t.a = 5
+                    print(value)
+                end
+            "#,
+            INLINE_VARIABLE,
+            None,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_inline_variable_not_allowed() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local cou<??>nt = 0
+                local function inc()
+                    count = count + 1
+                end
+            "#,
+            INLINE_VARIABLE,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local a = 1
+                local b<??> = a
+                a = 2
+                print(b)
+            "#,
+            INLINE_VARIABLE,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local a = 1
+                local b<??> = a
+                do
+                    local a = 2
+                    print(b)
+                end
+            "#,
+            INLINE_VARIABLE,
+            None,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_inline_function_expr() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local function add(a, b)
+                    return a + b
+                end
+                local x = 1
+                print(a<??>dd(x, 2) * 3)
+            "#,
+            INLINE_FUNCTION,
+            Some(
+                r#"
+                local function add(a, b)
+                    return a + b
+                end
+                local x = 1
+                print((x + 2) * 3)
+            "#
+            ),
+        ));
+        // a function with several statements does not fit in an expression
+        check!(ws.check_refactor_action(
+            r#"
+                local function f(a)
+                    print(a)
+                    return a
+                end
+                local y = <??>f(1)
+            "#,
+            INLINE_FUNCTION,
+            None,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_inline_function_stat() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local function log(message)
+                    print("[log] " .. message)
+                end
+
+                if true then
+                    l<??>og("start")
+                end
+            "#,
+            INLINE_FUNCTION,
+            Some(
+                r#"
+                local function log(message)
+                    print("[log] " .. message)
+                end
+
+                if true then
+                    print("[log] " .. "start")
+                end
+            "#
+            ),
+        ));
+        // arguments with side effects are evaluated once, before the body
+        check!(ws.check_refactor_action(
+            r#"
+                local function twice(s)
+                    print(s)
+                    print(s)
+                end
+                <??>twice(read())
+            "#,
+            INLINE_FUNCTION,
+            Some(
+                r#"
+                local function twice(s)
+                    print(s)
+                    print(s)
+                end
+                do
+                    local s = read()
+                    print(s)
+                    print(s)
+                end
+            "#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_inline_function_not_allowed() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local function f()
+                    return 1
+                end
+                f = function()
+                    return 2
+                end
+                print(<??>f())
+            "#,
+            INLINE_FUNCTION,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local n = 1
+                local function get()
+                    return n
+                end
+                do
+                    local n = 2
+                    print(<??>get())
+                end
+            "#,
+            INLINE_FUNCTION,
+            None,
+        ));
+        check!(ws.check_refactor_action(
+            r#"
+                local function check(a)
+                    if a then
+                        return
+                    end
+                    print(a)
+                end
+                <??>check(1)
+            "#,
+            INLINE_FUNCTION,
+            None,
+        ));
+        Ok(())
+    }
+}
@@ -1,12 +1,27 @@
+mod add_missing_cases_test;
 mod code_actions_test;
 mod completion_resolve_test;
+mod change_signature_test;
 mod completion_test;
+mod convert_method_test;
 mod definition_test;
+mod extract_test;
+mod fill_missing_fields_test;
+mod fix_all_test;
+mod generate_annotations_test;
 mod hover_function_test;
 mod hover_test;
+mod implement_members_test;
+mod inline_test;
+mod move_symbol_test;
 mod implementation_test;
 mod inlay_hint_test;
+mod organize_requires_test;
 mod references_test;
 mod rename_test;
+mod safe_delete_test;
 mod semantic_token_test;
 mod signature_helper_test;
+mod similar_name_test;
+mod type_definition_test;
+mod type_hierarchy_test;
@@ -0,0 +1,288 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_move_function() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_move_symbol(
+            vec![
+                (
+                    "util.lua",
+                    r#"
+                    local M = {}
+
+                    function M.log(msg)
+                        print(msg)
+                    end
+
+                    ---Pad the text
+                    ---@param text string
+                    ---@param width integer
+                    ---@return string
+                    function M.p<??>ad(text, width)
+                        return string.rep(" ", width - #text) .. text
+                    end
+
+                    return M
+                    "#,
+                ),
+                (
+                    "strutil.lua",
+                    r#"
+                    local M = {}
+
+                    function M.trim(s)
+                        return s
+                    end
+
+                    return M
+                    "#,
+                ),
+                (
+                    "main.lua",
+                    r#"
+                    local util = require("util")
+                    print(util.pad("a", 3))
+                    "#,
+                ),
+                (
+                    "other.lua",
+                    r#"local util = require("util")
+                    util.log("x")
+                    util.pad("b", 1)
+                    "#,
+                ),
+            ],
+            "strutil",
+            false,
+            Some(vec![
+                (
+                    "util.lua",
+                    r#"
+                    local M = {}
+
+                    function M.log(msg)
+                        print(msg)
+                    end
+
+                    return M
+                    "#,
+                ),
+                (
+                    "strutil.lua",
+                    r#"
+                    local M = {}
+
+                    function M.trim(s)
+                        return s
+                    end
+
+                    ---Pad the text
+                    ---@param text string
+                    ---@param width integer
+                    ---@return string
+                    function M.pad(text, width)
+                        return string.rep(" ", width - #text) .. text
+                    end
+
+                    return M
+                    "#,
+                ),
+                (
+                    "main.lua",
+                    r#"
+                    local strutil = require("strutil")
+                    print(strutil.pad("a", 3))
+                    "#,
+                ),
+                (
+                    "other.lua",
+                    r#"local util = require("util")
+local strutil = require("strutil")
+                    util.log("x")
+                    strutil.pad("b", 1)
+                    "#,
+                ),
+            ]),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_move_with_re_export() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_move_symbol(
+            vec![
+                (
+                    "app.lua",
+                    r#"local App = {}
+
+                    App.default_<??>port = 8080
+
+                    function App.start()
+                        print(App.default_port)
+                    end
+
+                    return App
+                    "#,
+                ),
+                (
+                    "config.lua",
+                    r#"
+                    local Config = {}
+                    return Config
+                    "#,
+                ),
+            ],
+            "config",
+            true,
+            Some(vec![
+                (
+                    "app.lua",
+                    r#"local config = require("config")
+local App = {}
+
+                    App.default_port = config.default_port
+
+                    function App.start()
+                        print(config.default_port)
+                    end
+
+                    return App
+                    "#,
+                ),
+                (
+                    "config.lua",
+                    r#"
+                    local Config = {}
+                    Config.default_port = 8080
+
+                    return Config
+                    "#,
+                ),
+            ]),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_move_refused() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let target = (
+            "target.lua",
+            r#"
+            local T = {}
+            return T
+            "#,
+        );
+        // the function reads a local of the old module
+        check!(ws.check_move_symbol(
+            vec![
+                (
+                    "source.lua",
+                    r#"
+                    local M = {}
+                    local count = 0
+
+                    function M.n<??>ext()
+                        count = count + 1
+                        return count
+                    end
+
+                    return M
+                    "#,
+                ),
+                target,
+            ],
+            "target",
+            false,
+            None,
+        ));
+        // an access the move can not change needs the re-export
+        check!(ws.check_move_symbol(
+            vec![
+                (
+                    "source.lua",
+                    r#"
+                    local M = {}
+
+                    function M.n<??>ame()
+                        return "source"
+                    end
+
+                    return M
+                    "#,
+                ),
+                target,
+                (
+                    "user.lua",
+                    r#"
+                    local m = require("source")
+                    local alias = m
+                    print(alias.name())
+                    "#,
+                ),
+            ],
+            "target",
+            false,
+            None,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_move_targets() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_move_targets(
+            vec![
+                (
+                    "source.lua",
+                    r#"
+                    local M = {}
+
+                    function M.p<??>arse(text)
+                        return text
+                    end
+
+                    return M
+                    "#,
+                ),
+                (
+                    "text.lua",
+                    r#"
+                    local M = {}
+                    return M
+                    "#,
+                ),
+                (
+                    "json.lua",
+                    r#"
+                    local json = {}
+                    function json.parse(text)
+                    end
+                    return json
+                    "#,
+                ),
+                ("main.lua", r#"print("main")"#),
+            ],
+            vec!["text"],
+        ));
+
+        // no other module to move to
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor_action(
+            r#"
+                local M = {}
+                function M.p<??>arse(text)
+                    return text
+                end
+                return M
+            "#,
+            "Move `parse` to another module",
+            None,
+        ));
+        Ok(())
+    }
+}
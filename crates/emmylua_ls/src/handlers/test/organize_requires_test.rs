@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_sort_and_group() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new_with_init_std_lib();
+        ws.def_file("util.lua", "return {}");
+        ws.def_file("app/config.lua", "return {}");
+        ws.def_file("app/unused.lua", "return {}");
+        check!(ws.check_refactor_action(
+            r#"<??>
+local util = require("util")
+---@type table
+local config = require("app.config")
+local unused = require("app.unused")
+local str = require("string")
+
+print(util, config, str)
+"#,
+            "Organize requires",
+            Some(
+                r#"
+local str = require("string")
+
+---@type table
+local config = require("app.config")
+local util = require("util")
+
+print(util, config, str)
+"#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_merge_duplicates() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file("list.lua", "return {}");
+        check!(ws.check_refactor_action(
+            r#"<??>
+local list = require("list")
+local List = require("list")
+local _side_effect = require("list")
+
+print(list, List.new)
+"#,
+            "Organize requires",
+            Some(
+                r#"
+local list = require("list")
+
+print(list, list.new)
+"#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_organized() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file("a.lua", "return {}");
+        ws.def_file("b.lua", "return {}");
+        check!(ws.check_refactor_action(
+            r#"<??>
+local a = require("a")
+local b = require("b")
+
+print(a, b)
+"#,
+            "Organize requires",
+            None,
+        ));
+        Ok(())
+    }
+}
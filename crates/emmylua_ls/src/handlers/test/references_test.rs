@@ -106,4 +106,37 @@ mod tests {
         ));
         Ok(())
     }
+
+    #[gtest]
+    fn test_label_references() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_references(
+            r#"
+                do
+                    goto done
+                end
+                goto done
+                ::do<??>ne::
+                local function f()
+                    goto done
+                    ::done::
+                end
+            "#,
+            vec![
+                VirtualLocation {
+                    file: "".to_string(),
+                    line: 2,
+                },
+                VirtualLocation {
+                    file: "".to_string(),
+                    line: 4,
+                },
+                VirtualLocation {
+                    file: "".to_string(),
+                    line: 5,
+                },
+            ]
+        ));
+        Ok(())
+    }
 }
@@ -307,4 +307,30 @@ mod tests {
         ));
         Ok(())
     }
+
+    #[gtest]
+    fn test_rename_label() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_rename(
+            r#"
+                goto a<??>
+                ::a::
+            "#,
+            "b".to_string(),
+            vec![(
+                "virtual_0.lua".to_string(),
+                vec![
+                    TextEdit {
+                        range: Range::new(Position::new(1, 21), Position::new(1, 22)),
+                        new_text: "b".to_string(),
+                    },
+                    TextEdit {
+                        range: Range::new(Position::new(2, 18), Position::new(2, 19)),
+                        new_text: "b".to_string(),
+                    },
+                ],
+            )]
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_delete_function() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_safe_delete(
+            r#"
+                local function helper()
+                end
+                ---Count down
+                ---@param n integer
+                local function count<??>down(n)
+                    if n > 0 then
+                        countdown(n - 1)
+                    end
+                end
+                print(helper)
+            "#,
+            Ok(r#"
+                local function helper()
+                end
+                print(helper)
+            "#),
+        ));
+        check!(ws.check_safe_delete(
+            r#"
+                local M = {}
+                function M.fo<??>rmat(s)
+                    return s
+                end
+                print(M.format("a"))
+            "#,
+            Err("Symbol `format` is still used at virtual_1.lua:6"),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_delete_field() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_safe_delete(
+            r#"
+                ---@class Options
+                ---@field name string
+                ---@field ver<??>bose boolean
+                local Options = {}
+                print(Options.name)
+            "#,
+            Ok(r#"
+                ---@class Options
+                ---@field name string
+                local Options = {}
+                print(Options.name)
+            "#),
+        ));
+        check!(ws.check_safe_delete(
+            r#"
+                local M = {}
+                M.ti<??>meout = 10
+                return M
+            "#,
+            Ok(r#"
+                local M = {}
+                return M
+            "#),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_delete_class() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_safe_delete(
+            r#"
+                ---@class Po<??>int
+                ---@field x number
+                ---@field next? Point
+                local Point = {}
+                local n = 1
+            "#,
+            Ok(r#"
+                local n = 1
+            "#),
+        ));
+        // a doc type still names the class
+        check!(ws.check_safe_delete(
+            r#"
+                ---@class Ve<??>ctor
+                local Vector = {}
+                ---@type Vector
+                local v
+            "#,
+            Err("Symbol `Vector` is still used at virtual_1.lua:4"),
+        ));
+        Ok(())
+    }
+}
@@ -1,13 +1,55 @@
 #[cfg(test)]
 mod tests {
-    use crate::handlers::{
-        semantic_token::CustomSemanticTokenType,
-        test_lib::{ProviderVirtualWorkspace, VirtualSemanticToken, check},
+    use crate::{
+        context::ClientId,
+        handlers::{
+            semantic_token::{
+                CustomSemanticTokenType, semantic_token, semantic_token_range,
+                semantic_tokens_edits,
+            },
+            test_lib::{ProviderVirtualWorkspace, VirtualSemanticToken, check},
+        },
     };
+    use emmylua_code_analysis::FileId;
     use googletest::prelude::*;
-    use lsp_types::{SemanticTokenModifier, SemanticTokenType};
+    use lsp_types::{
+        Position, Range, SemanticToken, SemanticTokenModifier, SemanticTokenType,
+        SemanticTokensRangeResult, SemanticTokensResult,
+    };
     use std::collections::HashSet;
 
+    fn full_tokens(ws: &ProviderVirtualWorkspace, file_id: FileId) -> Result<Vec<SemanticToken>> {
+        let result = semantic_token(&ws.analysis, file_id, true, ClientId::VSCode)
+            .ok_or("failed to get semantic tokens")
+            .or_fail()?;
+        match result {
+            SemanticTokensResult::Tokens(tokens) => Ok(tokens.data),
+            _ => Err("expected SemanticTokensResult::Tokens").or_fail(),
+        }
+    }
+
+    /// (line, start, length, type, modifiers) of each token
+    fn absolute_tokens(tokens: &[SemanticToken]) -> Vec<(u32, u32, u32, u32, u32)> {
+        let mut line = 0;
+        let mut start = 0;
+        let mut result = Vec::new();
+        for token in tokens {
+            if token.delta_line > 0 {
+                line += token.delta_line;
+                start = 0;
+            }
+            start += token.delta_start;
+            result.push((
+                line,
+                start,
+                token.length,
+                token.token_type,
+                token.token_modifiers_bitset,
+            ));
+        }
+        result
+    }
+
     #[gtest]
     fn test_1() -> Result<()> {
         let mut ws = ProviderVirtualWorkspace::new();
@@ -177,4 +219,85 @@ mod tests {
         ));
         Ok(())
     }
+
+    #[gtest]
+    fn test_range() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let file_id = ws.def(
+            r#"
+            local a = 1
+            local function f(x)
+                return x + a
+            end
+            print(f(a))
+            "#,
+        );
+        let full = absolute_tokens(&full_tokens(&ws, file_id)?);
+        let range = Range::new(Position::new(2, 0), Position::new(4, 0));
+        let Some(SemanticTokensRangeResult::Tokens(tokens)) =
+            semantic_token_range(&ws.analysis, file_id, range, true, ClientId::VSCode)
+        else {
+            return fail!("failed to get range semantic tokens");
+        };
+
+        let expected = full
+            .into_iter()
+            .filter(|(line, ..)| (2..=3).contains(line))
+            .collect::<Vec<_>>();
+        verify_that!(expected, not(is_empty()))?;
+        verify_eq!(absolute_tokens(&tokens.data), expected)
+    }
+
+    #[gtest]
+    fn test_delta_edits() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let file_id = ws.def_file(
+            "delta.lua",
+            r#"
+            local a = 1
+            local b = a
+            print(a, b)
+            "#,
+        );
+        let previous = full_tokens(&ws, file_id)?;
+        let file_id = ws.def_file(
+            "delta.lua",
+            r#"
+            local a = 1
+            local c, b = "", a
+            print(a, b)
+            "#,
+        );
+        let current = full_tokens(&ws, file_id)?;
+
+        let edits = semantic_tokens_edits(&previous, &current);
+        verify_eq!(edits.len(), 1)?;
+        verify_that!(edits[0].start, gt(0))?;
+
+        let flatten = |tokens: &[SemanticToken]| {
+            tokens
+                .iter()
+                .flat_map(|token| {
+                    [
+                        token.delta_line,
+                        token.delta_start,
+                        token.length,
+                        token.token_type,
+                        token.token_modifiers_bitset,
+                    ]
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut data = flatten(&previous);
+        for edit in edits.iter().rev() {
+            let start = edit.start as usize;
+            let end = start + edit.delete_count as usize;
+            data.splice(
+                start..end,
+                flatten(edit.data.as_deref().unwrap_or_default()),
+            );
+        }
+        verify_eq!(data, flatten(&current))?;
+        verify_that!(semantic_tokens_edits(&current, &current), is_empty())
+    }
 }
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+    use serde_json::json;
+
+    #[gtest]
+    fn test_replace_global() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new_with_init_std_lib();
+        check!(ws.check_quick_fix(
+            r#"
+                local message = "hello"
+                prnt(mesage)
+            "#,
+            "Change to `print`",
+            r#"
+                local message = "hello"
+                print(mesage)
+            "#,
+        ));
+        check!(ws.check_quick_fix(
+            r#"
+                local message = "hello"
+                print(mesage)
+            "#,
+            "Change to `message`",
+            r#"
+                local message = "hello"
+                print(message)
+            "#,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_replace_field() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_quick_fix(
+            r#"
+                ---@class Point
+                ---@field name string
+
+                ---@type Point
+                local p
+                local s = p.nmae
+            "#,
+            "Change to `name`",
+            r#"
+                ---@class Point
+                ---@field name string
+
+                ---@type Point
+                local p
+                local s = p.name
+            "#,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_replace_string_key() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_quick_fix(
+            r#"
+                ---@class Point
+                ---@field name string
+
+                ---@type Point
+                local p
+                local s = p["nmae"]
+            "#,
+            "Change to `name`",
+            r#"
+                ---@class Point
+                ---@field name string
+
+                ---@type Point
+                local p
+                local s = p["name"]
+            "#,
+        ));
+        // only a string key can take a name that is not an identifier
+        check!(ws.check_no_quick_fix(
+            r#"
+                ---@class Person
+                ---@field ["first name"] string
+
+                ---@type Person
+                local p
+                local s = p.first_name
+            "#,
+            "Change to `first name`",
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_global_commands() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let util = ws.def_file(
+            "util.lua",
+            r#"
+                local util = {}
+                return util
+            "#,
+        );
+        let file_id = ws.def("util.run()");
+        check!(ws.check_quick_fix_command(
+            file_id,
+            "Add `util` to diagnostics.globals",
+            vec![json!("util")],
+        ));
+        check!(ws.check_quick_fix_command(
+            file_id,
+            "Require `util` from `util`",
+            vec![
+                json!(file_id),
+                json!(util),
+                json!({ "line": 0, "character": 0 }),
+                json!(""),
+            ],
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, VirtualLocation, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_type_annotation() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file(
+            "1.lua",
+            r#"
+                ---@class MyClass
+                ---@field name string
+            "#,
+        );
+        check!(ws.check_type_definition(
+            r#"
+                ---@type MyClass
+                local a<??> = {}
+            "#,
+            vec![VirtualLocation {
+                file: "1.lua".to_string(),
+                line: 1,
+            }],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_inferred_instance() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file(
+            "1.lua",
+            r#"
+                ---@class Point
+                local Point = {}
+
+                ---@return Point
+                function Point.new() end
+
+                return Point
+            "#,
+        );
+        check!(ws.check_type_definition(
+            r#"
+                local Point = require("1")
+                local p = Point.new()
+                print(p<??>)
+            "#,
+            vec![VirtualLocation {
+                file: "1.lua".to_string(),
+                line: 1,
+            }],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_union() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_type_definition(
+            r#"
+                ---@alias Id string
+
+                ---@enum Kind
+                local Kind = { A = 1 }
+
+                ---@param value Id | Kind | nil
+                local function f(val<??>ue) end
+            "#,
+            vec![
+                VirtualLocation {
+                    file: "".to_string(),
+                    line: 1,
+                },
+                VirtualLocation {
+                    file: "".to_string(),
+                    line: 3,
+                },
+            ],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_generic_base() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_type_definition(
+            r#"
+                ---@class List<T>
+                local List = {}
+
+                ---@type List<string>
+                local list = {}
+                print(li<??>st)
+            "#,
+            vec![VirtualLocation {
+                file: "".to_string(),
+                line: 1,
+            }],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_field_type() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_type_definition(
+            r#"
+                ---@class Node
+                ---@field next Node?
+
+                ---@type Node
+                local node = {}
+                print(node.ne<??>xt)
+            "#,
+            vec![VirtualLocation {
+                file: "".to_string(),
+                line: 1,
+            }],
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, VirtualTypeHierarchy, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_class_hierarchy() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file(
+            "1.lua",
+            r#"
+                ---@class Animal
+
+                ---@class IWalker
+
+                ---@class Dog: Animal, IWalker
+            "#,
+        );
+        ws.def_file(
+            "2.lua",
+            r#"
+                ---@class Puppy: Dog
+
+                ---@class GuideDog: Dog
+            "#,
+        );
+        check!(ws.check_type_hierarchy(
+            r#"
+                ---@type Dog
+                local d<??>og
+            "#,
+            VirtualTypeHierarchy {
+                name: "Dog".to_string(),
+                supertypes: vec!["Animal".to_string(), "IWalker".to_string()],
+                subtypes: vec!["GuideDog".to_string(), "Puppy".to_string()],
+            },
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_doc_type_name() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_type_hierarchy(
+            r#"
+                ---@class Animal
+
+                ---@class Dog: Ani<??>mal
+            "#,
+            VirtualTypeHierarchy {
+                name: "Animal".to_string(),
+                supertypes: vec![],
+                subtypes: vec!["Dog".to_string()],
+            },
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_generic_super() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_type_hierarchy(
+            r#"
+                ---@class List<T>
+
+                ---@class StringList: List<string>
+                local StringList<??> = {}
+            "#,
+            VirtualTypeHierarchy {
+                name: "StringList".to_string(),
+                supertypes: vec!["List (List<string>)".to_string()],
+                subtypes: vec![],
+            },
+        ));
+        check!(ws.check_type_hierarchy(
+            r#"
+                ---@type List<integer>
+                local li<??>st
+            "#,
+            VirtualTypeHierarchy {
+                name: "List".to_string(),
+                supertypes: vec![],
+                subtypes: vec!["StringList".to_string()],
+            },
+        ));
+        Ok(())
+    }
+}
@@ -2,24 +2,32 @@ use emmylua_code_analysis::{EmmyLuaAnalysis, Emmyrc, FileId, VirtualUrlGenerator
 use googletest::prelude::*;
 use itertools::Itertools;
 use lsp_types::{
-    CodeActionOrCommand, CompletionItem, CompletionItemKind, CompletionResponse,
+    CodeActionKind, CodeActionOrCommand, CompletionItem, CompletionItemKind, CompletionResponse,
     CompletionTriggerKind, GotoDefinitionResponse, Hover, HoverContents, InlayHintLabel, Location,
-    MarkupContent, Position, SemanticTokenModifier, SemanticTokenType, SemanticTokensResult,
-    SignatureHelpContext, SignatureHelpTriggerKind, SignatureInformation, TextEdit,
+    MarkupContent, Position, Range, SemanticTokenModifier, SemanticTokenType, SemanticTokensResult,
+    SignatureHelpContext, SignatureHelpTriggerKind, SignatureInformation, TextEdit, WorkspaceEdit,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::{ops::Deref, sync::Arc};
 use tokio_util::sync::CancellationToken;
 
 use crate::{
     context::ClientId,
     handlers::{
-        code_actions::code_action,
+        code_actions::{
+            code_action, fix_all_edits, generate_definition_annotations, get_move_targets,
+            move_symbol, safe_delete,
+        },
+        command::{SignatureParam, change_signature, generate_annotations},
         completion::{completion, completion_resolve},
         inlay_hint::inlay_hint,
         rename::rename,
         semantic_token::semantic_token,
         signature_helper::signature_help,
+        type_definition::type_definition,
+        type_hierarchy::{
+            prepare_type_hierarchy, type_hierarchy_subtypes, type_hierarchy_supertypes,
+        },
     },
 };
 
@@ -100,6 +108,14 @@ pub struct VirtualCodeAction {
     pub title: String,
 }
 
+/// Items are written as `name`, or `name (detail)` when the item has a detail
+#[derive(Debug, Eq, PartialEq)]
+pub struct VirtualTypeHierarchy {
+    pub name: String,
+    pub supertypes: Vec<String>,
+    pub subtypes: Vec<String>,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct VirtualSemanticToken {
     pub line: u32,
@@ -310,6 +326,20 @@ impl ProviderVirtualWorkspace {
         Self::assert_definition(result, expected)
     }
 
+    pub fn check_type_definition(
+        &mut self,
+        block_str: &str,
+        expected: Vec<VirtualLocation>,
+    ) -> Result<()> {
+        let (content, position) = Self::handle_file_content(block_str)?;
+        let file_id = self.def(&content);
+        let result = type_definition(&self.analysis, file_id, position)
+            .ok_or("failed to get go to type definition response")
+            .or_fail()?;
+
+        Self::assert_definition(result, expected)
+    }
+
     fn assert_definition(
         result: GotoDefinitionResponse,
         expected: Vec<VirtualLocation>,
@@ -478,7 +508,7 @@ impl ProviderVirtualWorkspace {
             .diagnose_file(file_id, CancellationToken::new())
             .ok_or("failed to diagnose file")
             .or_fail()?;
-        let result = code_action(&self.analysis, file_id, result)
+        let result = code_action(&self.analysis, file_id, Range::default(), result, None)
             .ok_or("failed to generate code action")
             .or_fail()?;
 
@@ -498,6 +528,410 @@ impl ProviderVirtualWorkspace {
         )
     }
 
+    /// Apply the quickfix with the given title offered for the diagnostics of the file
+    pub fn check_quick_fix(&mut self, block_str: &str, title: &str, expected: &str) -> Result<()> {
+        let file_id = self.def(block_str);
+        let diagnostics = self
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .ok_or("failed to diagnose file")
+            .or_fail()?;
+        let actions = code_action(&self.analysis, file_id, Range::default(), diagnostics, None)
+            .unwrap_or_default();
+        let action = actions.into_iter().find_map(|action| match action {
+            CodeActionOrCommand::CodeAction(action) if action.title == title => Some(action),
+            _ => None,
+        });
+        self.check_code_action_edits(action, vec![(file_id, expected)])
+    }
+
+    pub fn check_no_quick_fix(&mut self, block_str: &str, title: &str) -> Result<()> {
+        let file_id = self.def(block_str);
+        let diagnostics = self
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .ok_or("failed to diagnose file")
+            .or_fail()?;
+        let actions = code_action(&self.analysis, file_id, Range::default(), diagnostics, None)
+            .unwrap_or_default();
+        let titles = actions
+            .iter()
+            .map(|action| match action {
+                CodeActionOrCommand::Command(command) => command.title.as_str(),
+                CodeActionOrCommand::CodeAction(action) => action.title.as_str(),
+            })
+            .collect::<Vec<_>>();
+        verify_that!(titles, not(contains(eq(&title))))
+    }
+
+    /// The arguments of the command run by the quickfix with the given title
+    pub fn check_quick_fix_command(
+        &mut self,
+        file_id: FileId,
+        title: &str,
+        expected: Vec<serde_json::Value>,
+    ) -> Result<()> {
+        let diagnostics = self
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .ok_or("failed to diagnose file")
+            .or_fail()?;
+        let actions = code_action(&self.analysis, file_id, Range::default(), diagnostics, None)
+            .unwrap_or_default();
+        let arguments = actions.into_iter().find_map(|action| match action {
+            CodeActionOrCommand::CodeAction(action) if action.title == title => {
+                action.command?.arguments
+            }
+            _ => None,
+        });
+        verify_that!(arguments, some(eq(&expected)))
+    }
+
+    /// Generate the missing annotations of the whole file
+    #[allow(clippy::mutable_key_type)]
+    pub fn check_generate_annotations(&mut self, block_str: &str, expected: &str) -> Result<()> {
+        let file_id = self.def(block_str);
+        let changes = generate_annotations(&self.analysis, &[file_id]);
+        let edit = Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        });
+        self.check_workspace_edit(edit, vec![(file_id, expected)])
+    }
+
+    /// Generate the missing annotations of the function or table class defined at the cursor
+    #[allow(clippy::mutable_key_type)]
+    pub fn check_generate_definition_annotations(
+        &mut self,
+        block_str: &str,
+        expected: &str,
+    ) -> Result<()> {
+        let (content, range) = Self::handle_file_selection(block_str)?;
+        let file_id = self.def(&content);
+        let uri = self.analysis.get_uri(file_id).ok_or("no uri").or_fail()?;
+        let edit =
+            generate_definition_annotations(&self.analysis, file_id, range.start).map(|edit| {
+                WorkspaceEdit {
+                    changes: Some(HashMap::from([(uri, vec![edit])])),
+                    ..Default::default()
+                }
+            });
+        self.check_workspace_edit(edit, vec![(file_id, expected)])
+    }
+
+    /// Generate the missing annotations of the files defined together, the expected content is
+    /// given by file name
+    pub fn check_generate_workspace_annotations(
+        &mut self,
+        files: Vec<(&str, &str)>,
+        expected: Vec<(&str, &str)>,
+    ) -> Result<()> {
+        let file_uris = files
+            .into_iter()
+            .map(|(file_name, content)| {
+                (
+                    self.virtual_url_generator.new_uri(file_name),
+                    Some(content.to_string()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let uris = file_uris
+            .iter()
+            .map(|(uri, _)| uri.clone())
+            .collect::<Vec<_>>();
+        self.analysis.update_files_by_uri(file_uris);
+        let file_ids = uris
+            .iter()
+            .map(|uri| self.analysis.get_file_id(uri).ok_or("no file").or_fail())
+            .collect::<Result<Vec<_>>>()?;
+        let edit = Some(WorkspaceEdit {
+            changes: Some(generate_annotations(&self.analysis, &file_ids)),
+            ..Default::default()
+        });
+
+        let expected_files = expected
+            .into_iter()
+            .map(|(file_name, content)| {
+                let uri = self.virtual_url_generator.new_uri(file_name);
+                Ok((
+                    self.analysis.get_file_id(&uri).ok_or("no file").or_fail()?,
+                    content,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.check_workspace_edit(edit, expected_files)
+    }
+
+    /// Apply every safe fix of the file at once
+    #[allow(clippy::mutable_key_type)]
+    pub fn check_fix_all(&mut self, block_str: &str, expected: Option<&str>) -> Result<()> {
+        let file_id = self.def(block_str);
+        let uri = self.analysis.get_uri(file_id).ok_or("no uri").or_fail()?;
+        let edit = fix_all_edits(&self.analysis, file_id).map(|edits| WorkspaceEdit {
+            changes: Some(HashMap::from([(uri, edits)])),
+            ..Default::default()
+        });
+        let Some(expected) = expected else {
+            return verify_that!(edit, none());
+        };
+        self.check_workspace_edit(edit, vec![(file_id, expected)])
+    }
+
+    /// Apply the code action with the given title to the text between the two `<??>`, or at
+    /// the cursor when there is a single `<??>`
+    pub fn check_refactor_action(
+        &mut self,
+        block_str: &str,
+        title: &str,
+        expected: Option<&str>,
+    ) -> Result<()> {
+        let (content, range) = Self::handle_file_selection(block_str)?;
+        let file_id = self.def(&content);
+        let action = self.find_code_action(file_id, range, title);
+        let Some(expected) = expected else {
+            return verify_that!(action, none());
+        };
+
+        self.check_code_action_edits(action, vec![(file_id, expected)])
+    }
+
+    /// The titles of the actions at the cursor when the client asks for the kinds in `only`
+    pub fn check_requested_actions(
+        &mut self,
+        block_str: &str,
+        only: Vec<CodeActionKind>,
+        expected: Vec<&str>,
+    ) -> Result<()> {
+        let (content, range) = Self::handle_file_selection(block_str)?;
+        let file_id = self.def(&content);
+        let actions = code_action(&self.analysis, file_id, range, Vec::new(), Some(&only))
+            .unwrap_or_default();
+        let titles = actions
+            .iter()
+            .map(|action| match action {
+                CodeActionOrCommand::Command(command) => command.title.as_str(),
+                CodeActionOrCommand::CodeAction(action) => action.title.as_str(),
+            })
+            .collect::<Vec<_>>();
+        verify_eq!(titles, expected)
+    }
+
+    /// Delete the symbol at the cursor, nothing is deleted when `expected` is an error and the
+    /// error is the reason given to the user
+    pub fn check_safe_delete(
+        &mut self,
+        block_str: &str,
+        expected: std::result::Result<&str, &str>,
+    ) -> Result<()> {
+        let (content, range) = Self::handle_file_selection(block_str)?;
+        let file_id = self.def(&content);
+        let result = safe_delete(&self.analysis, file_id, range.start)
+            .ok_or("nothing to delete")
+            .or_fail()?;
+        match (result, expected) {
+            (Ok(edit), Ok(expected)) => {
+                self.check_workspace_edit(Some(edit), vec![(file_id, expected)])
+            }
+            (Err(reason), Err(expected)) => verify_eq!(reason, expected.to_string()),
+            (result, _) => fail!("unexpected result {:?}", result),
+        }
+    }
+
+    /// Like `check_refactor_action` for an action that also edits other files, the expected
+    /// content of each edited file follows the one of the selected file
+    pub fn check_workspace_refactor_action(
+        &mut self,
+        block_str: &str,
+        title: &str,
+        expected: &str,
+        expected_files: Vec<(FileId, &str)>,
+    ) -> Result<()> {
+        let (content, range) = Self::handle_file_selection(block_str)?;
+        let file_id = self.def(&content);
+        let action = self.find_code_action(file_id, range, title);
+        let mut expected_files = expected_files;
+        expected_files.insert(0, (file_id, expected));
+        self.check_code_action_edits(action, expected_files)
+    }
+
+    fn find_code_action(
+        &self,
+        file_id: FileId,
+        range: Range,
+        title: &str,
+    ) -> Option<lsp_types::CodeAction> {
+        let actions =
+            code_action(&self.analysis, file_id, range, Vec::new(), None).unwrap_or_default();
+        actions.into_iter().find_map(|action| match action {
+            CodeActionOrCommand::CodeAction(action) if action.title == title => Some(action),
+            _ => None,
+        })
+    }
+
+    fn check_code_action_edits(
+        &self,
+        action: Option<lsp_types::CodeAction>,
+        expected_files: Vec<(FileId, &str)>,
+    ) -> Result<()> {
+        let action = action.ok_or("no code action with the title").or_fail()?;
+        self.check_workspace_edit(action.edit, expected_files)
+    }
+
+    /// Change the signature of the function defined at `<??>`
+    pub fn check_change_signature(
+        &mut self,
+        block_str: &str,
+        params: Vec<SignatureParam>,
+        expected: Option<&str>,
+        expected_files: Vec<(FileId, &str)>,
+    ) -> Result<()> {
+        let (content, range) = Self::handle_file_selection(block_str)?;
+        let file_id = self.def(&content);
+        let result = change_signature(&self.analysis, file_id, range.start, &params);
+        let Some(expected) = expected else {
+            return verify_that!(result, none());
+        };
+
+        let edit = result
+            .ok_or("the signature can not be changed")
+            .or_fail()?
+            .or_fail()?;
+        let mut expected_files = expected_files;
+        expected_files.insert(0, (file_id, expected));
+        self.check_workspace_edit(Some(edit), expected_files)
+    }
+
+    /// Change the signature of the function defined at `<??>`, which must fail with `reason`
+    pub fn check_change_signature_error(
+        &mut self,
+        block_str: &str,
+        params: Vec<SignatureParam>,
+        reason: &str,
+    ) -> Result<()> {
+        let (content, range) = Self::handle_file_selection(block_str)?;
+        let file_id = self.def(&content);
+        match change_signature(&self.analysis, file_id, range.start, &params) {
+            Some(Err(error)) => verify_eq!(error, reason.to_string()),
+            result => fail!("unexpected result {:?}", result),
+        }
+    }
+
+    /// Move the module member defined at `<??>` of the first file to `target_module`, the
+    /// files are defined together and the expected content is given by file name
+    pub fn check_move_symbol(
+        &mut self,
+        files: Vec<(&str, &str)>,
+        target_module: &str,
+        re_export: bool,
+        expected: Option<Vec<(&str, &str)>>,
+    ) -> Result<()> {
+        let (file_id, range) = self.def_files_with_selection(files)?;
+        let edit = move_symbol(
+            &self.analysis,
+            file_id,
+            range.start,
+            target_module,
+            re_export,
+        );
+        let Some(expected) = expected else {
+            return verify_that!(edit, none());
+        };
+
+        let expected_files = expected
+            .into_iter()
+            .map(|(file_name, content)| {
+                let uri = self.virtual_url_generator.new_uri(file_name);
+                Ok((
+                    self.analysis.get_file_id(&uri).ok_or("no file").or_fail()?,
+                    content,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.check_workspace_edit(edit, expected_files)
+    }
+
+    /// The modules offered as target to move the module member defined at `<??>` of the first
+    /// file
+    pub fn check_move_targets(
+        &mut self,
+        files: Vec<(&str, &str)>,
+        expected: Vec<&str>,
+    ) -> Result<()> {
+        let (file_id, range) = self.def_files_with_selection(files)?;
+        verify_eq!(
+            get_move_targets(&self.analysis, file_id, range.start),
+            expected
+        )
+    }
+
+    /// Define the files together, the first one with `<??>`
+    fn def_files_with_selection(&mut self, files: Vec<(&str, &str)>) -> Result<(FileId, Range)> {
+        let mut files = files
+            .into_iter()
+            .map(|(file_name, content)| (file_name, content.to_string()))
+            .collect::<Vec<_>>();
+        let (content, range) = Self::handle_file_selection(&files[0].1)?;
+        files[0].1 = content;
+        let file_uris = files
+            .into_iter()
+            .map(|(file_name, content)| {
+                (self.virtual_url_generator.new_uri(file_name), Some(content))
+            })
+            .collect::<Vec<_>>();
+        let uri = file_uris[0].0.clone();
+        self.analysis.update_files_by_uri(file_uris);
+        let file_id = self.analysis.get_file_id(&uri).ok_or("no file").or_fail()?;
+        Ok((file_id, range))
+    }
+
+    fn check_workspace_edit(
+        &self,
+        edit: Option<WorkspaceEdit>,
+        expected_files: Vec<(FileId, &str)>,
+    ) -> Result<()> {
+        let mut changes = edit
+            .and_then(|edit| edit.changes)
+            .ok_or("no changes")
+            .or_fail()?;
+        verify_eq!(changes.len(), expected_files.len())?;
+        for (file_id, expected) in expected_files {
+            let uri = self.analysis.get_uri(file_id).ok_or("no uri").or_fail()?;
+            let content = self
+                .analysis
+                .compilation
+                .get_db()
+                .get_vfs()
+                .get_file_content(&file_id)
+                .ok_or("no content")
+                .or_fail()?
+                .clone();
+            let edits = changes.remove(&uri).unwrap_or_default();
+            verify_eq!(apply_text_edits(&content, edits), expected.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// The content without the `<??>` and the range between them
+    fn handle_file_selection(content: &str) -> Result<(String, Range)> {
+        let mut parts = content.split("<??>").collect::<Vec<_>>();
+        if parts.len() == 2 {
+            parts.insert(1, "");
+        }
+        if parts.len() != 3 {
+            return Err("content should include one or two <??>").or_fail();
+        }
+
+        fn position_at(text: &str) -> Position {
+            let line = text.matches('\n').count();
+            let column = text.rsplit('\n').next().unwrap_or_default().chars().count();
+            Position::new(line as u32, column as u32)
+        }
+        let start = position_at(parts[0]);
+        let end = position_at(&parts[..2].concat());
+        Ok((parts.concat(), Range::new(start, end)))
+    }
+
     pub fn check_semantic_token(
         &mut self,
         block_str: &str,
@@ -596,6 +1030,36 @@ impl ProviderVirtualWorkspace {
         verify_eq!(items, expected)
     }
 
+    pub fn check_type_hierarchy(
+        &mut self,
+        block_str: &str,
+        expected: VirtualTypeHierarchy,
+    ) -> Result<()> {
+        let (content, position) = Self::handle_file_content(block_str)?;
+        let file_id = self.def(&content);
+        let items = prepare_type_hierarchy(&self.analysis, file_id, position)
+            .ok_or("failed to prepare type hierarchy")
+            .or_fail()?;
+        verify_eq!(items.len(), 1)?;
+        let item = &items[0];
+        let item_label = |item: &lsp_types::TypeHierarchyItem| match &item.detail {
+            Some(detail) => format!("{} ({})", item.name, detail),
+            None => item.name.clone(),
+        };
+        let supertypes = type_hierarchy_supertypes(&self.analysis, item)
+            .ok_or("failed to get supertypes")
+            .or_fail()?;
+        let subtypes = type_hierarchy_subtypes(&self.analysis, item)
+            .ok_or("failed to get subtypes")
+            .or_fail()?;
+        let result = VirtualTypeHierarchy {
+            name: item_label(item),
+            supertypes: supertypes.iter().map(item_label).collect(),
+            subtypes: subtypes.iter().map(item_label).collect(),
+        };
+        verify_eq!(result, expected)
+    }
+
     pub fn check_references(
         &mut self,
         block_str: &str,
@@ -609,3 +1073,28 @@ impl ProviderVirtualWorkspace {
         Self::assert_locations(result, expected)
     }
 }
+
+fn apply_text_edits(content: &str, mut edits: Vec<TextEdit>) -> String {
+    let offset_at = |position: Position| {
+        let line_start = content
+            .split_inclusive('\n')
+            .take(position.line as usize)
+            .map(str::len)
+            .sum::<usize>();
+        let column = content[line_start..]
+            .chars()
+            .take(position.character as usize)
+            .map(char::len_utf8)
+            .sum::<usize>();
+        line_start + column
+    };
+
+    edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+    let mut result = content.to_string();
+    for edit in edits.into_iter().rev() {
+        let start = offset_at(edit.range.start);
+        let end = offset_at(edit.range.end);
+        result.replace_range(start..end, &edit.new_text);
+    }
+    result
+}
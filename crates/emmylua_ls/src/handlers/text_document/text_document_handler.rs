@@ -3,7 +3,6 @@ use lsp_types::{
     DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
     DidSaveTextDocumentParams,
 };
-use std::time::Duration;
 
 use crate::context::{ServerContextSnapshot, WorkspaceDiagnosticLevel};
 
@@ -65,29 +64,15 @@ pub async fn on_did_save_text_document(
     context: ServerContextSnapshot,
     _: DidSaveTextDocumentParams,
 ) -> Option<()> {
-    let emmyrc = context.analysis().read().await.get_emmyrc();
-    if !emmyrc.workspace.enable_reindex {
-        if context.lsp_features().supports_workspace_diagnostic() {
-            context
-                .file_diagnostic()
-                .cancel_workspace_diagnostic()
-                .await;
-            let workspace_manager = context.workspace_manager().write().await;
-            workspace_manager.update_workspace_version(WorkspaceDiagnosticLevel::Slow, true);
-        }
-
-        return Some(());
+    if context.lsp_features().supports_workspace_diagnostic() {
+        context
+            .file_diagnostic()
+            .cancel_workspace_diagnostic()
+            .await;
+        let workspace_manager = context.workspace_manager().write().await;
+        workspace_manager.update_workspace_version(WorkspaceDiagnosticLevel::Slow, true);
     }
 
-    let mut duration = emmyrc.workspace.reindex_duration;
-    // if duration is less than 1000ms, set it to 1000ms
-    if duration < 1000 {
-        duration = 1000;
-    }
-    let workspace = context.workspace_manager().read().await;
-    workspace
-        .reindex_workspace(Duration::from_millis(duration))
-        .await;
     Some(())
 }
 
@@ -96,7 +81,7 @@ pub async fn on_did_change_text_document(
     params: DidChangeTextDocumentParams,
 ) -> Option<()> {
     let uri = params.text_document.uri;
-    let text = params.content_changes.first()?.text.clone();
+    let changes = params.content_changes;
 
     // Check if file should be filtered before acquiring locks
     // Follow lock order: workspace_manager (read) -> analysis (write)
@@ -119,7 +104,7 @@ pub async fn on_did_change_text_document(
     // Update file and get settings
     let (file_id, emmyrc, supports_pull) = {
         let mut analysis = context.analysis().write().await;
-        let file_id = analysis.update_file_by_uri(&uri, Some(text));
+        let file_id = analysis.change_file_by_uri(&uri, changes);
         let emmyrc = analysis.get_emmyrc();
         let supports_pull = context.lsp_features().supports_pull_diagnostic();
         (file_id, emmyrc, supports_pull)
@@ -127,12 +112,6 @@ pub async fn on_did_change_text_document(
 
     let interval = emmyrc.diagnostics.diagnostic_interval.unwrap_or(500);
 
-    // Handle reindex without holding locks
-    if emmyrc.workspace.enable_reindex {
-        let workspace = context.workspace_manager().read().await;
-        workspace.extend_reindex_delay().await;
-    }
-
     // Schedule diagnostic task
     if !supports_pull {
         if let Some(file_id) = file_id {
@@ -156,6 +135,7 @@ pub async fn on_did_close_document(
         .current_open_files
         .remove(&params.text_document.uri);
     drop(workspace);
+    context.semantic_tokens_cache().remove(uri);
     let lsp_features = context.lsp_features();
 
     // 如果关闭后文件不存在, 则移除
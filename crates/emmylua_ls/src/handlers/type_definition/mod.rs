@@ -0,0 +1,162 @@
+use emmylua_code_analysis::{
+    EmmyLuaAnalysis, FileId, LuaSemanticDeclId, LuaType, LuaTypeDeclId, SemanticDeclLevel,
+    SemanticModel, infer_param,
+};
+use emmylua_parser::{LuaAstNode, LuaExpr, LuaSyntaxToken, LuaTokenKind};
+use lsp_types::{
+    ClientCapabilities, GotoDefinitionResponse, Location, Position, ServerCapabilities,
+    TypeDefinitionProviderCapability,
+    request::{GotoTypeDefinitionParams, GotoTypeDefinitionResponse},
+};
+use rowan::TokenAtOffset;
+use tokio_util::sync::CancellationToken;
+
+use super::RegisterCapabilities;
+use crate::context::ServerContextSnapshot;
+
+pub async fn on_type_definition_handler(
+    context: ServerContextSnapshot,
+    params: GotoTypeDefinitionParams,
+    _: CancellationToken,
+) -> Option<GotoTypeDefinitionResponse> {
+    let uri = params.text_document_position_params.text_document.uri;
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+    let position = params.text_document_position_params.position;
+
+    type_definition(&analysis, file_id, position)
+}
+
+pub fn type_definition(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+) -> Option<GotoTypeDefinitionResponse> {
+    let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
+    let root = semantic_model.get_root();
+    let position_offset = {
+        let document = semantic_model.get_document();
+        document.get_offset(position.line as usize, position.character as usize)?
+    };
+
+    if position_offset > root.syntax().text_range().end() {
+        return None;
+    }
+
+    let token = match root.syntax().token_at_offset(position_offset) {
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(left, right) => {
+            if left.kind() == LuaTokenKind::TkName.into() {
+                left
+            } else {
+                right
+            }
+        }
+        TokenAtOffset::None => return None,
+    };
+
+    let typ = get_token_type(&semantic_model, &token)?;
+    let mut type_decl_ids = Vec::new();
+    collect_type_decl_ids(&typ, &mut type_decl_ids);
+
+    let mut locations: Vec<Location> = Vec::new();
+    for type_decl_id in type_decl_ids {
+        let Some(type_decl) = semantic_model
+            .get_db()
+            .get_type_index()
+            .get_type_decl(&type_decl_id)
+        else {
+            continue;
+        };
+
+        for lua_location in type_decl.get_locations() {
+            let Some(document) = semantic_model.get_document_by_file_id(lua_location.file_id)
+            else {
+                continue;
+            };
+            if let Some(location) = document.to_lsp_location(lua_location.range) {
+                locations.push(location);
+            }
+        }
+    }
+
+    if locations.is_empty() {
+        return None;
+    }
+
+    Some(GotoDefinitionResponse::Array(locations))
+}
+
+fn get_token_type(semantic_model: &SemanticModel, token: &LuaSyntaxToken) -> Option<LuaType> {
+    // the narrowed type of the expression at the cursor
+    if let Some(expr) = token.parent().and_then(LuaExpr::cast)
+        && matches!(expr, LuaExpr::NameExpr(_) | LuaExpr::IndexExpr(_))
+        && let Ok(typ) = semantic_model.infer_expr(expr)
+    {
+        return Some(typ);
+    }
+
+    let semantic_decl =
+        semantic_model.find_decl(token.clone().into(), SemanticDeclLevel::default())?;
+    match semantic_decl {
+        LuaSemanticDeclId::LuaDecl(decl_id) => {
+            let db = semantic_model.get_db();
+            let decl = db.get_decl_index().get_decl(&decl_id)?;
+            if decl.is_param() {
+                return infer_param(db, decl).ok();
+            }
+
+            Some(semantic_model.get_type(decl_id.into()))
+        }
+        LuaSemanticDeclId::Member(member_id) => Some(semantic_model.get_type(member_id.into())),
+        LuaSemanticDeclId::TypeDecl(type_decl_id) => Some(LuaType::Ref(type_decl_id)),
+        _ => None,
+    }
+}
+
+fn collect_type_decl_ids(typ: &LuaType, type_decl_ids: &mut Vec<LuaTypeDeclId>) {
+    match typ {
+        LuaType::Ref(type_decl_id) | LuaType::Def(type_decl_id) => {
+            if !type_decl_ids.contains(type_decl_id) {
+                type_decl_ids.push(type_decl_id.clone());
+            }
+        }
+        LuaType::Generic(generic) => {
+            collect_type_decl_ids(&generic.get_base_type(), type_decl_ids);
+        }
+        LuaType::Instance(instance) => {
+            collect_type_decl_ids(instance.get_base(), type_decl_ids);
+        }
+        LuaType::Array(array) => {
+            collect_type_decl_ids(array.get_base(), type_decl_ids);
+        }
+        LuaType::TypeGuard(inner) => {
+            collect_type_decl_ids(inner, type_decl_ids);
+        }
+        LuaType::Union(union) => {
+            for typ in union.into_vec() {
+                collect_type_decl_ids(&typ, type_decl_ids);
+            }
+        }
+        LuaType::MultiLineUnion(multi_union) => {
+            for (typ, _) in multi_union.get_unions() {
+                collect_type_decl_ids(typ, type_decl_ids);
+            }
+        }
+        LuaType::Intersection(intersection) => {
+            for typ in intersection.get_types() {
+                collect_type_decl_ids(typ, type_decl_ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub struct TypeDefinitionCapabilities;
+
+impl RegisterCapabilities for TypeDefinitionCapabilities {
+    fn register_capabilities(server_capabilities: &mut ServerCapabilities, _: &ClientCapabilities) {
+        server_capabilities.type_definition_provider =
+            Some(TypeDefinitionProviderCapability::Simple(true));
+    }
+}
@@ -0,0 +1,96 @@
+use emmylua_code_analysis::{
+    DbIndex, LuaSemanticDeclId, LuaType, LuaTypeDeclId, RenderLevel, SemanticModel, humanize_type,
+};
+use lsp_types::{SymbolKind, TypeHierarchyItem};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TypeHierarchyItemData {
+    pub type_decl_id: LuaTypeDeclId,
+}
+
+/// The class, enum or alias the declaration at the cursor stands for
+pub fn get_semantic_decl_type_id(
+    semantic_model: &SemanticModel,
+    semantic_decl: LuaSemanticDeclId,
+) -> Option<LuaTypeDeclId> {
+    let typ = match semantic_decl {
+        LuaSemanticDeclId::TypeDecl(type_decl_id) => return Some(type_decl_id),
+        LuaSemanticDeclId::LuaDecl(decl_id) => semantic_model.get_type(decl_id.into()),
+        LuaSemanticDeclId::Member(member_id) => semantic_model.get_type(member_id.into()),
+        _ => return None,
+    };
+
+    get_type_decl_id(&typ)
+}
+
+fn get_type_decl_id(typ: &LuaType) -> Option<LuaTypeDeclId> {
+    match typ {
+        LuaType::Ref(type_decl_id) | LuaType::Def(type_decl_id) => Some(type_decl_id.clone()),
+        LuaType::Generic(generic) => Some(generic.get_base_type_id()),
+        _ => None,
+    }
+}
+
+pub fn build_type_hierarchy_item(
+    db: &DbIndex,
+    type_decl_id: &LuaTypeDeclId,
+    detail: Option<String>,
+) -> Option<TypeHierarchyItem> {
+    let type_decl = db.get_type_index().get_type_decl(type_decl_id)?;
+    let location = type_decl.get_locations().first()?;
+    let document = db.get_vfs().get_document(&location.file_id)?;
+    let lsp_range = document.to_lsp_range(location.range)?;
+    let kind = if type_decl.is_enum() {
+        SymbolKind::ENUM
+    } else if type_decl.is_alias() {
+        SymbolKind::TYPE_PARAMETER
+    } else {
+        SymbolKind::CLASS
+    };
+    let data = TypeHierarchyItemData {
+        type_decl_id: type_decl_id.clone(),
+    };
+
+    Some(TypeHierarchyItem {
+        name: type_decl.get_full_name().to_string(),
+        kind,
+        tags: None,
+        detail,
+        uri: document.get_uri(),
+        range: lsp_range,
+        selection_range: lsp_range,
+        data: Some(serde_json::to_value(data).ok()?),
+    })
+}
+
+pub fn build_supertypes(db: &DbIndex, type_decl_id: &LuaTypeDeclId) -> Vec<TypeHierarchyItem> {
+    let mut items = Vec::new();
+    let Some(super_types) = db.get_type_index().get_super_types_iter(type_decl_id) else {
+        return items;
+    };
+
+    for super_type in super_types {
+        let Some(super_type_id) = get_type_decl_id(super_type) else {
+            continue;
+        };
+        // show the instantiation of a generic super, like `List<string>`
+        let detail = match super_type {
+            LuaType::Generic(_) => Some(humanize_type(db, super_type, RenderLevel::Simple)),
+            _ => None,
+        };
+        if let Some(item) = build_type_hierarchy_item(db, &super_type_id, detail) {
+            items.push(item);
+        }
+    }
+
+    items
+}
+
+pub fn build_subtypes(db: &DbIndex, type_decl_id: &LuaTypeDeclId) -> Vec<TypeHierarchyItem> {
+    db.get_type_index()
+        .get_sub_types(type_decl_id)
+        .iter()
+        .filter_map(|sub_type_id| build_type_hierarchy_item(db, sub_type_id, None))
+        .collect()
+}
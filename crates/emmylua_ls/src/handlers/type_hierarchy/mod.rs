@@ -0,0 +1,146 @@
+mod build_type_hierarchy;
+
+use build_type_hierarchy::{
+    TypeHierarchyItemData, build_subtypes, build_supertypes, build_type_hierarchy_item,
+    get_semantic_decl_type_id,
+};
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, SemanticDeclLevel};
+use emmylua_parser::{LuaAstNode, LuaTokenKind};
+use lsp_types::{
+    ClientCapabilities, DocumentFilter, Position, Registration, RegistrationParams,
+    TextDocumentRegistrationOptions, TypeHierarchyItem, TypeHierarchyPrepareParams,
+    TypeHierarchyRegistrationOptions, TypeHierarchySubtypesParams, TypeHierarchySupertypesParams,
+};
+use rowan::TokenAtOffset;
+use tokio_util::sync::CancellationToken;
+
+use crate::context::{ClientProxy, ServerContextSnapshot};
+
+pub async fn on_prepare_type_hierarchy_handler(
+    context: ServerContextSnapshot,
+    params: TypeHierarchyPrepareParams,
+    _: CancellationToken,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let uri = params.text_document_position_params.text_document.uri;
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+    let position = params.text_document_position_params.position;
+
+    prepare_type_hierarchy(&analysis, file_id, position)
+}
+
+pub async fn on_supertypes_handler(
+    context: ServerContextSnapshot,
+    params: TypeHierarchySupertypesParams,
+    _: CancellationToken,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let analysis = context.analysis().read().await;
+    type_hierarchy_supertypes(&analysis, &params.item)
+}
+
+pub async fn on_subtypes_handler(
+    context: ServerContextSnapshot,
+    params: TypeHierarchySubtypesParams,
+    _: CancellationToken,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let analysis = context.analysis().read().await;
+    type_hierarchy_subtypes(&analysis, &params.item)
+}
+
+pub fn prepare_type_hierarchy(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
+    let root = semantic_model.get_root();
+    let position_offset = {
+        let document = semantic_model.get_document();
+        document.get_offset(position.line as usize, position.character as usize)?
+    };
+
+    if position_offset > root.syntax().text_range().end() {
+        return None;
+    }
+
+    let token = match root.syntax().token_at_offset(position_offset) {
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(left, right) => {
+            if left.kind() == LuaTokenKind::TkName.into() {
+                left
+            } else {
+                right
+            }
+        }
+        TokenAtOffset::None => return None,
+    };
+
+    let semantic_decl =
+        semantic_model.find_decl(token.clone().into(), SemanticDeclLevel::default())?;
+    let type_decl_id = get_semantic_decl_type_id(&semantic_model, semantic_decl)?;
+
+    Some(vec![build_type_hierarchy_item(
+        semantic_model.get_db(),
+        &type_decl_id,
+        None,
+    )?])
+}
+
+pub fn type_hierarchy_supertypes(
+    analysis: &EmmyLuaAnalysis,
+    item: &TypeHierarchyItem,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let data = serde_json::from_value::<TypeHierarchyItemData>(item.data.clone()?).ok()?;
+    Some(build_supertypes(
+        analysis.compilation.get_db(),
+        &data.type_decl_id,
+    ))
+}
+
+pub fn type_hierarchy_subtypes(
+    analysis: &EmmyLuaAnalysis,
+    item: &TypeHierarchyItem,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let data = serde_json::from_value::<TypeHierarchyItemData>(item.data.clone()?).ok()?;
+    Some(build_subtypes(
+        analysis.compilation.get_db(),
+        &data.type_decl_id,
+    ))
+}
+
+/// `typeHierarchyProvider` is missing from the server capabilities of lsp_types, so the type
+/// hierarchy requests are registered dynamically
+pub fn register_type_hierarchy(client: &ClientProxy, client_capabilities: &ClientCapabilities) {
+    let dynamic_registration = client_capabilities
+        .text_document
+        .as_ref()
+        .and_then(|text_document| text_document.type_hierarchy.as_ref())
+        .and_then(|type_hierarchy| type_hierarchy.dynamic_registration)
+        .unwrap_or_default();
+    if !dynamic_registration {
+        return;
+    }
+
+    let options = TypeHierarchyRegistrationOptions {
+        text_document_registration_options: TextDocumentRegistrationOptions {
+            document_selector: Some(vec![DocumentFilter {
+                language: Some("lua".to_string()),
+                scheme: None,
+                pattern: None,
+            }]),
+        },
+        ..Default::default()
+    };
+    let Ok(register_options) = serde_json::to_value(options) else {
+        return;
+    };
+
+    let registration = Registration {
+        id: "emmylua_type_hierarchy".to_string(),
+        method: "textDocument/prepareTypeHierarchy".to_string(),
+        register_options: Some(register_options),
+    };
+    client.dynamic_register_capability(RegistrationParams {
+        registrations: vec![registration],
+    });
+}
@@ -17,6 +17,7 @@ pub use lua_type_operator_kind::{
 };
 pub use lua_version::{LuaVersionCondition, LuaVersionNumber};
 pub use lua_visibility_kind::VisibilityKind;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u16)]
@@ -94,6 +95,25 @@ impl LuaKind {
     }
 }
 
+impl Serialize for LuaKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u16(self.get_raw())
+    }
+}
+
+impl<'de> Deserialize<'de> for LuaKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = u16::deserialize(deserializer)?;
+        Ok(LuaKind::from_raw(raw))
+    }
+}
+
 #[derive(Debug)]
 pub struct PriorityTable {
     pub left: i32,
@@ -1,7 +1,10 @@
+use rowan::{TextRange, TextSize};
+
 use super::{
     lua_doc_parser::LuaDocParser,
     marker::{MarkEvent, MarkerEventContainer},
     parser_config::ParserConfig,
+    reparse::reparse_block,
 };
 use crate::text::Reader;
 use crate::{
@@ -78,6 +81,22 @@ impl<'a> LuaParser<'a> {
         LuaSyntaxTree::new(root, errors)
     }
 
+    /// Parse `text` after an edit that replaced `edit_range` of the text of `old_tree`
+    /// with `insert_len` bytes. Only the block around the edit is reparsed when possible,
+    /// otherwise the whole text is parsed again.
+    pub fn reparse(
+        old_tree: &LuaSyntaxTree,
+        text: &'a str,
+        edit_range: TextRange,
+        insert_len: TextSize,
+        mut config: ParserConfig,
+    ) -> LuaSyntaxTree {
+        match reparse_block(old_tree, text, edit_range, insert_len, config.reborrow()) {
+            Some(tree) => tree,
+            None => Self::parse(text, config),
+        }
+    }
+
     pub fn init(&mut self) {
         if self.tokens.is_empty() {
             self.current_token = LuaTokenKind::TkEof;
@@ -417,6 +436,99 @@ mod tests {
         println!("{:#?}", tree.get_red_root());
     }
 
+    fn apply_edit(
+        old_text: &str,
+        old: &str,
+        new: &str,
+    ) -> (String, rowan::TextRange, rowan::TextSize) {
+        let start = old_text.find(old).unwrap();
+        let range = rowan::TextRange::at((start as u32).into(), (old.len() as u32).into());
+        let mut text = old_text.to_string();
+        text.replace_range(start..start + old.len(), new);
+        (text, range, (new.len() as u32).into())
+    }
+
+    fn check_reparse(old_text: &str, old: &str, new: &str, incremental: bool) {
+        let old_tree = LuaParser::parse(old_text, ParserConfig::default());
+        let (text, range, insert_len) = apply_edit(old_text, old, new);
+        let block_tree = crate::parser::reparse::reparse_block(
+            &old_tree,
+            &text,
+            range,
+            insert_len,
+            ParserConfig::default(),
+        );
+        assert_eq!(block_tree.is_some(), incremental);
+
+        let tree = LuaParser::reparse(&old_tree, &text, range, insert_len, ParserConfig::default());
+        let full_tree = LuaParser::parse(&text, ParserConfig::default());
+        assert_eq!(tree.get_red_root().to_string(), text);
+        assert_eq!(
+            format!("{:#?}", tree.get_red_root()),
+            format!("{:#?}", full_tree.get_red_root())
+        );
+        assert_eq!(tree.get_errors(), full_tree.get_errors());
+    }
+
+    #[test]
+    fn test_reparse_block() {
+        let code = r#"
+            local function foo(a, b)
+                local c = a + b
+                return c
+            end
+
+            local function bar()
+                -- comment
+                while true do
+                    print(1)
+                end
+            end
+        "#;
+
+        check_reparse(code, "a + b", "a * b + foo(a)", true);
+        check_reparse(
+            code,
+            "print(1)",
+            "print(2)\n                    break",
+            true,
+        );
+        check_reparse(code, "-- comment", "---@type number", true);
+    }
+
+    #[test]
+    fn test_reparse_shift_errors() {
+        let code = r#"
+            local function foo(a, b)
+                return a + b
+            end
+
+            local x = = 1
+        "#;
+
+        check_reparse(code, "a + b", "a - b - 1", true);
+    }
+
+    #[test]
+    fn test_reparse_fallback() {
+        let code = r#"
+            local function foo(a, b)
+                return a + b
+            end
+            do print(a) end
+        "#;
+
+        // the edit changes the structure around the block
+        check_reparse(code, "return a + b", "if a then", false);
+        check_reparse(code, "return a + b", "end", false);
+        // top level statements
+        check_reparse(code, "local function foo", "function foo", false);
+        // a comment swallows the closing keyword
+        check_reparse(code, "print(a)", "print(a) --", false);
+        // unfinished long string
+        check_reparse(code, "return a + b", "return [[", false);
+    }
+
     #[test]
     fn test_parse_empty_file() {
         let lua_code = r#""#;
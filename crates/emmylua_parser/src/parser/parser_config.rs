@@ -41,6 +41,16 @@ impl<'cache> ParserConfig<'cache> {
         self.node_cache.as_deref_mut()
     }
 
+    /// A copy of the config that shares the node cache, used to parse a part of a file.
+    pub(crate) fn reborrow(&mut self) -> ParserConfig<'_> {
+        ParserConfig {
+            level: self.level,
+            lexer_config: self.lexer_config,
+            node_cache: self.node_cache.as_deref_mut(),
+            special_like: self.special_like.clone(),
+        }
+    }
+
     pub fn get_special_function(&self, name: &str) -> SpecialFunction {
         match name {
             "require" => SpecialFunction::Require,
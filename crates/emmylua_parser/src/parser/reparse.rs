@@ -0,0 +1,133 @@
+use rowan::{TextRange, TextSize};
+
+use crate::{
+    LuaParseError, LuaParser, LuaSyntaxNode, LuaSyntaxTree, ParserConfig,
+    kind::{LuaSyntaxKind, LuaTokenKind},
+};
+
+/// Reparse only the innermost block that contains the edit and splice the new green
+/// node into the old tree, so the green nodes outside of the block are reused.
+///
+/// `text` is the full text after the edit, `edit_range` is the replaced range in the
+/// old text and `insert_len` is the length of the inserted text.
+///
+/// Returns `None` when the edit can not be handled inside a single block, the caller
+/// should parse the whole text again.
+pub(crate) fn reparse_block(
+    old_tree: &LuaSyntaxTree,
+    text: &str,
+    edit_range: TextRange,
+    insert_len: TextSize,
+    config: ParserConfig,
+) -> Option<LuaSyntaxTree> {
+    let root = old_tree.get_red_root();
+    if root.text_range().len() + insert_len != TextSize::from(text.len() as u32) + edit_range.len()
+    {
+        return None;
+    }
+
+    let block = root
+        .covering_element(edit_range)
+        .ancestors()
+        .find(|node| is_reparsable_block(node, edit_range))?;
+    let old_range = block.text_range();
+    let new_range = TextRange::at(
+        old_range.start(),
+        old_range.len() - edit_range.len() + insert_len,
+    );
+    let fragment = text.get(usize::from(new_range.start())..usize::from(new_range.end()))?;
+    if !is_isolated_fragment(fragment) {
+        return None;
+    }
+
+    let fragment_tree = LuaParser::parse(fragment, config);
+    if !fragment_tree.get_errors().is_empty() {
+        return None;
+    }
+
+    let fragment_root = fragment_tree.get_red_root();
+    let mut children = fragment_root.children();
+    let new_block = children.next()?;
+    if children.next().is_some()
+        || new_block.kind() != LuaSyntaxKind::Block.into()
+        || new_block.text_range().len() != new_range.len()
+    {
+        return None;
+    }
+
+    let errors = shift_errors(old_tree.get_errors(), old_range, new_range)?;
+    let green = block.replace_with(new_block.green().into_owned());
+    Some(LuaSyntaxTree::new(green, errors))
+}
+
+/// The block must strictly contain the edit and be closed by a keyword, a block that
+/// ends at the end of file or by error recovery depends on the text around it.
+fn is_reparsable_block(node: &LuaSyntaxNode, edit_range: TextRange) -> bool {
+    if node.kind() != LuaSyntaxKind::Block.into() {
+        return false;
+    }
+
+    let range = node.text_range();
+    if range.start() >= edit_range.start() || edit_range.end() >= range.end() {
+        return false;
+    }
+
+    if node
+        .parent()
+        .is_none_or(|parent| parent.kind() == LuaSyntaxKind::Chunk.into())
+    {
+        return false;
+    }
+
+    node.last_token()
+        .and_then(|token| token.next_token())
+        .is_some_and(|token| {
+            matches!(
+                token.kind().to_token(),
+                LuaTokenKind::TkEnd
+                    | LuaTokenKind::TkElse
+                    | LuaTokenKind::TkElseIf
+                    | LuaTokenKind::TkUntil
+            )
+        })
+}
+
+/// Check that the fragment does not interact with the tokens around it: it is
+/// surrounded by whitespace, and neither a comment on its first line can be treated
+/// as an inline comment of the previous token, nor a comment on its last line can
+/// swallow the closing keyword.
+fn is_isolated_fragment(fragment: &str) -> bool {
+    let starts_with_space = fragment.starts_with(char::is_whitespace);
+    let ends_with_space = fragment.ends_with(char::is_whitespace);
+    if !starts_with_space || !ends_with_space {
+        return false;
+    }
+
+    let first_line = fragment.split('\n').next().unwrap_or_default();
+    let last_line = fragment.rsplit('\n').next().unwrap_or_default();
+    !first_line.contains("--") && !last_line.contains("--")
+}
+
+fn shift_errors(
+    errors: &[LuaParseError],
+    old_range: TextRange,
+    new_range: TextRange,
+) -> Option<Vec<LuaParseError>> {
+    let mut result = Vec::with_capacity(errors.len());
+    for error in errors {
+        let range = error.range;
+        if range.end() <= old_range.start() {
+            result.push(error.clone());
+        } else if range.start() >= old_range.end() {
+            let start = range.start() - old_range.end() + new_range.end();
+            let mut error = error.clone();
+            error.range = TextRange::at(start, range.len());
+            result.push(error);
+        } else if !old_range.contains_range(range) {
+            return None;
+        }
+        // errors inside the old block are replaced by the errors of the new block
+    }
+
+    Some(result)
+}
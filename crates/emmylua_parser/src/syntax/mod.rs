@@ -249,5 +249,27 @@ impl<T: LuaAstNode> LuaAstPtr<T> {
     }
 }
 
+impl<T: LuaAstNode> Serialize for LuaAstPtr<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.syntax_id.serialize(serializer)
+    }
+}
+
+impl<'de, T: LuaAstNode> Deserialize<'de> for LuaAstPtr<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let syntax_id = LuaSyntaxId::deserialize(deserializer)?;
+        Ok(LuaAstPtr {
+            syntax_id,
+            _phantom: PhantomData,
+        })
+    }
+}
+
 unsafe impl<T: LuaAstNode> Send for LuaAstPtr<T> {}
 unsafe impl<T: LuaAstNode> Sync for LuaAstPtr<T> {}